@@ -0,0 +1,61 @@
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Generates `$OUT_DIR/checkpoints.rs`, which embeds every bundled Sapling tree checkpoint
+/// for the network being built, so that the library does not need access to the APK assets.
+fn write_checkpoints(out_dir: &Path) {
+    let flavor = if env::var_os("CARGO_FEATURE_MAINNET").is_some() {
+        "zcashmainnet"
+    } else {
+        "zcashtestnet"
+    };
+    let dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap())
+        .join("src")
+        .join(flavor)
+        .join("assets/zcash/saplingtree");
+    println!("cargo:rerun-if-changed={}", dir.display());
+
+    let mut files: Vec<PathBuf> = fs::read_dir(&dir)
+        .expect("checkpoint directory exists")
+        .map(|entry| entry.expect("can read checkpoint directory").path())
+        .filter(|path| path.extension().map_or(false, |ext| ext == "json"))
+        .collect();
+    files.sort();
+
+    let mut out = fs::File::create(out_dir.join("checkpoints.rs")).unwrap();
+    writeln!(out, "&[").unwrap();
+    for file in files {
+        println!("cargo:rerun-if-changed={}", file.display());
+        writeln!(out, "    include_str!({:?}),", file.display().to_string()).unwrap();
+    }
+    writeln!(out, "]").unwrap();
+}
+
+/// Regenerates the protobuf messages and gRPC stubs for lightwalletd's `CompactTxStreamer`
+/// and `DarksideStreamer` services, which are checked in under `lightwalletd/proto` so that
+/// building the `lightwalletd` feature does not need `protoc`. Enabled by the
+/// `regenerate-protos` feature, which does need `protoc` on the `PATH`.
+#[cfg(feature = "regenerate-protos")]
+fn compile_protos() {
+    println!("cargo:rerun-if-changed=src/main/proto");
+    protoc_rust_grpc::run(protoc_rust_grpc::Args {
+        out_dir: "src/main/rust/lightwalletd/proto",
+        includes: &["src/main/proto"],
+        input: &[
+            "src/main/proto/service.proto",
+            "src/main/proto/darkside.proto",
+        ],
+        rust_protobuf: true,
+        ..Default::default()
+    })
+    .expect("protoc can compile the lightwalletd protos");
+}
+
+fn main() {
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    write_checkpoints(&out_dir);
+    #[cfg(feature = "regenerate-protos")]
+    compile_protos();
+}
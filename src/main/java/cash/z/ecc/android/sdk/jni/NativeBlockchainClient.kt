@@ -0,0 +1,50 @@
+package cash.z.ecc.android.sdk.jni
+
+import cash.z.ecc.android.sdk.service.LightWalletService
+
+/**
+ * The lightwalletd calls that the Rust sync engine makes through [RustBackendWelding.syncWallet].
+ * Like [LightWalletService], implementations should make blocking calls.
+ */
+interface NativeBlockchainClient {
+    /**
+     * Return the latest block height known to the server.
+     */
+    fun getLatestBlockHeight(): Int
+
+    /**
+     * Return the serialized compact blocks in the inclusive range [start]..[end].
+     */
+    fun getBlockRange(start: Int, end: Int): Array<ByteArray>
+
+    /**
+     * Return the raw bytes of the mined transaction with the given ID, or null if it cannot be
+     * found.
+     */
+    fun fetchTransaction(txId: ByteArray): ByteArray?
+}
+
+/**
+ * Receives progress from [RustBackendWelding.syncWallet], on the thread that called it.
+ */
+interface NativeSyncListener {
+    /**
+     * Called with each status as JSON, such as `{"status":"downloading","detail":{"start":1,"end":100}}`
+     * or `{"status":"synced","detail":1000}`. The statuses are `downloading`, `validating`,
+     * `rewound`, `scanning`, `enhancing` and `synced`.
+     */
+    fun onSyncStatus(status: String)
+}
+
+/**
+ * Adapts a [LightWalletService] for the Rust sync engine.
+ */
+class LightWalletNativeClient(private val service: LightWalletService) : NativeBlockchainClient {
+    override fun getLatestBlockHeight() = service.getLatestBlockHeight()
+
+    override fun getBlockRange(start: Int, end: Int) =
+        service.getBlockRange(start..end).map { it.toByteArray() }.toTypedArray()
+
+    override fun fetchTransaction(txId: ByteArray) =
+        service.fetchTransaction(txId)?.data?.toByteArray()
+}
@@ -72,10 +72,38 @@ class RustBackend : RustBackendWelding {
         height: Int,
         hash: String,
         time: Long,
-        saplingTree: String
+        saplingTree: String,
+        expectedHeight: Int,
+        expectedRoot: String?
     ): Boolean {
         birthdayHeight = height
-        return initBlocksTable(pathDataDb, height, hash, time, saplingTree)
+        return initBlocksTable(
+            pathDataDb,
+            height,
+            hash,
+            time,
+            saplingTree,
+            expectedHeight,
+            expectedRoot
+        )
+    }
+
+    override fun initBlocksTableFromCheckpoint(checkpoint: ByteArray): Int {
+        return initBlocksTableFromCheckpoint(pathDataDb, checkpoint).also {
+            this.birthdayHeight = it
+        }
+    }
+
+    override fun initBlocksTableFromBirthday(birthdayHeight: Int): Int {
+        return initBlocksTableFromBirthday(pathDataDb, birthdayHeight).also {
+            this.birthdayHeight = it
+        }
+    }
+
+    override fun initBlocksTableFromBirthdayTime(birthdayTime: Long): Int {
+        return initBlocksTableFromBirthdayTime(pathDataDb, birthdayTime).also {
+            this.birthdayHeight = it
+        }
     }
 
     override fun getAddress(account: Int) = getAddress(pathDataDb, account)
@@ -93,6 +121,9 @@ class RustBackend : RustBackendWelding {
 
     override fun rewindToHeight(height: Int) = rewindToHeight(pathDataDb, height)
 
+    override fun handleChainReorg(maxRewind: Int, newBlocks: Array<ByteArray>) =
+        handleChainReorg(pathCacheDb, pathDataDb, maxRewind, newBlocks)
+
     override fun scanBlocks(limit: Int): Boolean {
         return if (limit > 0) {
             scanBlockBatch(pathCacheDb, pathDataDb, limit)
@@ -101,15 +132,77 @@ class RustBackend : RustBackendWelding {
         }
     }
 
+    override fun syncWallet(client: NativeBlockchainClient, listener: NativeSyncListener?) =
+        syncWallet(pathCacheDb, pathDataDb, client, listener)
+
     override fun decryptAndStoreTransaction(tx: ByteArray) = decryptAndStoreTransaction(pathDataDb, tx)
 
+    override fun getTransactionHistory(
+        chainTip: Int,
+        account: Int,
+        direction: Int,
+        minHeight: Int,
+        maxHeight: Int,
+        memoQuery: String?,
+        offset: Int,
+        limit: Int
+    ) = getTransactionHistory(
+        pathDataDb,
+        account,
+        chainTip,
+        direction,
+        minHeight,
+        maxHeight,
+        memoQuery,
+        offset,
+        limit
+    )
+
+    override fun getTransactionsToEnhance() = getTransactionsToEnhance(pathDataDb)
+
+    override fun enhanceTransactions(transactions: Array<ByteArray>, heights: IntArray) =
+        enhanceTransactions(pathDataDb, transactions, heights)
+
+    override fun parseTransaction(tx: ByteArray, decrypt: Boolean) =
+        parseTransaction(if (decrypt) pathDataDb else null, tx)
+
+    override fun getUnminedTransactions(chainTip: Int) = getUnminedTransactions(pathDataDb, chainTip)
+
+    override fun expireTransactions(chainTip: Int) = expireTransactions(pathDataDb, chainTip)
+
+    override fun getTransactionsToRebroadcast(chainTip: Int) =
+        getTransactionsToRebroadcast(pathDataDb, chainTip)
+
+    override fun consolidateNotes(
+        consensusBranchId: Long,
+        account: Int,
+        extsk: String,
+        maxInputs: Int,
+        fee: Long,
+        expiryDelta: Int
+    ): String = consolidateNotes(
+        pathDataDb,
+        consensusBranchId,
+        account,
+        extsk,
+        maxInputs,
+        fee,
+        expiryDelta,
+        "${pathParamsDir}/$SPEND_PARAM_FILE_NAME",
+        "${pathParamsDir}/$OUTPUT_PARAM_FILE_NAME"
+    )
+
     override fun createToAddress(
         consensusBranchId: Long,
         account: Int,
         extsk: String,
         to: String,
         value: Long,
-        memo: ByteArray?
+        memo: ByteArray?,
+        fee: Long,
+        expiryDelta: Int,
+        noteSelection: Int,
+        maxInputs: Int
     ): Long = createToAddress(
         pathDataDb,
         consensusBranchId,
@@ -118,10 +211,88 @@ class RustBackend : RustBackendWelding {
         to,
         value,
         memo ?: ByteArray(0),
+        fee,
+        expiryDelta,
+        noteSelection,
+        maxInputs,
+        "${pathParamsDir}/$SPEND_PARAM_FILE_NAME",
+        "${pathParamsDir}/$OUTPUT_PARAM_FILE_NAME"
+    )
+
+    override fun createUnsignedTransaction(
+        consensusBranchId: Long,
+        account: Int,
+        to: String,
+        value: Long,
+        memo: ByteArray?,
+        fee: Long,
+        expiryDelta: Int,
+        noteSelection: Int,
+        maxInputs: Int
+    ): String = createUnsignedTransaction(
+        pathDataDb,
+        consensusBranchId,
+        account,
+        to,
+        value,
+        memo ?: ByteArray(0),
+        fee,
+        expiryDelta,
+        noteSelection,
+        maxInputs
+    )
+
+    override fun proposeTransfer(
+        account: Int,
+        value: Long,
+        fee: Long,
+        noteSelection: Int,
+        maxInputs: Int
+    ) = proposeTransfer(pathDataDb, account, value, fee, noteSelection, maxInputs)
+
+    override fun getMaxSendable(account: Int, to: String, fee: Long, maxInputs: Int) =
+        getMaxSendable(pathDataDb, account, to, fee, maxInputs)
+
+    override fun sweepToAddress(
+        consensusBranchId: Long,
+        account: Int,
+        extsk: String,
+        to: String,
+        memo: ByteArray?,
+        fee: Long,
+        expiryDelta: Int,
+        maxInputs: Int
+    ): Long = sweepToAddress(
+        pathDataDb,
+        consensusBranchId,
+        account,
+        extsk,
+        to,
+        memo ?: ByteArray(0),
+        fee,
+        expiryDelta,
+        maxInputs,
         "${pathParamsDir}/$SPEND_PARAM_FILE_NAME",
         "${pathParamsDir}/$OUTPUT_PARAM_FILE_NAME"
     )
 
+    override fun signTransaction(unsignedTransaction: String, extsk: String): ByteArray =
+        signTransaction(
+            unsignedTransaction,
+            extsk,
+            "${pathParamsDir}/$SPEND_PARAM_FILE_NAME",
+            "${pathParamsDir}/$OUTPUT_PARAM_FILE_NAME"
+        )
+
+    override fun recordSignedTransaction(unsignedTransaction: String, tx: ByteArray) =
+        recordSignedTransaction(
+            pathDataDb,
+            unsignedTransaction,
+            tx,
+            "${pathParamsDir}/$SPEND_PARAM_FILE_NAME",
+            "${pathParamsDir}/$OUTPUT_PARAM_FILE_NAME"
+        )
+
     override fun deriveSpendingKeys(seed: ByteArray, numberOfAccounts: Int) =
         deriveExtendedSpendingKeys(seed, numberOfAccounts)
 
@@ -194,9 +365,26 @@ class RustBackend : RustBackendWelding {
             height: Int,
             hash: String,
             time: Long,
-            saplingTree: String
+            saplingTree: String,
+            expectedHeight: Int,
+            expectedRoot: String?
         ): Boolean
 
+        @JvmStatic private external fun initBlocksTableFromCheckpoint(
+            dbDataPath: String,
+            checkpoint: ByteArray
+        ): Int
+
+        @JvmStatic private external fun initBlocksTableFromBirthday(
+            dbDataPath: String,
+            birthdayHeight: Int
+        ): Int
+
+        @JvmStatic private external fun initBlocksTableFromBirthdayTime(
+            dbDataPath: String,
+            birthdayTime: Long
+        ): Int
+
         @JvmStatic private external fun getAddress(dbDataPath: String, account: Int): String
 
         @JvmStatic private external fun isValidShieldedAddress(addr: String): Boolean
@@ -215,12 +403,69 @@ class RustBackend : RustBackendWelding {
 
         @JvmStatic private external fun rewindToHeight(dbDataPath: String, height: Int): Boolean
 
+        @JvmStatic private external fun handleChainReorg(
+            dbCachePath: String,
+            dbDataPath: String,
+            maxRewind: Int,
+            newBlocks: Array<ByteArray>
+        ): Int
+
         @JvmStatic private external fun scanBlocks(dbCachePath: String, dbDataPath: String): Boolean
 
         @JvmStatic private external fun scanBlockBatch(dbCachePath: String, dbDataPath: String, limit: Int): Boolean
 
+        @JvmStatic private external fun syncWallet(
+            dbCachePath: String,
+            dbDataPath: String,
+            client: NativeBlockchainClient,
+            listener: NativeSyncListener?
+        ): Int
+
         @JvmStatic private external fun decryptAndStoreTransaction(dbDataPath: String, tx: ByteArray)
 
+        @JvmStatic private external fun getTransactionHistory(
+            dbDataPath: String,
+            account: Int,
+            chainTip: Int,
+            direction: Int,
+            minHeight: Int,
+            maxHeight: Int,
+            memoQuery: String?,
+            offset: Int,
+            limit: Int
+        ): String
+
+        @JvmStatic private external fun getTransactionsToEnhance(dbDataPath: String): Array<ByteArray>
+
+        @JvmStatic private external fun enhanceTransactions(
+            dbDataPath: String,
+            transactions: Array<ByteArray>,
+            heights: IntArray
+        ): Array<ByteArray>
+
+        @JvmStatic private external fun parseTransaction(dbDataPath: String?, tx: ByteArray): String
+
+        @JvmStatic private external fun getUnminedTransactions(dbDataPath: String, chainTip: Int): String
+
+        @JvmStatic private external fun expireTransactions(dbDataPath: String, chainTip: Int): LongArray
+
+        @JvmStatic private external fun getTransactionsToRebroadcast(
+            dbDataPath: String,
+            chainTip: Int
+        ): Array<ByteArray>
+
+        @JvmStatic private external fun consolidateNotes(
+            dbDataPath: String,
+            consensusBranchId: Long,
+            account: Int,
+            extsk: String,
+            maxInputs: Int,
+            fee: Long,
+            expiryDelta: Int,
+            spendParamsPath: String,
+            outputParamsPath: String
+        ): String
+
         @JvmStatic private external fun createToAddress(
             dbDataPath: String,
             consensusBranchId: Long,
@@ -229,6 +474,69 @@ class RustBackend : RustBackendWelding {
             to: String,
             value: Long,
             memo: ByteArray,
+            fee: Long,
+            expiryDelta: Int,
+            noteSelection: Int,
+            maxInputs: Int,
+            spendParamsPath: String,
+            outputParamsPath: String
+        ): Long
+
+        @JvmStatic private external fun createUnsignedTransaction(
+            dbDataPath: String,
+            consensusBranchId: Long,
+            account: Int,
+            to: String,
+            value: Long,
+            memo: ByteArray,
+            fee: Long,
+            expiryDelta: Int,
+            noteSelection: Int,
+            maxInputs: Int
+        ): String
+
+        @JvmStatic private external fun proposeTransfer(
+            dbDataPath: String,
+            account: Int,
+            value: Long,
+            fee: Long,
+            noteSelection: Int,
+            maxInputs: Int
+        ): String
+
+        @JvmStatic private external fun getMaxSendable(
+            dbDataPath: String,
+            account: Int,
+            to: String,
+            fee: Long,
+            maxInputs: Int
+        ): String
+
+        @JvmStatic private external fun sweepToAddress(
+            dbDataPath: String,
+            consensusBranchId: Long,
+            account: Int,
+            extsk: String,
+            to: String,
+            memo: ByteArray,
+            fee: Long,
+            expiryDelta: Int,
+            maxInputs: Int,
+            spendParamsPath: String,
+            outputParamsPath: String
+        ): Long
+
+        @JvmStatic private external fun signTransaction(
+            unsignedTransaction: String,
+            extsk: String,
+            spendParamsPath: String,
+            outputParamsPath: String
+        ): ByteArray
+
+        @JvmStatic private external fun recordSignedTransaction(
+            dbDataPath: String,
+            unsignedTransaction: String,
+            tx: ByteArray,
             spendParamsPath: String,
             outputParamsPath: String
         ): Long
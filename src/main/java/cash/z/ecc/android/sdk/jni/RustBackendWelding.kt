@@ -8,15 +8,40 @@ package cash.z.ecc.android.sdk.jni
  */
 interface RustBackendWelding {
 
+    fun consolidateNotes(
+        consensusBranchId: Long,
+        account: Int,
+        extsk: String,
+        maxInputs: Int,
+        fee: Long = -1,
+        expiryDelta: Int = -1
+    ): String
+
     fun createToAddress(
         consensusBranchId: Long,
         account: Int,
         extsk: String,
         to: String,
         value: Long,
-        memo: ByteArray? = byteArrayOf()
+        memo: ByteArray? = byteArrayOf(),
+        fee: Long = -1,
+        expiryDelta: Int = -1,
+        noteSelection: Int = 0,
+        maxInputs: Int = -1
     ): Long
 
+    fun createUnsignedTransaction(
+        consensusBranchId: Long,
+        account: Int,
+        to: String,
+        value: Long,
+        memo: ByteArray? = byteArrayOf(),
+        fee: Long = -1,
+        expiryDelta: Int = -1,
+        noteSelection: Int = 0,
+        maxInputs: Int = -1
+    ): String
+
     fun deriveAddress(viewingKey: String): String
 
     fun deriveAddress(seed: ByteArray, accountIndex: Int = 0): String
@@ -29,11 +54,28 @@ interface RustBackendWelding {
 
     fun decryptAndStoreTransaction(tx: ByteArray)
 
+    fun enhanceTransactions(transactions: Array<ByteArray>, heights: IntArray): Array<ByteArray>
+
+    fun expireTransactions(chainTip: Int): LongArray
+
     fun initAccountsTable(seed: ByteArray, numberOfAccounts: Int): Array<String>
 
 //    fun initAccountsTable(extfvks: Array<ByteArray>, numberOfAccounts: Int)
 
-    fun initBlocksTable(height: Int, hash: String, time: Long, saplingTree: String): Boolean
+    fun initBlocksTable(
+        height: Int,
+        hash: String,
+        time: Long,
+        saplingTree: String,
+        expectedHeight: Int = -1,
+        expectedRoot: String? = null
+    ): Boolean
+
+    fun initBlocksTableFromCheckpoint(checkpoint: ByteArray): Int
+
+    fun initBlocksTableFromBirthday(birthdayHeight: Int): Int
+
+    fun initBlocksTableFromBirthdayTime(birthdayTime: Long): Int
 
     fun initDataDb(): Boolean
 
@@ -47,16 +89,64 @@ interface RustBackendWelding {
 
     fun getBranchIdForHeight(height: Int): Long
 
+    fun getMaxSendable(account: Int, to: String, fee: Long = -1, maxInputs: Int = -1): String
+
     fun getReceivedMemoAsUtf8(idNote: Long): String
 
     fun getSentMemoAsUtf8(idNote: Long): String
 
+    fun getTransactionHistory(
+        chainTip: Int,
+        account: Int = 0,
+        direction: Int = 0,
+        minHeight: Int = -1,
+        maxHeight: Int = -1,
+        memoQuery: String? = null,
+        offset: Int = 0,
+        limit: Int = 50
+    ): String
+
+    fun getTransactionsToEnhance(): Array<ByteArray>
+
+    fun getTransactionsToRebroadcast(chainTip: Int): Array<ByteArray>
+
+    fun getUnminedTransactions(chainTip: Int): String
+
     fun getVerifiedBalance(account: Int = 0): Long
 
+    fun handleChainReorg(maxRewind: Int, newBlocks: Array<ByteArray> = emptyArray()): Int
+
+    fun parseTransaction(tx: ByteArray, decrypt: Boolean = false): String
+
+    fun proposeTransfer(
+        account: Int,
+        value: Long,
+        fee: Long = -1,
+        noteSelection: Int = 0,
+        maxInputs: Int = -1
+    ): String
+
+    fun recordSignedTransaction(unsignedTransaction: String, tx: ByteArray): Long
+
     fun rewindToHeight(height: Int): Boolean
 
     fun scanBlocks(limit: Int = -1): Boolean
 
+    fun syncWallet(client: NativeBlockchainClient, listener: NativeSyncListener? = null): Int
+
+    fun signTransaction(unsignedTransaction: String, extsk: String): ByteArray
+
+    fun sweepToAddress(
+        consensusBranchId: Long,
+        account: Int,
+        extsk: String,
+        to: String,
+        memo: ByteArray? = byteArrayOf(),
+        fee: Long = -1,
+        expiryDelta: Int = -1,
+        maxInputs: Int = -1
+    ): Long
+
     fun validateCombinedChain(): Int
 
 }
@@ -1,197 +1,447 @@
-extern crate ff;
-extern crate futures;
-extern crate grpc;
 extern crate hex;
-extern crate httpbis;
-extern crate pairing;
-extern crate protobuf;
-extern crate tls_api;
-extern crate tls_api_rustls;
+extern crate structopt;
 extern crate zcash_client_backend;
 extern crate zcash_primitives;
+extern crate zcashwalletsdk;
 
-mod service;
-mod service_grpc;
-
-use ff::{PrimeField, PrimeFieldRepr};
-use futures::Stream;
-use grpc::ClientStub;
-use httpbis::ClientTlsOption;
-use pairing::bls12_381::{Fr, FrRepr};
-use std::env;
-use std::net::ToSocketAddrs;
-use std::sync::Arc;
-use tls_api::{TlsConnector, TlsConnectorBuilder};
-use zcash_client_backend::proto::compact_formats;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use structopt::StructOpt;
+use zcash_client_backend::proto::compact_formats::CompactBlock;
 use zcash_primitives::{merkle_tree::CommitmentTree, sapling::Node};
-
-use service_grpc::CompactTxStreamer;
+use zcashwalletsdk::{
+    checkpoint::{Checkpoint, CheckpointFormat, CheckpointRegistry},
+    lightwalletd::{LightwalletdClient, LightwalletdConfig},
+    updater::{
+        load_checkpoint, print_sapling_tree, scan_blocks, write_resume_file, BlockSource, Error,
+        ScanOptions,
+    },
+};
 
 #[cfg(feature = "mainnet")]
 const START_HEIGHT: u64 = 419200;
 #[cfg(feature = "mainnet")]
-const LIGHTWALLETD_HOST: &str = "lightwalletd.z.cash";
-#[cfg(feature = "mainnet")]
 const NETWORK: &str = "mainnet";
 
 #[cfg(not(feature = "mainnet"))]
 const START_HEIGHT: u64 = 280000;
 #[cfg(not(feature = "mainnet"))]
-const LIGHTWALLETD_HOST: &str = "lightwalletd.testnet.z.cash";
-#[cfg(not(feature = "mainnet"))]
 const NETWORK: &str = "testnet";
 
-const LIGHTWALLETD_PORT: u16 = 9067;
-const BATCH_SIZE: u64 = 10_000;
+const RESUME_INTERVAL: u64 = 1_000;
 const TARGET_HEIGHT: u64 = 810000;
 
-#[derive(Debug)]
-enum Error {
-    InvalidBlock,
-    Grpc(grpc::Error),
-    Io(std::io::Error),
-    TlsApi(tls_api::Error),
+/// Creates a Sapling tree checkpoint from compact blocks, downloaded from lightwalletd or read
+/// from disk.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "update-sapling-tree")]
+struct Opts {
+    /// Height of the checkpoint to create [default: 810000]
+    target_height: Option<u64>,
+
+    /// Host name of the lightwalletd server [default: the public server for this network]
+    #[structopt(long)]
+    host: Option<String>,
+
+    /// Port of the lightwalletd server [default: 9067]
+    #[structopt(long)]
+    port: Option<u16>,
+
+    /// Connect without TLS, e.g. to a local test server
+    #[structopt(long)]
+    plaintext: bool,
+
+    /// Seconds to wait for each response or streamed block from the server [default: 60]
+    #[structopt(long)]
+    timeout: Option<u64>,
+
+    /// Read blocks from a file of length-delimited CompactBlock protobufs instead of lightwalletd
+    #[structopt(
+        long,
+        parse(from_os_str),
+        conflicts_with_all = &["cache-db", "host", "port", "plaintext", "timeout"]
+    )]
+    block_file: Option<PathBuf>,
+
+    /// Read blocks from a cache DB with a compactblocks table instead of lightwalletd
+    #[structopt(
+        long,
+        parse(from_os_str),
+        conflicts_with_all = &["host", "port", "plaintext", "timeout"]
+    )]
+    cache_db: Option<PathBuf>,
+
+    /// Height to start scanning from [default: Sapling activation height]
+    #[structopt(long, conflicts_with = "from")]
+    start_height: Option<u64>,
+
+    /// Existing checkpoint JSON file to continue from, instead of starting from an empty tree
+    #[structopt(long, parse(from_os_str))]
+    from: Option<PathBuf>,
+
+    /// Also write a checkpoint every N blocks into --checkpoint-dir
+    #[structopt(long, requires = "checkpoint-dir")]
+    interval: Option<u64>,
+
+    /// Directory to write interval checkpoints to, as <height>.<format extension>
+    #[structopt(long, parse(from_os_str))]
+    checkpoint_dir: Option<PathBuf>,
+
+    /// Number of blocks to request from the server at a time [default: 10000]
+    #[structopt(long, parse(try_from_str = parse_batch_size))]
+    batch_size: Option<u64>,
+
+    /// Number of times to request missing blocks again before giving up [default: 3]
+    #[structopt(long)]
+    missing_block_retries: Option<u32>,
+
+    /// Number of times to retry a batch that fails with a server error, with exponential
+    /// backoff [default: 5]
+    #[structopt(long)]
+    batch_retries: Option<u32>,
+
+    /// File to save progress to every 1000 blocks, for continuing an interrupted run
+    #[structopt(long, parse(from_os_str))]
+    resume_file: Option<PathBuf>,
+
+    /// Continue from --resume-file if it exists, instead of from --from or --start-height
+    #[structopt(long, requires = "resume-file")]
+    resume: bool,
+
+    /// File to write the checkpoint to [default: stdout]
+    #[structopt(short, long, parse(from_os_str))]
+    output: Option<PathBuf>,
+
+    /// Format of the checkpoints written: json, kotlin or binary [default: json]
+    #[structopt(long)]
+    format: Option<CheckpointFormat>,
+
+    #[structopt(subcommand)]
+    cmd: Option<Command>,
 }
 
-impl From<grpc::Error> for Error {
-    fn from(e: grpc::Error) -> Self {
-        Error::Grpc(e)
+impl Opts {
+    fn scan_options(&self) -> ScanOptions {
+        let defaults = ScanOptions::default();
+        ScanOptions {
+            batch_size: self.batch_size.unwrap_or(defaults.batch_size),
+            missing_block_retries: self
+                .missing_block_retries
+                .unwrap_or(defaults.missing_block_retries),
+            batch_retries: self.batch_retries.unwrap_or(defaults.batch_retries),
+            ..defaults
+        }
     }
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::Io(e)
+/// Parses `--batch-size`, which must be positive for the scan to make progress.
+fn parse_batch_size(s: &str) -> Result<u64, String> {
+    match s.parse::<u64>() {
+        Ok(0) => Err("batch size must be greater than zero".to_owned()),
+        Ok(size) => Ok(size),
+        Err(e) => Err(e.to_string()),
     }
 }
 
-impl From<tls_api::Error> for Error {
-    fn from(e: tls_api::Error) -> Self {
-        Error::TlsApi(e)
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// Recomputes the Sapling tree from activation, --start-height or --from and checks
+    /// existing checkpoints against it
+    Verify {
+        /// Checkpoint files to verify [default: the checkpoints bundled into the library]
+        #[structopt(parse(from_os_str))]
+        checkpoints: Vec<PathBuf>,
+    },
+}
+
+fn write_checkpoint_file(
+    dir: &Path,
+    format: CheckpointFormat,
+    height: u64,
+    hash: &[u8],
+    time: u32,
+    tree: &CommitmentTree<Node>,
+) -> Result<(), Error> {
+    let path = dir.join(format!("{}.{}", height, format.extension()));
+    print_sapling_tree(File::create(&path)?, format, height, hash, time, tree)?;
+    eprintln!("wrote checkpoint to {}", path.display());
+    Ok(())
+}
+
+fn open_source(opts: &Opts) -> Result<BlockSource, Error> {
+    if let Some(ref path) = opts.block_file {
+        eprintln!("reading blocks from {}", path.display());
+        BlockSource::block_file(path)
+    } else if let Some(ref path) = opts.cache_db {
+        eprintln!("reading blocks from cache DB {}", path.display());
+        BlockSource::cache_db(path)
+    } else {
+        let mut config = LightwalletdConfig::default();
+        if let Some(ref host) = opts.host {
+            config.host = host.clone();
+        }
+        if let Some(port) = opts.port {
+            config.port = port;
+        }
+        config.tls = !opts.plaintext;
+        if let Some(timeout) = opts.timeout {
+            config.timeout = Duration::from_secs(timeout);
+        }
+        eprintln!(
+            "connecting to {}:{}{}",
+            config.host,
+            config.port,
+            if opts.plaintext { " (plaintext)" } else { "" }
+        );
+        Ok(BlockSource::Lightwalletd(LightwalletdClient::connect(
+            &config,
+        )?))
     }
 }
 
-fn print_sapling_tree(height: u64, mut hash: Vec<u8>, time: u32, tree: CommitmentTree<Node>) {
-    hash.reverse();
-    let mut tree_bytes = vec![];
-    tree.write(&mut tree_bytes).expect("can write into Vec");
-    println!("{{");
-    println!("  \"network\": \"{}\",", NETWORK);
-    println!("  \"height\": {},", height);
-    println!("  \"hash\": \"{}\",", hex::encode(hash));
-    println!("  \"time\": {},", time);
-    println!("  \"tree\": \"{}\"", hex::encode(tree_bytes));
-    println!("}}");
+/// Returns the height to start scanning from, the hash of the block before it if known, and
+/// the tree to extend: from `checkpoint` if given, and otherwise from `--start-height` (by
+/// default Sapling activation) with an empty tree.
+fn scan_start(
+    opts: &Opts,
+    checkpoint: Option<&PathBuf>,
+) -> Result<(u64, Option<Vec<u8>>, CommitmentTree<Node>), Error> {
+    match checkpoint {
+        Some(path) => {
+            let (height, hash, tree) = load_checkpoint(path)?;
+            eprintln!("continuing from checkpoint at height {}", height);
+            Ok((height + 1, Some(hash), tree))
+        }
+        None => Ok((
+            opts.start_height.unwrap_or(START_HEIGHT),
+            None,
+            CommitmentTree::new(),
+        )),
+    }
 }
 
-fn main() -> Result<(), Error> {
-    let args: Vec<String> = env::args().collect();
-    let mut target_height = TARGET_HEIGHT;
-
-    if args.len() > 1 {
-        let target = &args[1];
-        match target.parse() {
-            Ok(n) => target_height = n,
-            Err(_) => {
-                eprintln!(
-                    "warning: expected target height as the first argument but found '{}'\
-                     instead. Falling back to the default target height of {}.",
-                    target, target_height
-                );
-            }
-        };
+/// Creates a checkpoint at the target height.
+fn update(opts: &Opts) -> Result<(), Error> {
+    let target_height = opts.target_height.unwrap_or(TARGET_HEIGHT);
+    let format = opts.format.unwrap_or(CheckpointFormat::Json);
+
+    // Continue an interrupted run if asked to and there is one
+    let resume_from = match opts.resume_file {
+        Some(ref path) if opts.resume && path.exists() => Some(path),
+        _ => None,
+    };
+
+    let (start_height, prev_hash, mut tree) =
+        scan_start(opts, resume_from.or_else(|| opts.from.as_ref()))?;
+    if let Some(ref dir) = opts.checkpoint_dir {
+        fs::create_dir_all(dir)?;
+    }
+    if opts.interval == Some(0) {
+        return Err(Error::InvalidArgument(
+            "checkpoint interval must be greater than zero".to_owned(),
+        ));
+    }
+    if start_height > target_height {
+        return Err(Error::InvalidArgument(format!(
+            "start height {} is above target height {}",
+            start_height, target_height
+        )));
     }
 
-    println!(
+    eprintln!(
         "creating {} checkpoint for range {}..{}",
-        NETWORK, START_HEIGHT, target_height
+        NETWORK, start_height, target_height
     );
-    println!("connecting to {}:{}", LIGHTWALLETD_HOST, LIGHTWALLETD_PORT);
-
-    // For now, start from Sapling activation height
-    let mut start_height = START_HEIGHT;
-    let mut tree = CommitmentTree::new();
-
-    // If LIGHTWALLETD_HOST is behind a load balancer which resolves to multiple IP
-    // addresses, we need to select one ourselves and then use it directly, as a
-    // workaround for https://github.com/stepancheg/rust-http2/issues/7
-    let socket = (LIGHTWALLETD_HOST, LIGHTWALLETD_PORT)
-        .to_socket_addrs()?
-        .into_iter()
-        .next()
-        .expect("LIGHTWALLETD_HOST can be resolved");
-
-    let tls = {
-        let mut tls_connector = tls_api_rustls::TlsConnector::builder()?;
-
-        if tls_api_rustls::TlsConnector::supports_alpn() {
-            tls_connector.set_alpn_protocols(&[b"h2"])?;
+    let mut source = open_source(opts)?;
+
+    let mut end_hash = vec![];
+    let mut end_time = 0;
+    scan_blocks(
+        &mut source,
+        &opts.scan_options(),
+        start_height,
+        target_height,
+        prev_hash,
+        &mut tree,
+        |block, tree| {
+            if let (Some(interval), Some(dir)) = (opts.interval, opts.checkpoint_dir.as_ref()) {
+                if block.height % interval == 0 {
+                    write_checkpoint_file(
+                        dir,
+                        format,
+                        block.height,
+                        &block.hash,
+                        block.time,
+                        tree,
+                    )?;
+                }
+            }
+            if let Some(ref path) = opts.resume_file {
+                if block.height % RESUME_INTERVAL == 0 {
+                    write_resume_file(path, block.height, &block.hash, block.time, tree)?;
+                }
+            }
+            end_hash = block.hash.clone();
+            end_time = block.time;
+            Ok(())
+        },
+    )?;
+
+    match opts.output {
+        Some(ref path) => {
+            print_sapling_tree(
+                File::create(path)?,
+                format,
+                target_height,
+                &end_hash,
+                end_time,
+                &tree,
+            )?;
+            eprintln!("wrote checkpoint to {}", path.display());
         }
+        None => print_sapling_tree(
+            io::stdout(),
+            format,
+            target_height,
+            &end_hash,
+            end_time,
+            &tree,
+        )?,
+    }
 
-        let tls_connector = tls_connector.build()?;
+    // The run is complete, so there is nothing left to resume.
+    if let Some(ref path) = opts.resume_file {
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+    }
+    Ok(())
+}
 
-        let tls_connector = Arc::new(tls_connector);
-        ClientTlsOption::Tls(LIGHTWALLETD_HOST.to_owned(), tls_connector)
+/// Recomputes the tree from Sapling activation, or from `--start-height` or `--from`, and
+/// compares it with each checkpoint, stopping at the first mismatch.
+fn verify(opts: &Opts, files: &[PathBuf]) -> Result<(), Error> {
+    let mut checkpoints = if files.is_empty() {
+        CheckpointRegistry::bundled()
+            .map_err(Error::InvalidCheckpoint)?
+            .checkpoints()
+            .to_vec()
+    } else {
+        files
+            .iter()
+            .map(|path| Checkpoint::read(&fs::read(path)?).map_err(Error::InvalidCheckpoint))
+            .collect::<Result<Vec<_>, _>>()?
     };
+    checkpoints.sort_by_key(|c| c.height);
+    let last_height = match checkpoints.last() {
+        Some(checkpoint) => checkpoint.height as u64,
+        None => {
+            return Err(Error::InvalidArgument(
+                "no checkpoints to verify".to_owned(),
+            ))
+        }
+    };
+    if let Some(pair) = checkpoints
+        .windows(2)
+        .find(|pair| pair[0].height == pair[1].height)
+    {
+        return Err(Error::InvalidArgument(format!(
+            "more than one checkpoint at height {}",
+            pair[0].height
+        )));
+    }
 
-    let client_conf = Default::default();
-    let client = grpc::Client::new_expl::<tls_api_rustls::TlsConnector>(
-        &socket,
-        LIGHTWALLETD_HOST,
-        tls,
-        client_conf,
-    )
-    .map(|c| service_grpc::CompactTxStreamerClient::with_client(Arc::new(c)))?;
-
-    loop {
-        // Get the latest height
-        let latest_height = target_height;
-        let end_height = if latest_height - start_height < BATCH_SIZE {
-            latest_height
-        } else {
-            start_height + BATCH_SIZE - 1
-        };
-
-        // Request the next batch of blocks
-        println!("Fetching blocks {}..{}", start_height, end_height);
-        let mut start = service::BlockID::new();
-        start.set_height(start_height);
-        let mut end = service::BlockID::new();
-        end.set_height(end_height);
-        let mut range = service::BlockRange::new();
-        range.set_start(start);
-        range.set_end(end);
-        let blocks = client
-            .get_block_range(grpc::RequestOptions::new(), range)
-            .drop_metadata()
-            .wait();
-
-        let mut end_hash = vec![];
-        let mut end_time = 0;
-        let mut parsed = 0;
-        for block in blocks {
-            let block = block?;
-            end_hash = block.hash;
-            end_time = block.time;
-            for tx in block.vtx.iter() {
-                for output in tx.outputs.iter() {
-                    // Append commitment to tree
-                    let mut repr = FrRepr::default();
-                    repr.read_le(&output.cmu[..])?;
-                    let cmu = Fr::from_repr(repr).map_err(|_| Error::InvalidBlock)?;
-                    let node = Node::new(cmu.into_repr());
-                    tree.append(node).expect("tree is not full");
+    let (start_height, prev_hash, mut tree) = scan_start(opts, opts.from.as_ref())?;
+    if (checkpoints[0].height as u64) < start_height {
+        return Err(Error::InvalidArgument(format!(
+            "checkpoint at height {} is below the start height {}",
+            checkpoints[0].height, start_height
+        )));
+    }
+
+    eprintln!(
+        "verifying {} {} checkpoints for range {}..{}",
+        checkpoints.len(),
+        NETWORK,
+        start_height,
+        last_height
+    );
+    let mut source = open_source(opts)?;
+
+    let mut next = 0;
+    scan_blocks(
+        &mut source,
+        &opts.scan_options(),
+        start_height,
+        last_height,
+        prev_hash,
+        &mut tree,
+        |block, tree| {
+            // Every height is scanned, so the checkpoints are reached in order.
+            if let Some(checkpoint) = checkpoints.get(next) {
+                if checkpoint.height as u64 == block.height {
+                    verify_checkpoint(checkpoint, block, tree)?;
+                    eprintln!("checkpoint at height {} is correct", checkpoint.height);
+                    next += 1;
                 }
             }
-            parsed += 1
-        }
-        println!("Parsed {} blocks", parsed);
+            Ok(())
+        },
+    )?;
 
-        if end_height == latest_height {
-            print_sapling_tree(end_height, end_hash, end_time, tree);
-            break Ok(());
-        } else {
-            start_height = end_height + 1
-        }
+    if next != checkpoints.len() {
+        return Err(Error::CheckpointMismatch(format!(
+            "checkpoint at height {} was not reached",
+            checkpoints[next].height
+        )));
+    }
+    eprintln!("all checkpoints are correct");
+    Ok(())
+}
+
+fn verify_checkpoint(
+    checkpoint: &Checkpoint,
+    block: &CompactBlock,
+    tree: &CommitmentTree<Node>,
+) -> Result<(), Error> {
+    let mismatch = |field: &str, stored: String, computed: String| -> Result<(), Error> {
+        Err(Error::CheckpointMismatch(format!(
+            "{} of checkpoint at height {} is {}, but the chain has {}",
+            field, checkpoint.height, stored, computed
+        )))
+    };
+
+    let hash = checkpoint
+        .block_hash()
+        .map_err(Error::InvalidCheckpoint)?
+        .0
+        .to_vec();
+    if hash != block.hash {
+        let mut actual = block.hash.clone();
+        actual.reverse();
+        return mismatch("hash", checkpoint.hash.clone(), hex::encode(actual));
+    }
+    if checkpoint.time != block.time {
+        return mismatch("time", checkpoint.time.to_string(), block.time.to_string());
+    }
+    let mut tree_bytes = vec![];
+    tree.write(&mut tree_bytes)?;
+    if checkpoint
+        .sapling_tree()
+        .map_err(Error::InvalidCheckpoint)?
+        != tree_bytes
+    {
+        return mismatch("tree", checkpoint.tree.clone(), hex::encode(tree_bytes));
+    }
+    Ok(())
+}
+
+fn main() -> Result<(), Error> {
+    let opts = Opts::from_args();
+    match opts.cmd {
+        Some(Command::Verify { ref checkpoints }) => verify(&opts, checkpoints),
+        None => update(&opts),
     }
 }
@@ -0,0 +1,576 @@
+//! Sapling tree checkpoints used to initialize the blocks table.
+//!
+//! Checkpoints are the JSON files printed by the `update-sapling-tree` tool and shipped in
+//! `assets/zcash/saplingtree/`. They are embedded into the library at build time, so that a
+//! wallet can be restored from just a birthday height or time.
+//!
+//! Besides the JSON shape, a checkpoint can be written as a Kotlin snippet or in a compact
+//! binary form (see [`CheckpointFormat`]), and read back from any of them.
+
+use failure::{format_err, Error};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+use zcash_client_sqlite::init::init_blocks_table;
+use zcash_primitives::{
+    block::BlockHash,
+    consensus::{NetworkUpgrade, Parameters},
+    merkle_tree::{CommitmentTree, Hashable},
+    sapling::Node,
+};
+
+use crate::Network;
+
+#[cfg(feature = "mainnet")]
+pub const NETWORK: &str = "mainnet";
+#[cfg(not(feature = "mainnet"))]
+pub const NETWORK: &str = "testnet";
+
+/// Magic bytes at the start of the binary format.
+const BINARY_MAGIC: &[u8; 4] = b"ZCKP";
+const BINARY_VERSION: u8 = 1;
+
+/// The checkpoint files bundled for the network this library was built for.
+const BUNDLED_CHECKPOINTS: &[&str] = include!(concat!(env!("OUT_DIR"), "/checkpoints.rs"));
+
+/// The encodings a checkpoint can be written in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CheckpointFormat {
+    /// The JSON shape of the bundled asset files.
+    Json,
+    /// A Kotlin `WalletBirthday` constructor call, for pasting into SDK or app sources.
+    Kotlin,
+    /// A compact binary form that also carries the tree root and the number of Sapling note
+    /// commitments in the tree, both of which are checked when it is read.
+    Binary,
+}
+
+impl CheckpointFormat {
+    /// The file extension conventionally used for this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            CheckpointFormat::Json => "json",
+            CheckpointFormat::Kotlin => "kt",
+            CheckpointFormat::Binary => "bin",
+        }
+    }
+}
+
+impl FromStr for CheckpointFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match s {
+            "json" => Ok(CheckpointFormat::Json),
+            "kotlin" => Ok(CheckpointFormat::Kotlin),
+            "binary" => Ok(CheckpointFormat::Binary),
+            _ => Err(format_err!(
+                "Unknown checkpoint format {}, expected json, kotlin or binary",
+                s
+            )),
+        }
+    }
+}
+
+/// A Sapling commitment tree state at a given block, in the format of the bundled JSON files.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Checkpoint {
+    pub network: String,
+    pub height: i32,
+    /// The block hash, hex-encoded in RPC (big-endian) byte order.
+    pub hash: String,
+    pub time: u32,
+    /// The serialized `CommitmentTree`, hex-encoded.
+    pub tree: String,
+}
+
+impl Checkpoint {
+    /// Creates a checkpoint for this network from a block and the tree as of that block.
+    ///
+    /// `hash` is in the internal (little-endian) byte order used by compact blocks.
+    pub fn from_tree(height: i32, hash: &[u8], time: u32, tree: &CommitmentTree<Node>) -> Self {
+        let mut hash = hash.to_vec();
+        hash.reverse();
+        let mut tree_bytes = vec![];
+        tree.write(&mut tree_bytes).expect("can write into Vec");
+        Checkpoint {
+            network: NETWORK.to_owned(),
+            height,
+            hash: hex::encode(hash),
+            time,
+            tree: hex::encode(tree_bytes),
+        }
+    }
+
+    /// Parses a checkpoint from its JSON representation and checks that it is usable.
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let checkpoint: Checkpoint = serde_json::from_str(json)?;
+        checkpoint.validate()?;
+        Ok(checkpoint)
+    }
+
+    /// Parses a checkpoint in any of the [`CheckpointFormat`]s, detecting which one it is.
+    pub fn read(data: &[u8]) -> Result<Self, Error> {
+        let format = if data.starts_with(BINARY_MAGIC) {
+            CheckpointFormat::Binary
+        } else if String::from_utf8_lossy(data).trim_start().starts_with('{') {
+            CheckpointFormat::Json
+        } else {
+            CheckpointFormat::Kotlin
+        };
+        Checkpoint::parse(format, data)
+    }
+
+    /// Parses a checkpoint in the given format and checks that it is usable.
+    pub fn parse(format: CheckpointFormat, data: &[u8]) -> Result<Self, Error> {
+        match format {
+            CheckpointFormat::Json => Checkpoint::from_json(std::str::from_utf8(data)?),
+            CheckpointFormat::Kotlin => Checkpoint::from_kotlin(std::str::from_utf8(data)?),
+            CheckpointFormat::Binary => Checkpoint::from_binary(data),
+        }
+    }
+
+    /// Writes this checkpoint in the given format.
+    pub fn write<W: Write>(&self, format: CheckpointFormat, mut out: W) -> Result<(), Error> {
+        match format {
+            CheckpointFormat::Json => {
+                serde_json::to_writer_pretty(&mut out, self)?;
+                writeln!(out)?;
+            }
+            CheckpointFormat::Kotlin => {
+                writeln!(out, "// network = {:?}", self.network)?;
+                writeln!(out, "WalletBirthday(")?;
+                writeln!(out, "    height = {},", self.height)?;
+                writeln!(out, "    hash = {:?},", self.hash)?;
+                writeln!(out, "    time = {}L,", self.time)?;
+                writeln!(out, "    tree = {:?}", self.tree)?;
+                writeln!(out, ")")?;
+            }
+            CheckpointFormat::Binary => {
+                let tree = self.commitment_tree()?;
+                let tree_bytes = self.sapling_tree()?;
+                let mut root = vec![];
+                tree.root().write(&mut root)?;
+
+                out.write_all(BINARY_MAGIC)?;
+                out.write_all(&[BINARY_VERSION, network_id(&self.network)?])?;
+                out.write_all(&(self.height as u32).to_le_bytes())?;
+                out.write_all(&self.block_hash()?.0)?;
+                out.write_all(&self.time.to_le_bytes())?;
+                out.write_all(&(tree.size() as u64).to_le_bytes())?;
+                out.write_all(&root)?;
+                out.write_all(&(tree_bytes.len() as u32).to_le_bytes())?;
+                out.write_all(&tree_bytes)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses the Kotlin snippet written by [`Checkpoint::write`].
+    fn from_kotlin(source: &str) -> Result<Self, Error> {
+        if !source.contains("WalletBirthday(") {
+            return Err(format_err!("Checkpoint snippet has no WalletBirthday call"));
+        }
+        let mut network = None;
+        let mut height = None;
+        let mut hash = None;
+        let mut time = None;
+        let mut tree = None;
+        for line in source.lines() {
+            let line = line.trim().trim_start_matches("//").trim_end_matches(',');
+            let mut parts = line.splitn(2, '=');
+            let (name, value) = match (parts.next(), parts.next()) {
+                (Some(name), Some(value)) => (name.trim(), value.trim()),
+                _ => continue,
+            };
+            let value = value.trim_matches('"').trim_end_matches('L');
+            match name {
+                "network" => network = Some(value.to_owned()),
+                "height" => height = Some(value.parse()?),
+                "hash" => hash = Some(value.to_owned()),
+                "time" => time = Some(value.parse()?),
+                "tree" => tree = Some(value.to_owned()),
+                _ => (),
+            }
+        }
+        let missing = |field| format_err!("Checkpoint snippet has no {}", field);
+        let checkpoint = Checkpoint {
+            network: network.ok_or_else(|| missing("network"))?,
+            height: height.ok_or_else(|| missing("height"))?,
+            hash: hash.ok_or_else(|| missing("hash"))?,
+            time: time.ok_or_else(|| missing("time"))?,
+            tree: tree.ok_or_else(|| missing("tree"))?,
+        };
+        checkpoint.validate()?;
+        Ok(checkpoint)
+    }
+
+    /// Parses the binary form written by [`Checkpoint::write`], checking the tree root and
+    /// note commitment count against the tree.
+    fn from_binary(data: &[u8]) -> Result<Self, Error> {
+        let mut reader = BinaryReader { data };
+        if reader.take(4)? != BINARY_MAGIC {
+            return Err(format_err!("Not a binary checkpoint"));
+        }
+        let version = reader.take(1)?[0];
+        if version != BINARY_VERSION {
+            return Err(format_err!(
+                "Unsupported binary checkpoint version {}",
+                version
+            ));
+        }
+        let network = match reader.take(1)?[0] {
+            0 => "mainnet",
+            1 => "testnet",
+            id => return Err(format_err!("Unknown network id {} in checkpoint", id)),
+        };
+        let height = reader.u32()? as i32;
+        let mut hash = reader.take(32)?.to_vec();
+        hash.reverse();
+        let time = reader.u32()?;
+        let size = reader.u64()?;
+        let root = reader.take(32)?.to_vec();
+        let tree_len = reader.u32()? as usize;
+        let tree = reader.take(tree_len)?.to_vec();
+        if !reader.data.is_empty() {
+            return Err(format_err!(
+                "Invalid binary checkpoint at height {}: {} trailing bytes",
+                height,
+                reader.data.len()
+            ));
+        }
+
+        let checkpoint = Checkpoint {
+            network: network.to_owned(),
+            height,
+            hash: hex::encode(hash),
+            time,
+            tree: hex::encode(tree),
+        };
+        checkpoint.validate()?;
+        let commitment_tree = checkpoint.commitment_tree()?;
+        if commitment_tree.size() as u64 != size {
+            return Err(format_err!(
+                "Checkpoint at height {} claims {} note commitments, but its tree has {}",
+                height,
+                size,
+                commitment_tree.size()
+            ));
+        }
+        if checkpoint.tree_root()? != hex::encode(root) {
+            return Err(format_err!(
+                "Checkpoint at height {} has a tree root that does not match its tree",
+                height
+            ));
+        }
+        Ok(checkpoint)
+    }
+
+    /// Checks that this checkpoint is for the right network, that its height is not before
+    /// Sapling activation, and that its hash and tree decode.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.network != NETWORK {
+            return Err(format_err!(
+                "Checkpoint at height {} is for {}, expected {}",
+                self.height,
+                self.network,
+                NETWORK
+            ));
+        }
+        let sapling_activation = Network::activation_height(NetworkUpgrade::Sapling)
+            .expect("Sapling activation height must be known")
+            as i32;
+        if self.height < sapling_activation {
+            return Err(format_err!(
+                "Checkpoint height {} is below Sapling activation height {}",
+                self.height,
+                sapling_activation
+            ));
+        }
+        self.block_hash()?;
+        self.sapling_tree()?;
+        Ok(())
+    }
+
+    /// Returns the block hash in the internal (little-endian) byte order.
+    pub fn block_hash(&self) -> Result<BlockHash, Error> {
+        let mut hash = hex::decode(&self.hash)
+            .map_err(|e| format_err!("Invalid checkpoint hash at height {}: {}", self.height, e))?;
+        if hash.len() != 32 {
+            return Err(format_err!(
+                "Invalid checkpoint hash at height {}: expected 32 bytes, found {}",
+                self.height,
+                hash.len()
+            ));
+        }
+        hash.reverse();
+        Ok(BlockHash::from_slice(&hash))
+    }
+
+    /// Returns the serialized Sapling tree, after checking that it deserializes.
+    pub fn sapling_tree(&self) -> Result<Vec<u8>, Error> {
+        self.decode_tree().map(|(bytes, _)| bytes)
+    }
+
+    /// Returns the deserialized Sapling tree.
+    pub fn commitment_tree(&self) -> Result<CommitmentTree<Node>, Error> {
+        self.decode_tree().map(|(_, tree)| tree)
+    }
+
+    /// Returns the root of the Sapling tree, hex-encoded in its serialized byte order.
+    pub fn tree_root(&self) -> Result<String, Error> {
+        let tree = self.commitment_tree()?;
+        let mut root = vec![];
+        tree.root().write(&mut root)?;
+        Ok(hex::encode(root))
+    }
+
+    /// Checks this checkpoint against values the caller already knows, such as the height
+    /// encoded in an asset's file name or a tree root obtained from a trusted source.
+    pub fn check_expected(
+        &self,
+        expected_height: Option<i32>,
+        expected_root: Option<&str>,
+    ) -> Result<(), Error> {
+        if let Some(expected_height) = expected_height {
+            if self.height != expected_height {
+                return Err(format_err!(
+                    "Checkpoint height {} does not match expected height {}",
+                    self.height,
+                    expected_height
+                ));
+            }
+        }
+        if let Some(expected_root) = expected_root {
+            let root = self.tree_root()?;
+            if !root.eq_ignore_ascii_case(expected_root) {
+                return Err(format_err!(
+                    "Sapling tree root {} at height {} does not match expected root {}",
+                    root,
+                    self.height,
+                    expected_root
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn decode_tree(&self) -> Result<(Vec<u8>, CommitmentTree<Node>), Error> {
+        let bytes = hex::decode(&self.tree)
+            .map_err(|e| format_err!("Invalid checkpoint tree at height {}: {}", self.height, e))?;
+        let mut reader = &bytes[..];
+        let tree = CommitmentTree::<Node>::read(&mut reader)
+            .map_err(|e| format_err!("Invalid checkpoint tree at height {}: {}", self.height, e))?;
+        if !reader.is_empty() {
+            return Err(format_err!(
+                "Invalid checkpoint tree at height {}: {} trailing bytes",
+                self.height,
+                reader.len()
+            ));
+        }
+        Ok((bytes, tree))
+    }
+}
+
+fn network_id(network: &str) -> Result<u8, Error> {
+    match network {
+        "mainnet" => Ok(0),
+        "testnet" => Ok(1),
+        _ => Err(format_err!("Unknown network {}", network)),
+    }
+}
+
+/// Reads fixed-size fields from the binary checkpoint format.
+struct BinaryReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> BinaryReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.data.len() < len {
+            return Err(format_err!("Binary checkpoint is truncated"));
+        }
+        let (field, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(field)
+    }
+
+    fn u32(&mut self) -> Result<u32, Error> {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(self.take(4)?);
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    fn u64(&mut self) -> Result<u64, Error> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(bytes))
+    }
+}
+
+/// A set of checkpoints, ordered by height.
+pub struct CheckpointRegistry {
+    checkpoints: Vec<Checkpoint>,
+}
+
+impl CheckpointRegistry {
+    fn new(mut checkpoints: Vec<Checkpoint>) -> Result<Self, Error> {
+        if checkpoints.is_empty() {
+            return Err(format_err!("No {} checkpoints are available", NETWORK));
+        }
+        checkpoints.sort_by_key(|c| c.height);
+        Ok(CheckpointRegistry { checkpoints })
+    }
+
+    /// Loads the checkpoints that were embedded into the library at build time.
+    pub fn bundled() -> Result<Self, Error> {
+        let checkpoints = BUNDLED_CHECKPOINTS
+            .iter()
+            .map(|json| Checkpoint::from_json(json))
+            .collect::<Result<_, _>>()?;
+        CheckpointRegistry::new(checkpoints)
+    }
+
+    /// Returns all checkpoints, lowest first.
+    pub fn checkpoints(&self) -> &[Checkpoint] {
+        &self.checkpoints
+    }
+
+    /// Returns the highest checkpoint at or below the given height.
+    pub fn at_or_below_height(&self, height: i32) -> Option<&Checkpoint> {
+        self.checkpoints.iter().rev().find(|c| c.height <= height)
+    }
+
+    /// Returns the highest checkpoint whose block time is at or before the given UNIX time.
+    pub fn at_or_before_time(&self, time: u32) -> Option<&Checkpoint> {
+        self.checkpoints.iter().rev().find(|c| c.time <= time)
+    }
+}
+
+/// Initializes the blocks table of the data DB from the given checkpoint.
+pub(crate) fn init_blocks_table_from_checkpoint<P: AsRef<Path>>(
+    db_data: P,
+    checkpoint: &Checkpoint,
+) -> Result<(), Error> {
+    debug!(
+        "initializing blocks table from checkpoint at height {}",
+        checkpoint.height
+    );
+    init_blocks_table(
+        db_data,
+        checkpoint.height,
+        checkpoint.block_hash()?,
+        checkpoint.time,
+        &checkpoint.sapling_tree()?,
+    )
+    .map_err(|e| format_err!("Error while initializing blocks table: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Checkpoint, CheckpointFormat, CheckpointRegistry, NETWORK};
+
+    #[test]
+    fn bundled_checkpoints_are_valid() {
+        CheckpointRegistry::bundled().unwrap();
+    }
+
+    #[test]
+    fn selects_nearest_checkpoint_below() {
+        let registry = CheckpointRegistry::bundled().unwrap();
+        let first = &registry.checkpoints[0];
+        let second = &registry.checkpoints[1];
+
+        assert_eq!(registry.at_or_below_height(first.height - 1), None);
+        assert_eq!(registry.at_or_below_height(first.height), Some(first));
+        assert_eq!(registry.at_or_below_height(second.height - 1), Some(first));
+        assert_eq!(registry.at_or_before_time(second.time), Some(second));
+        assert_eq!(registry.at_or_before_time(second.time - 1), Some(first));
+    }
+
+    #[test]
+    fn rejects_wrong_network() {
+        let registry = CheckpointRegistry::bundled().unwrap();
+        let mut checkpoint = registry.checkpoints[0].clone();
+        checkpoint.network = format!("not-{}", NETWORK);
+        let json = serde_json::to_string(&checkpoint).unwrap();
+        assert!(Checkpoint::from_json(&json).is_err());
+    }
+
+    #[test]
+    fn checks_expected_values() {
+        let registry = CheckpointRegistry::bundled().unwrap();
+        let checkpoint = registry.checkpoints.last().unwrap();
+        let root = checkpoint.tree_root().unwrap();
+
+        assert!(checkpoint
+            .check_expected(Some(checkpoint.height), Some(&root))
+            .is_ok());
+        assert!(checkpoint
+            .check_expected(Some(checkpoint.height + 1), None)
+            .is_err());
+
+        let other_root = registry.checkpoints[0].tree_root().unwrap();
+        assert!(checkpoint.check_expected(None, Some(&other_root)).is_err());
+    }
+
+    #[test]
+    fn rejects_trailing_tree_bytes() {
+        let registry = CheckpointRegistry::bundled().unwrap();
+        let mut checkpoint = registry.checkpoints.last().unwrap().clone();
+        checkpoint.tree.push_str("00");
+        assert!(checkpoint.validate().is_err());
+    }
+
+    #[test]
+    fn rejects_truncated_tree() {
+        let registry = CheckpointRegistry::bundled().unwrap();
+        let mut checkpoint = registry.checkpoints.last().unwrap().clone();
+        checkpoint.tree.truncate(checkpoint.tree.len() - 2);
+        assert!(checkpoint.validate().is_err());
+    }
+
+    #[test]
+    fn formats_round_trip() {
+        let registry = CheckpointRegistry::bundled().unwrap();
+        for checkpoint in registry.checkpoints() {
+            for format in &[
+                CheckpointFormat::Json,
+                CheckpointFormat::Kotlin,
+                CheckpointFormat::Binary,
+            ] {
+                let mut data = vec![];
+                checkpoint.write(*format, &mut data).unwrap();
+                assert_eq!(&Checkpoint::parse(*format, &data).unwrap(), checkpoint);
+                assert_eq!(&Checkpoint::read(&data).unwrap(), checkpoint);
+            }
+        }
+    }
+
+    #[test]
+    fn from_tree_matches_bundled() {
+        let registry = CheckpointRegistry::bundled().unwrap();
+        let checkpoint = registry.checkpoints.last().unwrap();
+        let created = Checkpoint::from_tree(
+            checkpoint.height,
+            &checkpoint.block_hash().unwrap().0,
+            checkpoint.time,
+            &checkpoint.commitment_tree().unwrap(),
+        );
+        assert_eq!(&created, checkpoint);
+    }
+
+    #[test]
+    fn rejects_corrupted_binary_root() {
+        let registry = CheckpointRegistry::bundled().unwrap();
+        let checkpoint = registry.checkpoints.last().unwrap();
+        let mut data = vec![];
+        checkpoint
+            .write(CheckpointFormat::Binary, &mut data)
+            .unwrap();
+        // The root follows the magic, version, network, height, hash, time and size.
+        data[4 + 1 + 1 + 4 + 32 + 4 + 8] ^= 1;
+        assert!(Checkpoint::read(&data).is_err());
+    }
+}
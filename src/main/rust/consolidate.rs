@@ -0,0 +1,204 @@
+//! Consolidation of an account's small notes into one.
+//!
+//! Every note a transaction spends adds a proof to it, so a wallet that has received many
+//! small payments pays for them in size and proving time on each later spend.
+//! [`consolidate_notes`] merges the smallest of them into a single note at the account's own
+//! address.
+
+use failure::{format_err, Error};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::path::Path;
+use zcash_client_sqlite::address::RecipientAddress;
+use zcash_primitives::{
+    consensus::BranchId, prover::TxProver, transaction::components::Amount,
+    zip32::ExtendedSpendingKey,
+};
+
+use crate::sweep::input_limit;
+use crate::transact::{
+    account_extfvk, build_transaction, spendable_notes, store_sent_transaction,
+    target_and_anchor_heights, TransactionOptions,
+};
+
+/// The result of [`consolidate_notes`].
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Consolidation {
+    /// The row ID of the consolidating transaction, or `None` if there was nothing to merge.
+    pub id_tx: Option<i64>,
+    /// The number of notes merged.
+    pub merged_notes: usize,
+    /// The value of the new note, after the fee.
+    pub value: i64,
+    /// The number of the account's unspent notes left, not counting the new note until it
+    /// is mined.
+    pub remaining_notes: i64,
+}
+
+fn unspent_note_count(data: &Connection, account: u32) -> Result<i64, Error> {
+    Ok(data.query_row(
+        "SELECT COUNT(*) FROM received_notes WHERE account = ? AND spent IS NULL",
+        &[account],
+        |row| row.get(0),
+    )?)
+}
+
+/// Merges up to `max_inputs` of the account's smallest spendable notes into one note at its
+/// default address, but never more than [`MAX_SWEEP_INPUTS`], which keeps the transaction
+/// within the size limit. Nothing is created if fewer than two notes are spendable.
+///
+/// [`MAX_SWEEP_INPUTS`]: crate::sweep::MAX_SWEEP_INPUTS
+pub fn consolidate_notes<P: AsRef<Path>>(
+    db_data: P,
+    consensus_branch_id: BranchId,
+    prover: impl TxProver,
+    account: u32,
+    extsk: &ExtendedSpendingKey,
+    max_inputs: usize,
+    options: TransactionOptions,
+) -> Result<Consolidation, Error> {
+    if max_inputs < 2 {
+        return Err(format_err!("At least two notes are needed to consolidate"));
+    }
+
+    let mut data = Connection::open(db_data)?;
+    let extfvk = account_extfvk(&data, account, extsk)?;
+    let (target_height, anchor_height) = target_and_anchor_heights(&data)?;
+
+    let mut notes = spendable_notes(&data, &extfvk, account, anchor_height)?;
+    if notes.len() < 2 {
+        return Ok(Consolidation {
+            id_tx: None,
+            merged_notes: 0,
+            value: 0,
+            remaining_notes: unspent_note_count(&data, account)?,
+        });
+    }
+    notes.sort_by_key(|(_, selected)| selected.note.value);
+    notes.truncate(input_limit(Some(max_inputs)));
+    let notes: Vec<_> = notes.into_iter().map(|(_, selected)| selected).collect();
+
+    let total: u64 = notes.iter().map(|selected| selected.note.value).sum();
+    let value = total as i64 - i64::from(options.fee);
+    if value <= 0 {
+        return Err(format_err!(
+            "The {} smallest notes are worth {}, not more than the fee",
+            notes.len(),
+            total
+        ));
+    }
+    let value = Amount::from_i64(value).unwrap();
+    let merged_notes = notes.len();
+
+    let (_, address) = extfvk
+        .default_address()
+        .map_err(|_| format_err!("Viewing key has no default address"))?;
+    let to = RecipientAddress::Shielded(address);
+    let (tx, output_index) = build_transaction(
+        consensus_branch_id,
+        prover,
+        extsk,
+        notes,
+        &to,
+        value,
+        None,
+        target_height,
+        options,
+    )?;
+    let id_tx = store_sent_transaction(
+        &mut data,
+        &tx,
+        account,
+        &to,
+        value,
+        None,
+        output_index,
+        options.fee,
+    )?;
+
+    Ok(Consolidation {
+        id_tx: Some(id_tx),
+        merged_notes,
+        value: i64::from(value),
+        remaining_notes: unspent_note_count(&data, account)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use failure::Error;
+    use zcash_primitives::consensus::BranchId;
+
+    use super::{consolidate_notes, Consolidation};
+    use crate::fixtures::{wallet_at_checkpoint, write_cache_db, MockProver, TestWallet};
+    use crate::transact::TransactionOptions;
+    use crate::wallet;
+
+    /// Returns a wallet whose account has scanned notes of 1,000, 2,000, 3,000 and 50,000
+    /// zatoshis.
+    fn setup() -> TestWallet {
+        let mut wallet = wallet_at_checkpoint().unwrap();
+        for &value in &[50_000, 2_000, 1_000, 3_000] {
+            wallet.chain.receive(&wallet.extfvk, value).unwrap();
+        }
+        wallet.chain.mine_blocks(11);
+        write_cache_db(&wallet.db_cache, wallet.chain.blocks()).unwrap();
+        wallet::scan_blocks(&wallet.db_cache, &wallet.db_data, None).unwrap();
+        wallet
+    }
+
+    fn consolidate(
+        wallet: &TestWallet,
+        max_inputs: usize,
+        fee: i64,
+    ) -> Result<Consolidation, Error> {
+        let options = TransactionOptions::new(Some(fee), None).unwrap();
+        consolidate_notes(
+            &wallet.db_data,
+            BranchId::Sapling,
+            MockProver,
+            0,
+            &wallet.extsk,
+            max_inputs,
+            options,
+        )
+    }
+
+    #[test]
+    fn smallest_notes_are_merged_less_the_fee() {
+        let wallet = setup();
+        assert!(consolidate(&wallet, 1, 1_000).is_err());
+
+        let consolidation = consolidate(&wallet, 3, 1_000).unwrap();
+        assert!(consolidation.id_tx.is_some());
+        assert_eq!(consolidation.merged_notes, 3);
+        assert_eq!(consolidation.value, 5_000);
+        assert_eq!(consolidation.remaining_notes, 1);
+        // The new note is not received until the transaction is mined.
+        assert_eq!(wallet::get_balance(&wallet.db_data, 0).unwrap(), 50_000);
+    }
+
+    #[test]
+    fn inputs_are_capped() {
+        // A limit beyond MAX_SWEEP_INPUTS is capped, which still leaves room for every note.
+        let wallet = setup();
+        let consolidation = consolidate(&wallet, usize::max_value(), 1_000).unwrap();
+        assert_eq!(consolidation.merged_notes, 4);
+        assert_eq!(consolidation.value, 55_000);
+        assert_eq!(consolidation.remaining_notes, 0);
+    }
+
+    #[test]
+    fn consolidations_must_be_worth_more_than_the_fee() {
+        let wallet = setup();
+        let err = consolidate(&wallet, 3, 6_000).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The 3 smallest notes are worth 6000, not more than the fee"
+        );
+        assert_eq!(wallet::get_balance(&wallet.db_data, 0).unwrap(), 56_000);
+
+        // Nothing was spent, so a smaller fee still works.
+        assert_eq!(consolidate(&wallet, 3, 5_000).unwrap().value, 1_000);
+    }
+}
@@ -0,0 +1,613 @@
+//! A test harness for darkside mode, in which lightwalletd serves a chain staged by the test.
+//!
+//! The [`Darkside`] trait mirrors the `DarksideStreamer` service in `darkside.proto`. It is
+//! implemented by [`DarksideClient`], which talks to a real lightwalletd started with
+//! `--darkside-very-insecure`, and by [`FakeDarkside`], which keeps the chain in memory and
+//! serves it through [`BlockchainClient`], so that tests can stage reorgs and incoming
+//! transactions and then run the [`Synchronizer`](crate::sync::Synchronizer) offline.
+
+use failure::{format_err, Error};
+use ff::{PrimeField, PrimeFieldRepr};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use zcash_client_backend::proto::compact_formats::{
+    CompactBlock, CompactOutput, CompactSpend, CompactTx,
+};
+use zcash_primitives::{block::BlockHeader, serialize::CompactSize, transaction::Transaction};
+
+use crate::sync::BlockchainClient;
+
+/// Staging and inspection operations of a darkside lightwalletd.
+pub trait Darkside {
+    /// Clears all blocks and transactions, and sets what `GetLightdInfo` reports.
+    fn reset(
+        &self,
+        sapling_activation: u32,
+        branch_id: &str,
+        chain_name: &str,
+    ) -> Result<(), Error>;
+
+    /// Stages full, serialized blocks, in order.
+    fn stage_blocks(&self, blocks: &[Vec<u8>]) -> Result<(), Error>;
+
+    /// Stages `count` empty blocks starting at `height`. Different nonces give the blocks
+    /// different hashes, which is how tests create competing chains.
+    fn stage_blocks_create(&self, height: u32, nonce: u32, count: u32) -> Result<(), Error>;
+
+    /// Stages full, serialized transactions to be mined into the block at `height`.
+    fn stage_transactions(&self, height: u32, txs: &[Vec<u8>]) -> Result<(), Error>;
+
+    /// Merges the staged blocks into the active chain, mines the staged transactions, and
+    /// sets the height reported as the chain tip.
+    fn apply_staged(&self, latest_height: u32) -> Result<(), Error>;
+
+    /// Returns and clears the transactions submitted with `SendTransaction`.
+    fn incoming_transactions(&self) -> Result<Vec<Vec<u8>>, Error>;
+
+    /// Clears the transactions submitted with `SendTransaction`.
+    fn clear_incoming_transactions(&self) -> Result<(), Error>;
+}
+
+/// Returns the hash of an empty block created by `StageBlocksCreate`.
+fn created_block_hash(height: u32, nonce: u32) -> Vec<u8> {
+    let mut hash = vec![0xdd; 32];
+    hash[..4].copy_from_slice(&height.to_le_bytes());
+    hash[4..8].copy_from_slice(&nonce.to_le_bytes());
+    hash
+}
+
+/// Reads the block height from a coinbase transaction's script, as specified by BIP 34.
+fn coinbase_height(tx: &Transaction) -> Result<u32, Error> {
+    let script = match tx.vin.first() {
+        Some(input) => &input.script_sig.0,
+        None => return Err(format_err!("Block has no coinbase input")),
+    };
+    let len = match script.first() {
+        Some(&len) if len >= 1 && len <= 4 && script.len() > len as usize => len as usize,
+        _ => return Err(format_err!("Coinbase does not start with the block height")),
+    };
+    let mut height = [0; 4];
+    height[..len].copy_from_slice(&script[1..=len]);
+    Ok(u32::from_le_bytes(height))
+}
+
+/// Converts a full transaction into the compact form that lightwalletd serves.
+pub fn compact_tx(index: u64, tx: &Transaction) -> Result<CompactTx, Error> {
+    let mut ctx = CompactTx::new();
+    ctx.set_index(index);
+    ctx.set_hash(tx.txid().0.to_vec());
+    for spend in tx.shielded_spends.iter() {
+        let mut cspend = CompactSpend::new();
+        cspend.set_nf(spend.nullifier.to_vec());
+        ctx.spends.push(cspend);
+    }
+    for output in tx.shielded_outputs.iter() {
+        let mut cmu = vec![];
+        output.cmu.into_repr().write_le(&mut cmu)?;
+        let mut epk = vec![];
+        output.ephemeral_key.write(&mut epk)?;
+
+        let mut coutput = CompactOutput::new();
+        coutput.set_cmu(cmu);
+        coutput.set_epk(epk);
+        coutput.set_ciphertext(output.enc_ciphertext[..52].to_vec());
+        ctx.outputs.push(coutput);
+    }
+    Ok(ctx)
+}
+
+/// Converts a full block into the compact form that lightwalletd serves, also returning the
+/// raw transactions it contains.
+pub fn compact_block(data: &[u8]) -> Result<(CompactBlock, Vec<Vec<u8>>), Error> {
+    let mut reader = data;
+    let header = BlockHeader::read(&mut reader)?;
+    let tx_count = CompactSize::read(&mut reader)?;
+
+    let mut block = CompactBlock::new();
+    block.set_hash(header.hash().0.to_vec());
+    block.set_prevHash(header.prev_block.0.to_vec());
+    block.set_time(header.time);
+
+    let mut raw_txs = vec![];
+    for index in 0..tx_count {
+        let before = reader;
+        let tx = Transaction::read(&mut reader)?;
+        raw_txs.push(before[..before.len() - reader.len()].to_vec());
+
+        if index == 0 {
+            block.set_height(u64::from(coinbase_height(&tx)?));
+        }
+        if !tx.shielded_spends.is_empty() || !tx.shielded_outputs.is_empty() {
+            block.vtx.push(compact_tx(index as u64, &tx)?);
+        }
+    }
+    Ok((block, raw_txs))
+}
+
+/// The chain and transactions served by a [`FakeDarkside`].
+#[derive(Default)]
+struct State {
+    sapling_activation: u32,
+    branch_id: String,
+    chain_name: String,
+    latest_height: u32,
+    blocks: Vec<CompactBlock>,
+    /// Mined transactions by txid, with the height they were mined at.
+    transactions: HashMap<Vec<u8>, (Vec<u8>, u32)>,
+    staged_blocks: Vec<(CompactBlock, Vec<Vec<u8>>)>,
+    staged_transactions: Vec<(u32, Vec<u8>)>,
+    incoming_transactions: Vec<Vec<u8>>,
+}
+
+impl State {
+    /// Mines a transaction into the active block at `height`.
+    fn mine(&mut self, height: u32, raw: Vec<u8>) -> Result<(), Error> {
+        let tx = Transaction::read(&raw[..])?;
+        let block = self
+            .blocks
+            .iter_mut()
+            .find(|block| block.height == u64::from(height))
+            .ok_or_else(|| format_err!("No active block at height {}", height))?;
+        if !tx.shielded_spends.is_empty() || !tx.shielded_outputs.is_empty() {
+            let index = block.vtx.len() as u64 + 1;
+            block.vtx.push(compact_tx(index, &tx)?);
+        }
+        self.transactions
+            .insert(tx.txid().0.to_vec(), (raw, height));
+        Ok(())
+    }
+}
+
+/// An in-memory darkside lightwalletd.
+///
+/// Clones share the same state, so one clone can be handed to a
+/// [`Synchronizer`](crate::sync::Synchronizer) while the test stages blocks through another.
+#[derive(Clone, Default)]
+pub struct FakeDarkside {
+    state: Arc<Mutex<State>>,
+}
+
+impl FakeDarkside {
+    pub fn new() -> Self {
+        FakeDarkside::default()
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().expect("darkside state is not poisoned")
+    }
+
+    /// Returns the Sapling activation height, branch ID and chain name set by `reset`.
+    pub fn lightd_info(&self) -> (u32, String, String) {
+        let state = self.state();
+        (
+            state.sapling_activation,
+            state.branch_id.clone(),
+            state.chain_name.clone(),
+        )
+    }
+
+    /// Accepts a transaction as `SendTransaction` would.
+    pub fn send_transaction(&self, raw: &[u8]) -> Result<(), Error> {
+        Transaction::read(raw)?;
+        self.state().incoming_transactions.push(raw.to_vec());
+        Ok(())
+    }
+}
+
+impl Darkside for FakeDarkside {
+    fn reset(
+        &self,
+        sapling_activation: u32,
+        branch_id: &str,
+        chain_name: &str,
+    ) -> Result<(), Error> {
+        *self.state() = State {
+            sapling_activation,
+            branch_id: branch_id.to_owned(),
+            chain_name: chain_name.to_owned(),
+            ..State::default()
+        };
+        Ok(())
+    }
+
+    fn stage_blocks(&self, blocks: &[Vec<u8>]) -> Result<(), Error> {
+        let blocks = blocks
+            .iter()
+            .map(|data| compact_block(data))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.state().staged_blocks.extend(blocks);
+        Ok(())
+    }
+
+    fn stage_blocks_create(&self, height: u32, nonce: u32, count: u32) -> Result<(), Error> {
+        let mut state = self.state();
+        for height in height..height + count {
+            let mut block = CompactBlock::new();
+            block.set_height(u64::from(height));
+            block.set_hash(created_block_hash(height, nonce));
+            block.set_time(1_600_000_000 + height * 75);
+            state.staged_blocks.push((block, vec![]));
+        }
+        Ok(())
+    }
+
+    fn stage_transactions(&self, height: u32, txs: &[Vec<u8>]) -> Result<(), Error> {
+        let mut state = self.state();
+        for tx in txs {
+            state.staged_transactions.push((height, tx.clone()));
+        }
+        Ok(())
+    }
+
+    fn apply_staged(&self, latest_height: u32) -> Result<(), Error> {
+        let mut state = self.state();
+
+        let staged_blocks: Vec<_> = state.staged_blocks.drain(..).collect();
+        for (mut block, raw_txs) in staged_blocks {
+            let height = block.height;
+            // Staged blocks replace the active chain from their height upwards.
+            state.blocks.retain(|b| b.height < height);
+            state
+                .transactions
+                .retain(|_, (_, h)| u64::from(*h) < height);
+            match state.blocks.last() {
+                Some(prev) if prev.height + 1 != height => {
+                    return Err(format_err!(
+                        "Staged block {} would leave a gap after block {}",
+                        height,
+                        prev.height
+                    ));
+                }
+                // As in darksidewalletd, staged blocks are relinked onto the active chain.
+                Some(prev) => block.set_prevHash(prev.hash.clone()),
+                None if height != u64::from(state.sapling_activation) => {
+                    return Err(format_err!(
+                        "The first block must be at the Sapling activation height {}",
+                        state.sapling_activation
+                    ));
+                }
+                None => (),
+            }
+            state.blocks.push(block);
+            for raw in raw_txs {
+                let tx = Transaction::read(&raw[..])?;
+                state
+                    .transactions
+                    .insert(tx.txid().0.to_vec(), (raw, height as u32));
+            }
+        }
+
+        let staged_transactions: Vec<_> = state.staged_transactions.drain(..).collect();
+        for (height, raw) in staged_transactions {
+            state.mine(height, raw)?;
+        }
+
+        state.latest_height = latest_height;
+        Ok(())
+    }
+
+    fn incoming_transactions(&self) -> Result<Vec<Vec<u8>>, Error> {
+        Ok(self.state().incoming_transactions.drain(..).collect())
+    }
+
+    fn clear_incoming_transactions(&self) -> Result<(), Error> {
+        self.state().incoming_transactions.clear();
+        Ok(())
+    }
+}
+
+impl BlockchainClient for FakeDarkside {
+    fn latest_height(&self) -> Result<u32, Error> {
+        let state = self.state();
+        match state.blocks.last() {
+            Some(tip) => Ok(state.latest_height.min(tip.height as u32)),
+            None => Err(format_err!("No blocks have been applied")),
+        }
+    }
+
+    fn block_range(&self, start: u32, end: u32) -> Result<Vec<CompactBlock>, Error> {
+        let end = end.min(self.latest_height()?);
+        Ok(self
+            .state()
+            .blocks
+            .iter()
+            .filter(|b| b.height >= u64::from(start) && b.height <= u64::from(end))
+            .cloned()
+            .collect())
+    }
+
+    fn transaction(&self, txid: &[u8]) -> Result<Vec<u8>, Error> {
+        match self.state().transactions.get(txid) {
+            Some((raw, _)) => Ok(raw.clone()),
+            None => Err(format_err!("Transaction {} not found", hex::encode(txid))),
+        }
+    }
+}
+
+#[cfg(feature = "lightwalletd")]
+pub use self::client::DarksideClient;
+
+#[cfg(feature = "lightwalletd")]
+mod client {
+    use failure::{format_err, Error};
+    use futures::{Future, Stream};
+    use grpc::{ClientStub, RequestOptions, StreamingRequest};
+    use std::sync::Arc;
+
+    use super::Darkside;
+    use crate::lightwalletd::{
+        self, grpc_client,
+        proto::{
+            darkside::{DarksideBlock, DarksideEmptyBlocks, DarksideHeight, DarksideMetaState},
+            darkside_grpc::{DarksideStreamer, DarksideStreamerClient},
+            service::{Empty, RawTransaction},
+        },
+        LightwalletdConfig, Timeouts,
+    };
+
+    fn lightwalletd_err(e: lightwalletd::Error) -> Error {
+        format_err!("{}", e)
+    }
+
+    /// A client for the `DarksideStreamer` service of a real darkside lightwalletd.
+    ///
+    /// The same server can be synced from with a
+    /// [`LightwalletdClient`](crate::lightwalletd::LightwalletdClient).
+    pub struct DarksideClient {
+        client: DarksideStreamerClient,
+        timeouts: Timeouts,
+    }
+
+    impl DarksideClient {
+        pub fn connect(config: &LightwalletdConfig) -> Result<Self, Error> {
+            let client = grpc_client(config).map_err(lightwalletd_err)?;
+            Ok(DarksideClient {
+                client: DarksideStreamerClient::with_client(Arc::new(client)),
+                timeouts: Timeouts::new(config.timeout),
+            })
+        }
+
+        /// Waits for a response, converting the error for the [`Darkside`] trait.
+        fn wait<T, F>(&self, response: F) -> Result<T, Error>
+        where
+            F: Future<Item = T, Error = grpc::Error>,
+        {
+            self.timeouts.wait(response).map_err(lightwalletd_err)
+        }
+    }
+
+    impl Darkside for DarksideClient {
+        fn reset(
+            &self,
+            sapling_activation: u32,
+            branch_id: &str,
+            chain_name: &str,
+        ) -> Result<(), Error> {
+            let mut state = DarksideMetaState::new();
+            state.set_saplingActivation(sapling_activation as i32);
+            state.set_branchID(branch_id.to_owned());
+            state.set_chainName(chain_name.to_owned());
+            let response = self.client.reset(RequestOptions::new(), state);
+            self.wait(response.drop_metadata()).map(|_| ())
+        }
+
+        fn stage_blocks(&self, blocks: &[Vec<u8>]) -> Result<(), Error> {
+            let blocks: Vec<_> = blocks
+                .iter()
+                .map(|data| {
+                    let mut block = DarksideBlock::new();
+                    block.set_block(hex::encode(data));
+                    block
+                })
+                .collect();
+            let response = self
+                .client
+                .stage_blocks_stream(RequestOptions::new(), StreamingRequest::iter(blocks));
+            self.wait(response.drop_metadata()).map(|_| ())
+        }
+
+        fn stage_blocks_create(&self, height: u32, nonce: u32, count: u32) -> Result<(), Error> {
+            let mut blocks = DarksideEmptyBlocks::new();
+            blocks.set_height(height as i32);
+            blocks.set_nonce(nonce as i32);
+            blocks.set_count(count as i32);
+            let response = self
+                .client
+                .stage_blocks_create(RequestOptions::new(), blocks);
+            self.wait(response.drop_metadata()).map(|_| ())
+        }
+
+        fn stage_transactions(&self, height: u32, txs: &[Vec<u8>]) -> Result<(), Error> {
+            let txs: Vec<_> = txs
+                .iter()
+                .map(|data| {
+                    let mut tx = RawTransaction::new();
+                    tx.set_data(data.clone());
+                    tx.set_height(u64::from(height));
+                    tx
+                })
+                .collect();
+            let response = self
+                .client
+                .stage_transactions_stream(RequestOptions::new(), StreamingRequest::iter(txs));
+            self.wait(response.drop_metadata()).map(|_| ())
+        }
+
+        fn apply_staged(&self, latest_height: u32) -> Result<(), Error> {
+            let mut height = DarksideHeight::new();
+            height.set_height(latest_height as i32);
+            let response = self.client.apply_staged(RequestOptions::new(), height);
+            self.wait(response.drop_metadata()).map(|_| ())
+        }
+
+        fn incoming_transactions(&self) -> Result<Vec<Vec<u8>>, Error> {
+            let txs = self
+                .client
+                .get_incoming_transactions(RequestOptions::new(), Empty::new())
+                .drop_metadata();
+            let txs = self.wait(txs.collect())?;
+            Ok(txs.into_iter().map(|mut tx| tx.take_data()).collect())
+        }
+
+        fn clear_incoming_transactions(&self) -> Result<(), Error> {
+            let response = self
+                .client
+                .clear_incoming_transactions(RequestOptions::new(), Empty::new());
+            self.wait(response.drop_metadata()).map(|_| ())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+    use zcash_client_backend::keys::spending_key;
+    use zcash_client_sqlite::{
+        init::{init_accounts_table, init_blocks_table, init_data_database},
+        query::{get_balance, get_verified_balance},
+    };
+    use zcash_primitives::{
+        block::BlockHash,
+        consensus::{NetworkUpgrade, Parameters},
+        merkle_tree::CommitmentTree,
+        sapling::Node,
+        transaction::{components::Amount, TransactionData},
+        zip32::ExtendedFullViewingKey,
+    };
+
+    use super::{created_block_hash, Darkside, FakeDarkside};
+    use crate::fixtures::shielded_transaction;
+    use crate::sync::{BlockchainClient, Synchronizer};
+    use crate::Network;
+
+    fn sapling_activation() -> u32 {
+        Network::activation_height(NetworkUpgrade::Sapling).unwrap()
+    }
+
+    fn extfvk() -> ExtendedFullViewingKey {
+        ExtendedFullViewingKey::from(&spending_key(&[0; 32], 1, 0))
+    }
+
+    /// Returns the total and verified balances of the wallet created in `dir`.
+    fn balances(dir: &TempDir) -> (Amount, Amount) {
+        let db_data = dir.path().join("data.db");
+        (
+            get_balance(&db_data, 0).unwrap(),
+            get_verified_balance(&db_data, 0).unwrap(),
+        )
+    }
+
+    /// Creates a wallet whose birthday is the first block of the darkside chain.
+    fn wallet(darkside: &FakeDarkside) -> (TempDir, Synchronizer<FakeDarkside>) {
+        let dir = tempfile::tempdir().unwrap();
+        let db_cache = dir.path().join("cache.db");
+        let db_data = dir.path().join("data.db");
+
+        rusqlite::Connection::open(&db_cache)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE compactblocks (height INTEGER PRIMARY KEY, data BLOB NOT NULL)",
+            )
+            .unwrap();
+        init_data_database(&db_data).unwrap();
+        init_accounts_table(&db_data, &[extfvk()]).unwrap();
+
+        let birthday = sapling_activation();
+        let block = &darkside.block_range(birthday, birthday).unwrap()[0];
+        let mut tree = vec![];
+        CommitmentTree::<Node>::new().write(&mut tree).unwrap();
+        init_blocks_table(
+            &db_data,
+            birthday as i32,
+            BlockHash::from_slice(&block.hash),
+            block.time,
+            &tree,
+        )
+        .unwrap();
+
+        (dir, Synchronizer::new(darkside.clone(), db_cache, db_data))
+    }
+
+    #[test]
+    fn staged_reorg_is_followed() {
+        let activation = sapling_activation();
+        let darkside = FakeDarkside::new();
+        darkside.reset(activation, "2bb40e60", "test").unwrap();
+        darkside.stage_blocks_create(activation, 0, 20).unwrap();
+        // One note is mined well before the reorg, and one in a block that it replaces.
+        let kept = shielded_transaction(&extfvk(), 30_000, 0).unwrap();
+        let orphaned = shielded_transaction(&extfvk(), 5_000, 1).unwrap();
+        darkside
+            .stage_transactions(activation + 5, &[kept])
+            .unwrap();
+        darkside
+            .stage_transactions(activation + 17, &[orphaned])
+            .unwrap();
+        darkside.apply_staged(activation + 19).unwrap();
+
+        let (dir, sync) = wallet(&darkside);
+        assert_eq!(sync.sync(|_| ()).unwrap(), activation + 19);
+        // Only the older note is below the anchor, 10 blocks under the tip.
+        assert_eq!(
+            balances(&dir),
+            (
+                Amount::from_u64(35_000).unwrap(),
+                Amount::from_u64(30_000).unwrap()
+            )
+        );
+
+        // Replace the chain from activation + 15 with a longer fork.
+        darkside
+            .stage_blocks_create(activation + 15, 1, 10)
+            .unwrap();
+        darkside.apply_staged(activation + 24).unwrap();
+        assert_eq!(sync.sync(|_| ()).unwrap(), activation + 24);
+
+        let block = &darkside
+            .block_range(activation + 15, activation + 15)
+            .unwrap()[0];
+        assert_eq!(block.hash, created_block_hash(activation + 15, 1));
+        let note_value = Amount::from_u64(30_000).unwrap();
+        assert_eq!(balances(&dir), (note_value, note_value));
+    }
+
+    #[test]
+    fn tip_can_lag_behind_active_blocks() {
+        let activation = sapling_activation();
+        let darkside = FakeDarkside::new();
+        darkside.reset(activation, "2bb40e60", "test").unwrap();
+        darkside.stage_blocks_create(activation, 0, 20).unwrap();
+        darkside.apply_staged(activation + 9).unwrap();
+
+        let (_dir, sync) = wallet(&darkside);
+        assert_eq!(sync.sync(|_| ()).unwrap(), activation + 9);
+
+        darkside.apply_staged(activation + 19).unwrap();
+        assert_eq!(sync.sync(|_| ()).unwrap(), activation + 19);
+    }
+
+    #[test]
+    fn incoming_transactions_can_be_mined() {
+        let activation = sapling_activation();
+        let darkside = FakeDarkside::new();
+        darkside.reset(activation, "2bb40e60", "test").unwrap();
+        darkside.stage_blocks_create(activation, 0, 10).unwrap();
+        darkside.apply_staged(activation + 9).unwrap();
+
+        let tx = TransactionData::new().freeze().unwrap();
+        let mut raw = vec![];
+        tx.write(&mut raw).unwrap();
+        darkside.send_transaction(&raw).unwrap();
+
+        let incoming = darkside.incoming_transactions().unwrap();
+        assert_eq!(incoming, vec![raw.clone()]);
+        assert!(darkside.incoming_transactions().unwrap().is_empty());
+        assert!(darkside.transaction(&tx.txid().0).is_err());
+
+        darkside
+            .stage_transactions(activation + 5, &incoming)
+            .unwrap();
+        darkside.apply_staged(activation + 9).unwrap();
+        assert_eq!(darkside.transaction(&tx.txid().0).unwrap(), raw);
+    }
+}
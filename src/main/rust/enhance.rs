@@ -0,0 +1,358 @@
+//! Enhancement of scanned transactions with their full data.
+//!
+//! Compact blocks carry neither memos nor outgoing ciphertexts, so after scanning, the wallet
+//! knows which of its transactions were mined but not what their memos say or, for
+//! transactions sent from another instance of the wallet, where they were sent. The caller
+//! fetches the full transactions listed by [`transactions_to_enhance`] and hands them back in
+//! batches to [`enhance_transactions`].
+
+use failure::{format_err, Error};
+use ff::{PrimeField, PrimeFieldRepr};
+use rusqlite::{types::ToSql, Connection, NO_PARAMS};
+use std::path::Path;
+use zcash_client_backend::{
+    decrypt::decrypt_transaction, encoding::decode_extended_full_viewing_key,
+};
+use zcash_client_sqlite::address::RecipientAddress;
+use zcash_primitives::{transaction::Transaction, zip32::ExtendedFullViewingKey};
+
+#[cfg(feature = "mainnet")]
+use zcash_client_backend::constants::mainnet::HRP_SAPLING_EXTENDED_FULL_VIEWING_KEY;
+#[cfg(not(feature = "mainnet"))]
+use zcash_client_backend::constants::testnet::HRP_SAPLING_EXTENDED_FULL_VIEWING_KEY;
+
+/// A transaction in a batch that could not be enhanced.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnhancementFailure {
+    /// The position of the transaction in the batch.
+    pub index: usize,
+    /// The txid, if the transaction could be parsed.
+    pub txid: Option<Vec<u8>>,
+    pub error: String,
+}
+
+/// Returns the txids, in internal byte order, of the wallet's transactions that are missing
+/// their raw bytes or have received notes without memos.
+pub fn transactions_to_enhance<P: AsRef<Path>>(db_data: P) -> Result<Vec<Vec<u8>>, Error> {
+    let data = Connection::open(db_data)?;
+    let mut stmt = data.prepare(
+        "SELECT DISTINCT transactions.txid FROM transactions
+        LEFT JOIN received_notes ON received_notes.tx = transactions.id_tx
+        WHERE transactions.raw IS NULL
+            OR (received_notes.id_note IS NOT NULL AND received_notes.memo IS NULL)
+        ORDER BY transactions.id_tx",
+    )?;
+    let rows = stmt.query_map(NO_PARAMS, |row| row.get(0))?;
+    Ok(rows.collect::<Result<_, _>>()?)
+}
+
+pub(crate) fn tracked_extfvks(data: &Connection) -> Result<Vec<ExtendedFullViewingKey>, Error> {
+    let mut stmt = data.prepare("SELECT extfvk FROM accounts ORDER BY account ASC")?;
+    let rows = stmt.query_map(NO_PARAMS, |row| row.get::<_, String>(0))?;
+    let mut extfvks = vec![];
+    for extfvk in rows {
+        match decode_extended_full_viewing_key(HRP_SAPLING_EXTENDED_FULL_VIEWING_KEY, &extfvk?) {
+            Ok(Some(extfvk)) => extfvks.push(extfvk),
+            Ok(None) => return Err(format_err!("Stored viewing key is for the wrong network")),
+            Err(e) => return Err(format_err!("Invalid stored viewing key: {}", e)),
+        }
+    }
+    Ok(extfvks)
+}
+
+/// Stores the raw bytes and decrypted outputs of one transaction mined at `height`, which is
+/// 0 if the caller does not know it.
+fn store_transaction(
+    data: &Connection,
+    extfvks: &[ExtendedFullViewingKey],
+    tx: &Transaction,
+    raw: &[u8],
+    height: u32,
+) -> Result<(), Error> {
+    let outputs = decrypt_transaction(tx, extfvks);
+    let txid = tx.txid().0.to_vec();
+    let expiry_height = tx.expiry_height;
+    // Only link the transaction to a block that the wallet has scanned.
+    let height = if height > 0 { Some(height) } else { None };
+
+    let updated = data.execute(
+        "UPDATE transactions
+        SET expiry_height = ?, raw = ?,
+            block = COALESCE(block, (SELECT height FROM blocks WHERE height = ?))
+        WHERE txid = ?",
+        &[&expiry_height as &dyn ToSql, &raw, &height, &txid],
+    )?;
+    let tx_ref: i64 = if updated == 0 {
+        if outputs.is_empty() {
+            // Not one of ours.
+            return Ok(());
+        }
+        data.execute(
+            "INSERT INTO transactions (txid, block, expiry_height, raw)
+            VALUES (?, (SELECT height FROM blocks WHERE height = ?), ?, ?)",
+            &[&txid as &dyn ToSql, &height, &expiry_height, &raw],
+        )?;
+        data.last_insert_rowid()
+    } else {
+        data.query_row(
+            "SELECT id_tx FROM transactions WHERE txid = ?",
+            &[&txid],
+            |row| row.get(0),
+        )?
+    };
+
+    for output in outputs {
+        let output_index = output.index as i64;
+        let account = output.account as i64;
+        let value = output.note.value as i64;
+        let memo = output.memo.as_bytes();
+
+        if output.outgoing {
+            let to = RecipientAddress::from(output.to).to_string();
+            let updated = data.execute(
+                "UPDATE sent_notes SET from_account = ?, address = ?, value = ?, memo = ?
+                WHERE tx = ? AND output_index = ?",
+                &[
+                    &account as &dyn ToSql,
+                    &to,
+                    &value,
+                    &memo,
+                    &tx_ref,
+                    &output_index,
+                ],
+            )?;
+            if updated == 0 {
+                data.execute(
+                    "INSERT INTO sent_notes (tx, output_index, from_account, address, value, memo)
+                    VALUES (?, ?, ?, ?, ?, ?)",
+                    &[
+                        &tx_ref as &dyn ToSql,
+                        &output_index,
+                        &account,
+                        &to,
+                        &value,
+                        &memo,
+                    ],
+                )?;
+            }
+        } else {
+            // Received notes are created by scanning, which also computes their nullifiers and
+            // witnesses, so only their memos are filled in here.
+            let mut rcm = vec![];
+            output.note.r.into_repr().write_le(&mut rcm)?;
+            data.execute(
+                "UPDATE received_notes SET memo = ?
+                WHERE tx = ? AND output_index = ? AND rcm = ?",
+                &[&memo as &dyn ToSql, &tx_ref, &output_index, &rcm],
+            )?;
+        }
+    }
+
+    let missing: i64 = data.query_row(
+        "SELECT COUNT(*) FROM received_notes WHERE tx = ? AND memo IS NULL",
+        &[tx_ref],
+        |row| row.get(0),
+    )?;
+    if missing > 0 {
+        return Err(format_err!(
+            "{} received notes could not be decrypted",
+            missing
+        ));
+    }
+    Ok(())
+}
+
+/// Stores a batch of full transactions, given as raw bytes with the height each was mined at
+/// (or 0 if unknown), in a single database transaction.
+///
+/// Transactions that fail to parse or decrypt are rolled back individually and reported; the
+/// rest of the batch is still stored. An `Err` means nothing was stored.
+pub fn enhance_transactions<P: AsRef<Path>>(
+    db_data: P,
+    txs: &[(Vec<u8>, u32)],
+) -> Result<Vec<EnhancementFailure>, Error> {
+    let mut data = Connection::open(db_data)?;
+    let extfvks = tracked_extfvks(&data)?;
+    let mut failures = vec![];
+
+    let db_tx = data.transaction()?;
+    for (index, (raw, height)) in txs.iter().enumerate() {
+        let tx = match Transaction::read(&raw[..]) {
+            Ok(tx) => tx,
+            Err(e) => {
+                failures.push(EnhancementFailure {
+                    index,
+                    txid: None,
+                    error: format!("Invalid transaction: {}", e),
+                });
+                continue;
+            }
+        };
+
+        db_tx.execute_batch("SAVEPOINT enhance")?;
+        if let Err(e) = store_transaction(&db_tx, &extfvks, &tx, raw, *height) {
+            db_tx.execute_batch("ROLLBACK TO enhance")?;
+            failures.push(EnhancementFailure {
+                index,
+                txid: Some(tx.txid().0.to_vec()),
+                error: e.to_string(),
+            });
+        }
+        db_tx.execute_batch("RELEASE enhance")?;
+    }
+    db_tx.commit()?;
+
+    Ok(failures)
+}
+
+#[cfg(test)]
+mod tests {
+    use rusqlite::{Connection, NO_PARAMS};
+    use zcash_primitives::{
+        note_encryption::Memo,
+        redjubjub::Signature,
+        transaction::{Transaction, TransactionData},
+    };
+
+    use super::{enhance_transactions, transactions_to_enhance};
+    use crate::fixtures::{shielded_output, wallet_at_checkpoint, write_cache_db, TestWallet};
+    use crate::wallet;
+
+    /// Returns a transaction paying `value` zatoshis to the wallet with `memo`.
+    fn payment(wallet: &TestWallet, value: u64, memo: &Memo, seed: u64) -> TransactionData {
+        let (_, to) = wallet.extfvk.default_address().unwrap();
+        let mut tx = TransactionData::new();
+        tx.shielded_outputs
+            .push(shielded_output(wallet.extfvk.fvk.ovk, &to, value, memo.clone(), seed).unwrap());
+        tx.binding_sig = Some(Signature::read(&[0u8; 64][..]).unwrap());
+        tx
+    }
+
+    fn serialize(tx: &Transaction) -> Vec<u8> {
+        let mut raw = vec![];
+        tx.write(&mut raw).unwrap();
+        raw
+    }
+
+    fn raw_transaction(wallet: &TestWallet, txid: &[u8]) -> Option<Vec<u8>> {
+        Connection::open(&wallet.db_data)
+            .unwrap()
+            .query_row(
+                "SELECT raw FROM transactions WHERE txid = ?",
+                &[txid],
+                |row| row.get(0),
+            )
+            .unwrap()
+    }
+
+    fn received_memo(wallet: &TestWallet, txid: &[u8]) -> Option<Vec<u8>> {
+        Connection::open(&wallet.db_data)
+            .unwrap()
+            .query_row(
+                "SELECT memo FROM received_notes
+                JOIN transactions ON transactions.id_tx = received_notes.tx
+                WHERE txid = ?",
+                &[txid],
+                |row| row.get(0),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn scanned_transactions_need_enhancement() {
+        let mut wallet = wallet_at_checkpoint().unwrap();
+        assert!(transactions_to_enhance(&wallet.db_data).unwrap().is_empty());
+
+        let note = wallet.chain.receive(&wallet.extfvk, 1_000).unwrap();
+        wallet.chain.mine();
+        write_cache_db(&wallet.db_cache, wallet.chain.blocks()).unwrap();
+        wallet::scan_blocks(&wallet.db_cache, &wallet.db_data, None).unwrap();
+        assert_eq!(
+            transactions_to_enhance(&wallet.db_data).unwrap(),
+            vec![note.txid]
+        );
+
+        // Garbage is reported without affecting the rest of the batch.
+        let failures = enhance_transactions(&wallet.db_data, &[(vec![1, 2, 3], 0)]).unwrap();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].index, 0);
+        assert_eq!(failures[0].txid, None);
+
+        let raw: Option<Vec<u8>> = Connection::open(&wallet.db_data)
+            .unwrap()
+            .query_row("SELECT raw FROM transactions", NO_PARAMS, |row| row.get(0))
+            .unwrap();
+        assert_eq!(raw, None);
+    }
+
+    #[test]
+    fn memos_and_raw_transactions_are_stored() {
+        let mut wallet = wallet_at_checkpoint().unwrap();
+        let memo = Memo::from_bytes(b"Thanks for the coffee").unwrap();
+        let tx = payment(&wallet, 20_000, &memo, 1).freeze().unwrap();
+        let raw = serialize(&tx);
+        let txid = wallet.chain.add_transaction(&tx).unwrap();
+        let height = wallet.chain.mine().height as u32;
+        write_cache_db(&wallet.db_cache, wallet.chain.blocks()).unwrap();
+        wallet::scan_blocks(&wallet.db_cache, &wallet.db_data, None).unwrap();
+        assert_eq!(received_memo(&wallet, &txid), None);
+
+        let failures = enhance_transactions(&wallet.db_data, &[(raw.clone(), height)]).unwrap();
+
+        assert!(failures.is_empty(), "{:?}", failures);
+        assert_eq!(raw_transaction(&wallet, &txid), Some(raw));
+        assert_eq!(
+            received_memo(&wallet, &txid),
+            Some(memo.as_bytes().to_vec())
+        );
+        assert!(transactions_to_enhance(&wallet.db_data).unwrap().is_empty());
+    }
+
+    #[test]
+    fn failed_transactions_leave_nothing_behind() {
+        let mut wallet = wallet_at_checkpoint().unwrap();
+        let memo = Memo::from_bytes(b"first").unwrap();
+        let good = payment(&wallet, 20_000, &memo, 1).freeze().unwrap();
+        let good_txid = wallet.chain.add_transaction(&good).unwrap();
+
+        // The compact form only carries the start of the note ciphertext, so the wallet
+        // scans this note, but the full ciphertext fails to decrypt.
+        let mut bad = payment(&wallet, 30_000, &Memo::default(), 2);
+        bad.shielded_outputs[0].enc_ciphertext[100] ^= 1;
+        let bad = bad.freeze().unwrap();
+        let bad_txid = wallet.chain.add_transaction(&bad).unwrap();
+
+        let height = wallet.chain.mine().height as u32;
+        write_cache_db(&wallet.db_cache, wallet.chain.blocks()).unwrap();
+        wallet::scan_blocks(&wallet.db_cache, &wallet.db_data, None).unwrap();
+
+        let failures = enhance_transactions(
+            &wallet.db_data,
+            &[
+                (serialize(&bad), height),
+                (serialize(&good), height),
+                (vec![1, 2, 3], height),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].index, 0);
+        assert_eq!(failures[0].txid, Some(bad_txid.clone()));
+        assert_eq!(failures[1].index, 2);
+        assert_eq!(failures[1].txid, None);
+
+        // The raw bytes stored before the failure were rolled back with it.
+        assert_eq!(raw_transaction(&wallet, &bad_txid), None);
+        assert_eq!(received_memo(&wallet, &bad_txid), None);
+        assert_eq!(raw_transaction(&wallet, &good_txid), Some(serialize(&good)));
+        assert_eq!(
+            received_memo(&wallet, &good_txid),
+            Some(memo.as_bytes().to_vec())
+        );
+        assert_eq!(
+            transactions_to_enhance(&wallet.db_data).unwrap(),
+            vec![bad_txid]
+        );
+    }
+}
@@ -0,0 +1,220 @@
+//! Management of sent transactions that have not been mined.
+//!
+//! Creating a transaction marks the notes it spends as spent, so until it is mined they are
+//! missing from the balance. If it is never mined, it becomes invalid after its expiry height,
+//! and [`expire_transactions`] returns its notes to the balance. Until then,
+//! [`transactions_to_rebroadcast`] returns it for the caller to submit again.
+
+use failure::Error;
+use rusqlite::{Connection, NO_PARAMS};
+use serde::Serialize;
+use std::path::Path;
+
+/// A transaction created by the wallet that has not been seen in a block.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct UnminedTransaction {
+    pub id_tx: i64,
+    /// The txid, as a hex string in display order.
+    pub txid: String,
+    pub created: Option<String>,
+    /// The last height at which the transaction can be mined.
+    pub expiry_height: u32,
+    pub expired: bool,
+}
+
+/// Returns the height that expiry is judged against: the chain tip, but no further than the
+/// wallet has scanned, as a transaction may have been mined in a block not scanned yet.
+pub(crate) fn effective_tip(data: &Connection, chain_tip: u32) -> Result<u32, Error> {
+    let scanned: Option<u32> =
+        data.query_row("SELECT MAX(height) FROM blocks", NO_PARAMS, |row| {
+            row.get(0)
+        })?;
+    Ok(scanned.map_or(0, |scanned| scanned.min(chain_tip)))
+}
+
+/// Whether a transaction with the given expiry height can no longer be mined above `tip`.
+/// An expiry height of 0 means the transaction does not expire.
+pub(crate) fn is_expired(expiry_height: u32, tip: u32) -> bool {
+    expiry_height > 0 && expiry_height <= tip
+}
+
+/// Returns the wallet's unmined transactions, oldest first, judging expiry at `chain_tip`.
+pub fn unmined_transactions<P: AsRef<Path>>(
+    db_data: P,
+    chain_tip: u32,
+) -> Result<Vec<UnminedTransaction>, Error> {
+    let data = Connection::open(db_data)?;
+    let tip = effective_tip(&data, chain_tip)?;
+    let mut stmt = data.prepare(
+        "SELECT id_tx, txid, created, expiry_height FROM transactions
+        WHERE block IS NULL AND raw IS NOT NULL
+        ORDER BY id_tx",
+    )?;
+    let rows = stmt.query_map(NO_PARAMS, |row| {
+        let mut txid: Vec<u8> = row.get(1)?;
+        txid.reverse();
+        let expiry_height: Option<u32> = row.get(3)?;
+        let expiry_height = expiry_height.unwrap_or(0);
+        Ok(UnminedTransaction {
+            id_tx: row.get(0)?,
+            txid: hex::encode(txid),
+            created: row.get(2)?,
+            expiry_height,
+            expired: is_expired(expiry_height, tip),
+        })
+    })?;
+    Ok(rows.collect::<Result<_, _>>()?)
+}
+
+/// Releases the notes spent by unmined transactions that have expired at `chain_tip`, so
+/// that they can be spent again. Returns the IDs of the newly expired transactions.
+pub fn expire_transactions<P: AsRef<Path>>(db_data: P, chain_tip: u32) -> Result<Vec<i64>, Error> {
+    let mut data = Connection::open(db_data)?;
+    let tip = effective_tip(&data, chain_tip)?;
+
+    let db_tx = data.transaction()?;
+    let expired = {
+        // Only transactions still holding notes are newly expired.
+        let mut stmt = db_tx.prepare(
+            "SELECT DISTINCT transactions.id_tx FROM transactions
+            INNER JOIN received_notes ON received_notes.spent = transactions.id_tx
+            WHERE transactions.block IS NULL
+                AND transactions.expiry_height > 0
+                AND transactions.expiry_height <= ?
+            ORDER BY transactions.id_tx",
+        )?;
+        let rows = stmt.query_map(&[tip], |row| row.get(0))?;
+        rows.collect::<Result<Vec<i64>, _>>()?
+    };
+    for id_tx in &expired {
+        db_tx.execute(
+            "UPDATE received_notes SET spent = NULL WHERE spent = ?",
+            &[id_tx],
+        )?;
+    }
+    db_tx.commit()?;
+
+    Ok(expired)
+}
+
+/// Returns the raw bytes of the unmined transactions that can still be mined after
+/// `chain_tip`, oldest first.
+pub fn transactions_to_rebroadcast<P: AsRef<Path>>(
+    db_data: P,
+    chain_tip: u32,
+) -> Result<Vec<Vec<u8>>, Error> {
+    let data = Connection::open(db_data)?;
+    let tip = effective_tip(&data, chain_tip)?;
+    let mut stmt = data.prepare(
+        "SELECT raw FROM transactions
+        WHERE block IS NULL AND raw IS NOT NULL
+            AND (expiry_height IS NULL OR expiry_height = 0 OR expiry_height > ?)
+        ORDER BY id_tx",
+    )?;
+    let rows = stmt.query_map(&[tip], |row| row.get(0))?;
+    Ok(rows.collect::<Result<_, _>>()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use rusqlite::{types::ToSql, Connection, NO_PARAMS};
+
+    use super::{expire_transactions, transactions_to_rebroadcast, unmined_transactions};
+    use crate::fixtures::{wallet_at_checkpoint, TestWallet};
+
+    /// Creates a wallet with a note mined at the birthday height, and two unmined
+    /// transactions that spend it, expiring 5 and 20 blocks later.
+    fn setup() -> (TestWallet, Connection, u32) {
+        let wallet = wallet_at_checkpoint().unwrap();
+        let birthday = wallet.checkpoint.height as u32;
+
+        let data = Connection::open(&wallet.db_data).unwrap();
+        data.execute(
+            "INSERT INTO transactions (id_tx, txid, block) VALUES (1, X'01', ?)",
+            &[birthday],
+        )
+        .unwrap();
+        for (id_tx, expiry_height) in &[(2, birthday + 5), (3, birthday + 20)] {
+            data.execute(
+                "INSERT INTO transactions (id_tx, txid, created, expiry_height, raw)
+                VALUES (?, ?, '2020-01-01', ?, ?)",
+                &[
+                    id_tx as &dyn ToSql,
+                    &vec![*id_tx as u8],
+                    expiry_height,
+                    &vec![0xaa, *id_tx as u8],
+                ],
+            )
+            .unwrap();
+        }
+        for (id_note, spent) in &[(1, 2), (2, 3)] {
+            data.execute(
+                "INSERT INTO received_notes
+                (id_note, tx, output_index, account, diversifier, value, rcm, nf, is_change, spent)
+                VALUES (?, 1, ?, 0, X'00', 1000, X'00', ?, 0, ?)",
+                &[id_note as &dyn ToSql, id_note, &vec![*id_note as u8], spent],
+            )
+            .unwrap();
+        }
+        (wallet, data, birthday)
+    }
+
+    fn unspent(data: &Connection) -> i64 {
+        data.query_row(
+            "SELECT COUNT(*) FROM received_notes WHERE spent IS NULL",
+            NO_PARAMS,
+            |row| row.get(0),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn expiry_waits_for_the_scanned_height() {
+        let (wallet, data, birthday) = setup();
+        let db_data = &wallet.db_data;
+
+        // The chain tip is past the first expiry, but the wallet has not scanned that far.
+        assert!(expire_transactions(db_data, birthday + 10)
+            .unwrap()
+            .is_empty());
+        let unmined = unmined_transactions(db_data, birthday + 10).unwrap();
+        assert_eq!(unmined.len(), 2);
+        assert!(!unmined[0].expired);
+        assert_eq!(unmined[0].txid, "02");
+        assert_eq!(unmined[0].expiry_height, birthday + 5);
+        assert_eq!(
+            transactions_to_rebroadcast(db_data, birthday + 10).unwrap(),
+            vec![vec![0xaa, 2], vec![0xaa, 3]]
+        );
+        assert_eq!(unspent(&data), 0);
+    }
+
+    #[test]
+    fn expired_transactions_release_their_notes() {
+        let (wallet, data, birthday) = setup();
+        let db_data = &wallet.db_data;
+        data.execute(
+            "INSERT INTO blocks (height, hash, time, sapling_tree) VALUES (?, X'00', 0, X'00')",
+            &[birthday + 10],
+        )
+        .unwrap();
+
+        assert_eq!(
+            expire_transactions(db_data, birthday + 10).unwrap(),
+            vec![2]
+        );
+        assert_eq!(unspent(&data), 1);
+        // Expiring again has nothing left to do.
+        assert!(expire_transactions(db_data, birthday + 10)
+            .unwrap()
+            .is_empty());
+
+        let unmined = unmined_transactions(db_data, birthday + 10).unwrap();
+        assert!(unmined[0].expired);
+        assert!(!unmined[1].expired);
+        assert_eq!(
+            transactions_to_rebroadcast(db_data, birthday + 10).unwrap(),
+            vec![vec![0xaa, 3]]
+        );
+    }
+}
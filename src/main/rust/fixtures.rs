@@ -0,0 +1,541 @@
+//! Deterministic synthetic chains for tests.
+//!
+//! [`ChainBuilder`] produces linked `CompactBlock`s whose Sapling outputs are really encrypted
+//! to the given viewing keys, and whose spends reveal the nullifiers the wallet will compute
+//! for those notes. The builder tracks the note commitment tree, so a chain started from a
+//! checkpoint can be scanned by a wallet initialized from the same checkpoint.
+
+use failure::{format_err, Error};
+use ff::{Field, PrimeField, PrimeFieldRepr};
+use pairing::bls12_381::{Bls12, Fr};
+use protobuf::Message;
+use rand_chacha::ChaChaRng;
+use rand_core::{RngCore, SeedableRng};
+use rusqlite::{types::ToSql, Connection, NO_PARAMS};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+use zcash_client_backend::{
+    keys::spending_key,
+    proto::compact_formats::{CompactBlock, CompactOutput, CompactSpend, CompactTx},
+};
+use zcash_client_sqlite::init::init_accounts_table;
+use zcash_primitives::{
+    jubjub::{edwards, fs::Fs, Unknown},
+    keys::OutgoingViewingKey,
+    merkle_tree::{CommitmentTree, CommitmentTreeWitness},
+    note_encryption::{Memo, SaplingNoteEncryption},
+    primitives::{Diversifier, PaymentAddress, ProofGenerationKey},
+    prover::TxProver,
+    redjubjub::{PublicKey, Signature},
+    sapling::Node,
+    transaction::{
+        components::{Amount, OutputDescription, GROTH_PROOF_SIZE},
+        Transaction, TransactionData,
+    },
+    zip32::{ExtendedFullViewingKey, ExtendedSpendingKey},
+    JUBJUB,
+};
+
+use crate::checkpoint::{Checkpoint, CheckpointRegistry};
+use crate::wallet;
+
+/// Seconds between generated blocks, matching the post-Blossom target spacing.
+pub const DEFAULT_BLOCK_SPACING: u32 = 75;
+
+/// A note generated for a viewing key.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GeneratedNote {
+    pub height: u64,
+    pub txid: Vec<u8>,
+    pub value: u64,
+    /// The note's position in the commitment tree.
+    pub position: u64,
+    pub nf: Vec<u8>,
+}
+
+/// Builds a chain of compact blocks one block at a time.
+///
+/// Transactions added with [`ChainBuilder::receive`] and [`ChainBuilder::spend`] go into the
+/// next block, which is created by [`ChainBuilder::mine`].
+#[derive(Clone)]
+pub struct ChainBuilder {
+    rng: ChaChaRng,
+    blocks: Vec<CompactBlock>,
+    next_height: u64,
+    prev_hash: Vec<u8>,
+    time: u32,
+    block_spacing: u32,
+    tree: CommitmentTree<Node>,
+    pending: Vec<CompactTx>,
+}
+
+impl ChainBuilder {
+    /// Starts a chain at `height`, on top of the block with hash `prev_hash` (in internal
+    /// byte order), time `prev_time` and note commitment tree `tree`.
+    pub fn new(height: u64, prev_hash: &[u8], prev_time: u32, tree: CommitmentTree<Node>) -> Self {
+        ChainBuilder {
+            rng: ChaChaRng::seed_from_u64(0),
+            blocks: vec![],
+            next_height: height,
+            prev_hash: prev_hash.to_vec(),
+            time: prev_time,
+            block_spacing: DEFAULT_BLOCK_SPACING,
+            tree,
+            pending: vec![],
+        }
+    }
+
+    /// Starts a chain on top of a checkpoint block.
+    pub fn from_checkpoint(checkpoint: &Checkpoint) -> Result<Self, Error> {
+        Ok(ChainBuilder::new(
+            checkpoint.height as u64 + 1,
+            &checkpoint.block_hash()?.0,
+            checkpoint.time,
+            checkpoint.commitment_tree()?,
+        ))
+    }
+
+    /// Reseeds the generator, so that chains built with different seeds get different hashes
+    /// and note randomness.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = ChaChaRng::seed_from_u64(seed);
+        self
+    }
+
+    pub fn with_block_spacing(mut self, seconds: u32) -> Self {
+        self.block_spacing = seconds;
+        self
+    }
+
+    /// Returns a copy of this builder that generates a competing chain from the same point.
+    pub fn fork(&self, seed: u64) -> Self {
+        self.clone().with_seed(seed)
+    }
+
+    fn random_bytes(&mut self) -> Vec<u8> {
+        let mut bytes = vec![0; 32];
+        self.rng.fill_bytes(&mut bytes);
+        bytes
+    }
+
+    fn push_tx(&mut self, mut tx: CompactTx) -> Vec<u8> {
+        let txid = self.random_bytes();
+        tx.set_index(self.pending.len() as u64);
+        tx.set_hash(txid.clone());
+        self.pending.push(tx);
+        txid
+    }
+
+    /// Adds a transaction with a single output of `value` zatoshis to the default address of
+    /// `extfvk`, returning the note as the wallet will see it.
+    pub fn receive(
+        &mut self,
+        extfvk: &ExtendedFullViewingKey,
+        value: u64,
+    ) -> Result<GeneratedNote, Error> {
+        let (_, to) = extfvk
+            .default_address()
+            .map_err(|_| format_err!("Viewing key has no default address"))?;
+        let note = to
+            .create_note(value, Fs::random(&mut self.rng), &JUBJUB)
+            .ok_or_else(|| format_err!("Invalid payment address"))?;
+        let encryptor = SaplingNoteEncryption::new(
+            extfvk.fvk.ovk,
+            note.clone(),
+            to,
+            Memo::default(),
+            &mut self.rng,
+        );
+
+        let cmu = note.cm(&JUBJUB).into_repr();
+        let position = self.tree.size() as u64;
+        self.tree
+            .append(Node::new(cmu))
+            .map_err(|_| format_err!("Note commitment tree is full"))?;
+
+        let mut output = CompactOutput::new();
+        let mut cmu_bytes = vec![];
+        cmu.write_le(&mut cmu_bytes)?;
+        output.set_cmu(cmu_bytes);
+        let mut epk = vec![];
+        encryptor.epk().write(&mut epk)?;
+        output.set_epk(epk);
+        output.set_ciphertext(encryptor.encrypt_note_plaintext()[..52].to_vec());
+
+        let mut tx = CompactTx::new();
+        tx.outputs.push(output);
+        let txid = self.push_tx(tx);
+
+        Ok(GeneratedNote {
+            height: self.next_height,
+            txid,
+            value,
+            position,
+            nf: note.nf(&extfvk.fvk.vk, position, &JUBJUB),
+        })
+    }
+
+    /// Adds a transaction that spends the note with nullifier `nf`, returning its txid.
+    pub fn spend(&mut self, nf: &[u8]) -> Vec<u8> {
+        let mut spend = CompactSpend::new();
+        spend.set_nf(nf.to_vec());
+        let mut tx = CompactTx::new();
+        tx.spends.push(spend);
+        self.push_tx(tx)
+    }
+
+    /// Adds the compact form of a full transaction, such as one with outputs made by
+    /// [`shielded_output`], returning its txid.
+    pub fn add_transaction(&mut self, tx: &Transaction) -> Result<Vec<u8>, Error> {
+        let mut ctx = CompactTx::new();
+        for spend in &tx.shielded_spends {
+            let mut cspend = CompactSpend::new();
+            cspend.set_nf(spend.nullifier.to_vec());
+            ctx.spends.push(cspend);
+        }
+        for output in &tx.shielded_outputs {
+            let cmu = output.cmu.into_repr();
+            self.tree
+                .append(Node::new(cmu))
+                .map_err(|_| format_err!("Note commitment tree is full"))?;
+
+            let mut coutput = CompactOutput::new();
+            let mut cmu_bytes = vec![];
+            cmu.write_le(&mut cmu_bytes)?;
+            coutput.set_cmu(cmu_bytes);
+            let mut epk = vec![];
+            output.ephemeral_key.write(&mut epk)?;
+            coutput.set_epk(epk);
+            coutput.set_ciphertext(output.enc_ciphertext[..52].to_vec());
+            ctx.outputs.push(coutput);
+        }
+
+        let txid = tx.txid().0.to_vec();
+        ctx.set_index(self.pending.len() as u64);
+        ctx.set_hash(txid.clone());
+        self.pending.push(ctx);
+        Ok(txid)
+    }
+
+    /// Creates the next block from the pending transactions.
+    pub fn mine(&mut self) -> &CompactBlock {
+        self.time += self.block_spacing;
+        let mut block = CompactBlock::new();
+        block.set_height(self.next_height);
+        block.set_hash(self.random_bytes());
+        block.set_prevHash(self.prev_hash.clone());
+        block.set_time(self.time);
+        block.vtx = self.pending.drain(..).collect();
+
+        self.next_height += 1;
+        self.prev_hash = block.hash.clone();
+        self.blocks.push(block);
+        self.blocks.last().unwrap()
+    }
+
+    /// Creates `count` blocks, the first of which holds any pending transactions.
+    pub fn mine_blocks(&mut self, count: u64) {
+        for _ in 0..count {
+            self.mine();
+        }
+    }
+
+    pub fn blocks(&self) -> &[CompactBlock] {
+        &self.blocks
+    }
+
+    pub fn into_blocks(self) -> Vec<CompactBlock> {
+        self.blocks
+    }
+
+    /// Returns the height of the last block, if any have been mined.
+    pub fn tip_height(&self) -> Option<u64> {
+        self.blocks.last().map(|block| block.height)
+    }
+
+    /// Returns the note commitment tree, including outputs of pending transactions.
+    pub fn tree(&self) -> &CommitmentTree<Node> {
+        &self.tree
+    }
+
+    /// Returns a checkpoint for the last block, for initializing a wallet part way along, or
+    /// `None` if no block has been mined or transactions are pending.
+    pub fn checkpoint(&self) -> Option<Checkpoint> {
+        if !self.pending.is_empty() {
+            return None;
+        }
+        self.blocks.last().map(|block| {
+            Checkpoint::from_tree(block.height as i32, &block.hash, block.time, &self.tree)
+        })
+    }
+}
+
+/// Returns a Sapling output paying `value` zatoshis to `to`. The note and its ciphertexts are
+/// real, so the output can be decrypted by the recipient and recovered with `ovk`, but the
+/// value commitment and proof are placeholders that the network would reject.
+pub fn shielded_output(
+    ovk: OutgoingViewingKey,
+    to: &PaymentAddress<Bls12>,
+    value: u64,
+    memo: Memo,
+    seed: u64,
+) -> Result<OutputDescription, Error> {
+    let mut rng = ChaChaRng::seed_from_u64(seed);
+    let note = to
+        .create_note(value, Fs::random(&mut rng), &JUBJUB)
+        .ok_or_else(|| format_err!("Invalid payment address"))?;
+    let cmu = note.cm(&JUBJUB);
+    let encryptor = SaplingNoteEncryption::new(ovk, note, to.clone(), memo, &mut rng);
+    let epk: edwards::Point<Bls12, Unknown> = encryptor.epk().clone().into();
+    let cv = epk.clone();
+
+    Ok(OutputDescription {
+        cv: cv.clone(),
+        cmu,
+        ephemeral_key: epk,
+        enc_ciphertext: encryptor.encrypt_note_plaintext(),
+        out_ciphertext: encryptor.encrypt_outgoing_plaintext(&cv, &cmu),
+        zkproof: [0; GROTH_PROOF_SIZE],
+    })
+}
+
+/// Returns a serialized v4 transaction with a single Sapling output of `value` zatoshis to the
+/// default address of `extfvk`, for serving as a full transaction.
+///
+/// The output is made by [`shielded_output`], and the binding signature is a placeholder too.
+pub fn shielded_transaction(
+    extfvk: &ExtendedFullViewingKey,
+    value: u64,
+    seed: u64,
+) -> Result<Vec<u8>, Error> {
+    let (_, to) = extfvk
+        .default_address()
+        .map_err(|_| format_err!("Viewing key has no default address"))?;
+
+    let mut tx = TransactionData::new();
+    tx.shielded_outputs.push(shielded_output(
+        extfvk.fvk.ovk,
+        &to,
+        value,
+        Memo::default(),
+        seed,
+    )?);
+    tx.binding_sig = Some(Signature::read(&[0u8; 64][..])?);
+
+    let mut raw = vec![];
+    tx.freeze()?.write(&mut raw)?;
+    Ok(raw)
+}
+
+/// A [`TxProver`] that makes placeholder proofs and signatures, so that transactions with
+/// Sapling spends and outputs can be built without the Sapling parameters. Nothing it makes
+/// would pass verification.
+pub struct MockProver;
+
+impl TxProver for MockProver {
+    type SaplingProvingContext = ();
+
+    fn new_sapling_proving_context(&self) {}
+
+    fn spend_proof(
+        &self,
+        _: &mut (),
+        _: ProofGenerationKey<Bls12>,
+        _: Diversifier,
+        _: Fs,
+        _: Fs,
+        _: u64,
+        _: Fr,
+        _: CommitmentTreeWitness<Node>,
+    ) -> Result<
+        (
+            [u8; GROTH_PROOF_SIZE],
+            edwards::Point<Bls12, Unknown>,
+            PublicKey<Bls12>,
+        ),
+        (),
+    > {
+        Ok((
+            [0; GROTH_PROOF_SIZE],
+            edwards::Point::zero(),
+            PublicKey(edwards::Point::zero()),
+        ))
+    }
+
+    fn output_proof(
+        &self,
+        _: &mut (),
+        _: Fs,
+        _: PaymentAddress<Bls12>,
+        _: Fs,
+        _: u64,
+    ) -> ([u8; GROTH_PROOF_SIZE], edwards::Point<Bls12, Unknown>) {
+        ([0; GROTH_PROOF_SIZE], edwards::Point::zero())
+    }
+
+    fn binding_sig(&self, _: &mut (), _: Amount, _: &[u8; 32]) -> Result<Signature, ()> {
+        Signature::read(&[0u8; 64][..]).map_err(|_| ())
+    }
+}
+
+/// Stores blocks in a cache DB, creating the `compactblocks` table if it does not exist.
+pub fn write_cache_db<P: AsRef<Path>>(db_cache: P, blocks: &[CompactBlock]) -> Result<(), Error> {
+    let mut cache = Connection::open(db_cache)?;
+    cache.execute(
+        "CREATE TABLE IF NOT EXISTS compactblocks (
+            height INTEGER PRIMARY KEY,
+            data BLOB NOT NULL
+        )",
+        NO_PARAMS,
+    )?;
+    let tx = cache.transaction()?;
+    {
+        let mut insert =
+            tx.prepare("INSERT OR REPLACE INTO compactblocks (height, data) VALUES (?, ?)")?;
+        for block in blocks {
+            let data = block.write_to_bytes()?;
+            insert.execute(&[&(block.height as i64) as &dyn ToSql, &data])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// Writes blocks as length-delimited messages, as read by `update-sapling-tree --block-file`.
+pub fn write_block_file<W: Write>(mut out: W, blocks: &[CompactBlock]) -> Result<(), Error> {
+    for block in blocks {
+        block.write_length_delimited_to_writer(&mut out)?;
+    }
+    Ok(())
+}
+
+/// A wallet in a temporary directory, as set up by [`wallet_at_checkpoint`].
+pub struct TestWallet {
+    pub dir: TempDir,
+    pub db_cache: PathBuf,
+    pub db_data: PathBuf,
+    pub extsk: ExtendedSpendingKey,
+    pub extfvk: ExtendedFullViewingKey,
+    /// The checkpoint the wallet was initialized from, which is its birthday.
+    pub checkpoint: Checkpoint,
+    /// An empty chain starting at the checkpoint, for the test to extend and scan.
+    pub chain: ChainBuilder,
+}
+
+/// Creates a wallet whose data DB is initialized from the latest bundled checkpoint, with a
+/// single account for `spending_key(&[0; 32], 1, 0)`, and an empty cache DB next to it.
+pub fn wallet_at_checkpoint() -> Result<TestWallet, Error> {
+    let dir = tempfile::tempdir()?;
+    let db_cache = dir.path().join("cache.db");
+    let db_data = dir.path().join("data.db");
+    write_cache_db(&db_cache, &[])?;
+    wallet::init_data_db(&db_data)?;
+    let registry = CheckpointRegistry::bundled()?;
+    let checkpoint = registry
+        .checkpoints()
+        .last()
+        .ok_or_else(|| format_err!("No bundled checkpoints"))?
+        .clone();
+    wallet::init_blocks_table(&db_data, &checkpoint, None, None)?;
+    let extsk = spending_key(&[0; 32], 1, 0);
+    let extfvk = ExtendedFullViewingKey::from(&extsk);
+    init_accounts_table(&db_data, &[extfvk.clone()])
+        .map_err(|e| format_err!("Error while initializing accounts: {}", e))?;
+    let chain = ChainBuilder::from_checkpoint(&checkpoint)?;
+
+    Ok(TestWallet {
+        dir,
+        db_cache,
+        db_data,
+        extsk,
+        extfvk,
+        checkpoint,
+        chain,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rusqlite::{Connection, NO_PARAMS};
+    use zcash_client_sqlite::{
+        chain::{rewind_to_height, validate_combined_chain},
+        error::ErrorKind,
+        query::get_balance,
+        scan::scan_cached_blocks,
+    };
+    use zcash_primitives::transaction::components::Amount;
+
+    use super::{wallet_at_checkpoint, write_block_file, write_cache_db, TestWallet};
+
+    fn balance(wallet: &TestWallet) -> Amount {
+        get_balance(&wallet.db_data, 0).unwrap()
+    }
+
+    #[test]
+    fn scanned_notes_are_received_and_spent() {
+        let mut wallet = wallet_at_checkpoint().unwrap();
+        let note = wallet.chain.receive(&wallet.extfvk, 50_000).unwrap();
+        wallet.chain.mine_blocks(10);
+        write_cache_db(&wallet.db_cache, wallet.chain.blocks()).unwrap();
+        scan_cached_blocks(&wallet.db_cache, &wallet.db_data, None).unwrap();
+        assert_eq!(balance(&wallet), Amount::from_u64(50_000).unwrap());
+
+        wallet.chain.spend(&note.nf);
+        wallet.chain.mine();
+        write_cache_db(&wallet.db_cache, wallet.chain.blocks()).unwrap();
+        scan_cached_blocks(&wallet.db_cache, &wallet.db_data, None).unwrap();
+        assert_eq!(balance(&wallet), Amount::zero());
+    }
+
+    #[test]
+    fn forks_are_detected_and_rewound() {
+        let mut wallet = wallet_at_checkpoint().unwrap();
+        wallet.chain.mine_blocks(5);
+        let mut fork = wallet.chain.fork(1);
+        wallet.chain.receive(&wallet.extfvk, 10_000).unwrap();
+        wallet.chain.mine_blocks(5);
+        write_cache_db(&wallet.db_cache, wallet.chain.blocks()).unwrap();
+        scan_cached_blocks(&wallet.db_cache, &wallet.db_data, None).unwrap();
+        assert_eq!(balance(&wallet), Amount::from_u64(10_000).unwrap());
+
+        // The fork replaces the block with the note.
+        fork.mine_blocks(6);
+        write_cache_db(&wallet.db_cache, fork.blocks()).unwrap();
+        let fork_height = fork.blocks()[5].height;
+        match validate_combined_chain(&wallet.db_cache, &wallet.db_data) {
+            Err(e) => match e.kind() {
+                ErrorKind::InvalidChain(..) => (),
+                _ => panic!("unexpected error {}", e),
+            },
+            Ok(()) => panic!("fork was not detected"),
+        }
+        rewind_to_height(&wallet.db_data, fork_height as i32 - 1).unwrap();
+        scan_cached_blocks(&wallet.db_cache, &wallet.db_data, None).unwrap();
+        assert_eq!(balance(&wallet), Amount::zero());
+    }
+
+    #[test]
+    fn chains_are_deterministic() {
+        let mut a = wallet_at_checkpoint().unwrap().chain;
+        let mut b = wallet_at_checkpoint().unwrap().chain;
+        a.mine_blocks(3);
+        b.mine_blocks(3);
+        assert_eq!(a.blocks(), b.blocks());
+
+        let mut file = vec![];
+        write_block_file(&mut file, a.blocks()).unwrap();
+        assert!(!file.is_empty());
+
+        let dir = tempfile::tempdir().unwrap();
+        let db_cache = dir.path().join("cache.db");
+        write_cache_db(&db_cache, a.blocks()).unwrap();
+        let count: i64 = Connection::open(&db_cache)
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM compactblocks", NO_PARAMS, |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(count, 3);
+    }
+}
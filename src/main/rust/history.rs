@@ -0,0 +1,411 @@
+//! The transaction history of an account.
+//!
+//! [`transaction_history`] summarizes each transaction an account took part in from the
+//! `transactions`, `received_notes` and `sent_notes` tables, so that callers need not query
+//! the data DB schema themselves. Transactions are listed newest first, with unmined ones at
+//! the top, in the order the SDK's paged transaction lists use.
+
+use failure::{format_err, Error};
+use rusqlite::{types::ToSql, Connection};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
+use std::path::Path;
+use zcash_client_backend::encoding::encode_payment_address;
+use zcash_primitives::{note_encryption::Memo, primitives::Diversifier, JUBJUB};
+
+use crate::expiry::{effective_tip, is_expired};
+use crate::transact::{has_fee_table, tracked_extfvk};
+use crate::wallet::decode_extfvk;
+
+#[cfg(feature = "mainnet")]
+use zcash_client_backend::constants::mainnet::HRP_SAPLING_PAYMENT_ADDRESS;
+#[cfg(not(feature = "mainnet"))]
+use zcash_client_backend::constants::testnet::HRP_SAPLING_PAYMENT_ADDRESS;
+
+/// Whether a transaction moved funds out of the account or only into it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Direction {
+    /// The account spent notes in the transaction or created it, including transfers to
+    /// itself.
+    Sent,
+    Received,
+}
+
+impl Direction {
+    /// Returns the direction filter with the given code, as passed over JNI: 0 for either
+    /// direction, 1 for sent and 2 for received.
+    pub fn from_code(code: i32) -> Result<Option<Self>, Error> {
+        match code {
+            0 => Ok(None),
+            1 => Ok(Some(Direction::Sent)),
+            2 => Ok(Some(Direction::Received)),
+            _ => Err(format_err!("Unknown transaction direction {}", code)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TransactionStatus {
+    Mined,
+    /// Not mined yet, but still able to be.
+    Pending,
+    /// Not mined, and past its expiry height.
+    Expired,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MemoStatus {
+    /// No memo, or only empty ones.
+    None,
+    /// At least one memo is text.
+    Text,
+    /// Memos are present but none of them is text.
+    Binary,
+}
+
+/// One transaction in an account's history.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct HistoryEntry {
+    pub id_tx: i64,
+    /// The txid, as a hex string in display order.
+    pub txid: String,
+    pub mined_height: Option<u32>,
+    /// The time of the block the transaction was mined in, in seconds since the epoch.
+    pub block_time: Option<u32>,
+    pub direction: Direction,
+    /// The change in the account's balance, in zatoshis. For unmined transactions, whose
+    /// change is not known yet, this is what the account pays to others plus the fee.
+    pub net_value: i64,
+    /// The fee, if the wallet created the transaction.
+    pub fee: Option<i64>,
+    /// The addresses paid by a sent transaction, or the account's addresses that received
+    /// funds in a received one.
+    pub addresses: Vec<String>,
+    pub memo_status: MemoStatus,
+    /// The text memos, excluding those on change.
+    pub memos: Vec<String>,
+    pub status: TransactionStatus,
+}
+
+/// Which transactions [`transaction_history`] returns.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HistoryFilter {
+    pub direction: Option<Direction>,
+    /// The lowest mined height. Unmined transactions are left out if either bound is set.
+    pub min_height: Option<u32>,
+    /// The highest mined height.
+    pub max_height: Option<u32>,
+    /// Text that one of the memos must contain, ignoring case.
+    pub memo: Option<String>,
+}
+
+impl HistoryFilter {
+    /// Whether the text memos match the memo query. The other conditions are applied by the
+    /// query in [`transaction_history`].
+    fn matches_memo(&self, memos: &[String]) -> bool {
+        match &self.memo {
+            Some(query) => {
+                let query = query.to_lowercase();
+                memos
+                    .iter()
+                    .any(|memo| memo.to_lowercase().contains(&query))
+            }
+            None => true,
+        }
+    }
+}
+
+/// The conditions selecting the transactions of account `?1` in direction `?2`, which is 1
+/// for sent, 0 for received and NULL for either, mined between heights `?3` and `?4`, either
+/// of which may be NULL. A transaction is sent if it spends one of the account's notes or
+/// pays from the account.
+const HISTORY_CONDITIONS: &str = "
+    WHERE (id_tx IN (SELECT tx FROM received_notes WHERE account = ?1)
+            OR id_tx IN (SELECT spent FROM received_notes WHERE account = ?1)
+            OR id_tx IN (SELECT tx FROM sent_notes WHERE from_account = ?1))
+        AND (?2 IS NULL
+            OR ?2 = (id_tx IN (SELECT spent FROM received_notes
+                    WHERE account = ?1 AND spent IS NOT NULL AND value > 0)
+                OR id_tx IN (SELECT tx FROM sent_notes WHERE from_account = ?1)))
+        AND ((?3 IS NULL AND ?4 IS NULL)
+            OR (block >= IFNULL(?3, block) AND block <= IFNULL(?4, block)))";
+
+/// A page of an account's history.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct HistoryPage {
+    /// The number of transactions matching the filter, across all pages.
+    pub total: usize,
+    pub offset: usize,
+    pub entries: Vec<HistoryEntry>,
+}
+
+/// What the memos of a transaction contain.
+#[derive(Default)]
+struct Memos {
+    texts: Vec<String>,
+    binary: bool,
+}
+
+impl Memos {
+    fn add(&mut self, memo: Option<Vec<u8>>) {
+        let memo = match memo {
+            Some(memo) => memo,
+            None => return,
+        };
+        // A memo starting with 0xF6 is empty.
+        if memo.first().map_or(true, |&first| first == 0xF6) {
+            return;
+        }
+        match Memo::from_bytes(&memo).and_then(|memo| memo.to_utf8()) {
+            Some(Ok(text)) => self.texts.push(text),
+            _ => self.binary = true,
+        }
+    }
+
+    fn status(&self) -> MemoStatus {
+        if !self.texts.is_empty() {
+            MemoStatus::Text
+        } else if self.binary {
+            MemoStatus::Binary
+        } else {
+            MemoStatus::None
+        }
+    }
+}
+
+/// Returns up to `limit` of the account's transactions matching `filter`, skipping the first
+/// `offset`, and judging the expiry of unmined transactions at `chain_tip`.
+pub fn transaction_history<P: AsRef<Path>>(
+    db_data: P,
+    account: u32,
+    chain_tip: u32,
+    filter: &HistoryFilter,
+    offset: usize,
+    limit: usize,
+) -> Result<HistoryPage, Error> {
+    let data = Connection::open(db_data)?;
+    let extfvk = decode_extfvk(&tracked_extfvk(&data, account)?)?;
+    let own_address: String = data.query_row(
+        "SELECT address FROM accounts WHERE account = ?",
+        &[account],
+        |row| row.get(0),
+    )?;
+    let tip = effective_tip(&data, chain_tip)?;
+
+    // Data DBs created by earlier versions have no fee table until they are sent from.
+    let (fee, fees) = if has_fee_table(&data)? {
+        ("fee", "LEFT JOIN sent_transaction_fees USING (id_tx)")
+    } else {
+        ("NULL", "")
+    };
+    let mut stmt_txs = data.prepare(&format!(
+        "SELECT id_tx, txid, block, blocks.time, expiry_height, {}
+        FROM transactions
+        LEFT JOIN blocks ON blocks.height = transactions.block
+        {}
+        {}
+        ORDER BY block IS NOT NULL, block DESC, tx_index DESC, id_tx DESC
+        LIMIT ?5 OFFSET ?6",
+        fee, fees, HISTORY_CONDITIONS
+    ))?;
+    let mut stmt_received = data.prepare(
+        "SELECT diversifier, value, is_change, memo FROM received_notes
+        WHERE tx = ? AND account = ?",
+    )?;
+    let mut stmt_spent =
+        data.prepare("SELECT SUM(value) FROM received_notes WHERE spent = ? AND account = ?")?;
+    let mut stmt_sent = data
+        .prepare("SELECT address, value, memo FROM sent_notes WHERE tx = ? AND from_account = ?")?;
+
+    // The memo query is matched after decoding the memos, so it needs every transaction and
+    // pages them itself. Otherwise the database pages them, and counts the matches.
+    let sent = filter
+        .direction
+        .map(|direction| direction == Direction::Sent);
+    let (limit_sql, offset_sql) = match filter.memo {
+        Some(_) => (-1, 0),
+        None => (
+            i64::try_from(limit).unwrap_or(-1),
+            i64::try_from(offset).unwrap_or(i64::MAX),
+        ),
+    };
+    let txs = stmt_txs.query_map(
+        &[
+            &account as &dyn ToSql,
+            &sent,
+            &filter.min_height,
+            &filter.max_height,
+            &limit_sql,
+            &offset_sql,
+        ],
+        |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, Vec<u8>>(1)?,
+                row.get::<_, Option<u32>>(2)?,
+                row.get::<_, Option<u32>>(3)?,
+                row.get::<_, Option<u32>>(4)?,
+                row.get::<_, Option<i64>>(5)?,
+            ))
+        },
+    )?;
+
+    let mut entries = vec![];
+    for tx in txs {
+        let (id_tx, mut txid, mined_height, block_time, expiry_height, fee) = tx?;
+        txid.reverse();
+
+        let mut memos = Memos::default();
+        let mut addresses = BTreeSet::new();
+        let mut received = 0;
+        let rows = stmt_received.query_map(&[id_tx, i64::from(account)], |row| {
+            Ok((
+                row.get::<_, Vec<u8>>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, bool>(2)?,
+                row.get::<_, Option<Vec<u8>>>(3)?,
+            ))
+        })?;
+        for row in rows {
+            let (diversifier, value, is_change, memo) = row?;
+            received += value;
+            if is_change {
+                continue;
+            }
+            memos.add(memo);
+            if diversifier.len() == 11 {
+                let mut d = [0; 11];
+                d.copy_from_slice(&diversifier);
+                if let Some(address) = extfvk.fvk.vk.into_payment_address(Diversifier(d), &JUBJUB) {
+                    addresses.insert(encode_payment_address(
+                        HRP_SAPLING_PAYMENT_ADDRESS,
+                        &address,
+                    ));
+                }
+            }
+        }
+
+        let spent = stmt_spent
+            .query_row(&[id_tx, i64::from(account)], |row| {
+                row.get::<_, Option<i64>>(0)
+            })?
+            .unwrap_or(0);
+
+        let mut sent_addresses = BTreeSet::new();
+        let mut paid_to_others = 0;
+        let rows = stmt_sent.query_map(&[id_tx, i64::from(account)], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, Option<Vec<u8>>>(2)?,
+            ))
+        })?;
+        for row in rows {
+            let (address, value, memo) = row?;
+            if address != own_address {
+                paid_to_others += value;
+            }
+            memos.add(memo);
+            sent_addresses.insert(address);
+        }
+
+        let direction = if spent > 0 || !sent_addresses.is_empty() {
+            Direction::Sent
+        } else {
+            Direction::Received
+        };
+        if direction == Direction::Sent && !sent_addresses.is_empty() {
+            addresses = sent_addresses;
+        }
+        let status = match (mined_height, expiry_height) {
+            (Some(_), _) => TransactionStatus::Mined,
+            (None, Some(expiry_height)) if is_expired(expiry_height, tip) => {
+                TransactionStatus::Expired
+            }
+            (None, _) => TransactionStatus::Pending,
+        };
+        let net_value = match status {
+            TransactionStatus::Mined => received - spent,
+            _ => -(paid_to_others + fee.unwrap_or(0)),
+        };
+
+        entries.push(HistoryEntry {
+            id_tx,
+            txid: hex::encode(txid),
+            mined_height,
+            block_time,
+            direction,
+            net_value,
+            fee,
+            addresses: addresses.into_iter().collect(),
+            memo_status: memos.status(),
+            memos: memos.texts,
+            status,
+        });
+    }
+
+    if filter.memo.is_some() {
+        let entries: Vec<HistoryEntry> = entries
+            .into_iter()
+            .filter(|entry| filter.matches_memo(&entry.memos))
+            .collect();
+        return Ok(HistoryPage {
+            total: entries.len(),
+            offset,
+            entries: entries.into_iter().skip(offset).take(limit).collect(),
+        });
+    }
+
+    let total: i64 = data.query_row(
+        &format!("SELECT COUNT(*) FROM transactions {}", HISTORY_CONDITIONS),
+        &[
+            &account as &dyn ToSql,
+            &sent,
+            &filter.min_height,
+            &filter.max_height,
+        ],
+        |row| row.get(0),
+    )?;
+    Ok(HistoryPage {
+        total: total as usize,
+        offset,
+        entries,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction, HistoryFilter};
+
+    #[test]
+    fn memo_queries_ignore_case() {
+        let memos = vec!["Rent for March".to_owned(), "coffee".to_owned()];
+        assert!(HistoryFilter::default().matches_memo(&[]));
+
+        let rent = HistoryFilter {
+            memo: Some("rent".to_owned()),
+            ..Default::default()
+        };
+        assert!(rent.matches_memo(&memos));
+        assert!(!rent.matches_memo(&memos[1..]));
+        assert!(!rent.matches_memo(&[]));
+
+        let tea = HistoryFilter {
+            memo: Some("TEA".to_owned()),
+            ..Default::default()
+        };
+        assert!(!tea.matches_memo(&memos));
+    }
+
+    #[test]
+    fn direction_codes_are_parsed() {
+        assert_eq!(Direction::from_code(0).unwrap(), None);
+        assert_eq!(Direction::from_code(1).unwrap(), Some(Direction::Sent));
+        assert_eq!(Direction::from_code(2).unwrap(), Some(Direction::Received));
+        assert!(Direction::from_code(3).is_err());
+    }
+}
@@ -0,0 +1,266 @@
+//! A structured view of raw transactions, for support tooling and debug screens.
+
+use failure::{format_err, Error};
+use ff::{PrimeField, PrimeFieldRepr};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::path::Path;
+use zcash_client_backend::decrypt::decrypt_transaction;
+use zcash_client_sqlite::address::RecipientAddress;
+use zcash_primitives::{consensus::BranchId, transaction::Transaction};
+
+use crate::enhance::tracked_extfvks;
+use crate::Network;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TransparentInput {
+    /// The txid of the spent output, as a hex string in display order.
+    pub prevout_txid: String,
+    pub prevout_index: u32,
+    pub script_sig: String,
+    pub sequence: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TransparentOutput {
+    pub value: i64,
+    pub script_pubkey: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SaplingOutput {
+    /// The note commitment, as a hex string in little-endian order.
+    pub cmu: String,
+    pub ephemeral_key: String,
+}
+
+/// An output of the transaction that one of the wallet's viewing keys can decrypt.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DecryptedOutput {
+    /// The index of the output among the transaction's Sapling outputs.
+    pub index: usize,
+    pub account: usize,
+    /// Whether the output was sent by the account, rather than received by it.
+    pub outgoing: bool,
+    pub address: String,
+    pub value: u64,
+    /// The memo, if it is valid UTF-8.
+    pub memo: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TransactionInfo {
+    /// The txid, as a hex string in display order.
+    pub txid: String,
+    pub overwintered: bool,
+    pub version: u32,
+    pub version_group_id: u32,
+    /// The consensus branch active at the expiry height, which is the branch the transaction
+    /// was built for unless it was built shortly before a network upgrade. `None` if the
+    /// transaction does not expire.
+    pub consensus_branch_id: Option<u32>,
+    pub lock_time: u32,
+    pub expiry_height: u32,
+    pub transparent_inputs: Vec<TransparentInput>,
+    pub transparent_outputs: Vec<TransparentOutput>,
+    /// The nullifiers revealed by the Sapling spends, as hex strings.
+    pub sapling_nullifiers: Vec<String>,
+    pub sapling_outputs: Vec<SaplingOutput>,
+    pub joinsplits: usize,
+    /// The net value leaving the Sapling pool, in zatoshis.
+    pub value_balance: i64,
+    pub has_binding_sig: bool,
+    /// The outputs that decrypt to the wallet, if a wallet was given.
+    pub decrypted_outputs: Option<Vec<DecryptedOutput>>,
+}
+
+/// Returns the txid as block explorers display it.
+fn display_txid(txid: &[u8]) -> String {
+    let mut txid = txid.to_vec();
+    txid.reverse();
+    hex::encode(txid)
+}
+
+fn inspect(tx: &Transaction) -> Result<TransactionInfo, Error> {
+    let mut transparent_inputs = vec![];
+    for input in &tx.vin {
+        // An outpoint is the txid followed by the little-endian output index.
+        let mut prevout = vec![];
+        input.prevout.write(&mut prevout)?;
+        let mut index = [0; 4];
+        index.copy_from_slice(&prevout[32..36]);
+        transparent_inputs.push(TransparentInput {
+            prevout_txid: display_txid(&prevout[..32]),
+            prevout_index: u32::from_le_bytes(index),
+            script_sig: hex::encode(&input.script_sig.0),
+            sequence: input.sequence,
+        });
+    }
+
+    let mut sapling_outputs = vec![];
+    for output in &tx.shielded_outputs {
+        let mut cmu = vec![];
+        output.cmu.into_repr().write_le(&mut cmu)?;
+        let mut ephemeral_key = vec![];
+        output.ephemeral_key.write(&mut ephemeral_key)?;
+        sapling_outputs.push(SaplingOutput {
+            cmu: hex::encode(cmu),
+            ephemeral_key: hex::encode(ephemeral_key),
+        });
+    }
+
+    let consensus_branch_id = if tx.expiry_height > 0 {
+        Some(u32::from(BranchId::for_height::<Network>(
+            tx.expiry_height - 1,
+        )))
+    } else {
+        None
+    };
+
+    Ok(TransactionInfo {
+        txid: display_txid(&tx.txid().0),
+        overwintered: tx.overwintered,
+        version: tx.version,
+        version_group_id: tx.version_group_id,
+        consensus_branch_id,
+        lock_time: tx.lock_time,
+        expiry_height: tx.expiry_height,
+        transparent_inputs,
+        transparent_outputs: tx
+            .vout
+            .iter()
+            .map(|output| TransparentOutput {
+                value: i64::from(output.value),
+                script_pubkey: hex::encode(&output.script_pubkey.0),
+            })
+            .collect(),
+        sapling_nullifiers: tx
+            .shielded_spends
+            .iter()
+            .map(|spend| hex::encode(spend.nullifier))
+            .collect(),
+        sapling_outputs,
+        joinsplits: tx.joinsplits.len(),
+        value_balance: i64::from(tx.value_balance),
+        has_binding_sig: tx.binding_sig.is_some(),
+        decrypted_outputs: None,
+    })
+}
+
+/// Parses a raw transaction into a structured view.
+pub fn parse_transaction(raw: &[u8]) -> Result<TransactionInfo, Error> {
+    let tx = Transaction::read(raw).map_err(|e| format_err!("Invalid transaction: {}", e))?;
+    inspect(&tx)
+}
+
+/// Parses a raw transaction, and also reports which of its outputs the accounts in the data
+/// DB can decrypt.
+pub fn parse_transaction_for_wallet<P: AsRef<Path>>(
+    raw: &[u8],
+    db_data: P,
+) -> Result<TransactionInfo, Error> {
+    let tx = Transaction::read(raw).map_err(|e| format_err!("Invalid transaction: {}", e))?;
+    let extfvks = tracked_extfvks(&Connection::open(db_data)?)?;
+
+    let mut info = inspect(&tx)?;
+    info.decrypted_outputs = Some(
+        decrypt_transaction(&tx, &extfvks)
+            .into_iter()
+            .map(|output| DecryptedOutput {
+                index: output.index,
+                account: output.account,
+                outgoing: output.outgoing,
+                address: RecipientAddress::from(output.to).to_string(),
+                value: output.note.value,
+                memo: output.memo.to_utf8().and_then(Result::ok),
+            })
+            .collect(),
+    );
+    Ok(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use zcash_client_backend::keys::spending_key;
+    use zcash_primitives::{
+        legacy::Script,
+        note_encryption::Memo,
+        redjubjub::Signature,
+        transaction::{
+            components::{Amount, TxOut},
+            TransactionData,
+        },
+    };
+
+    use super::{parse_transaction, parse_transaction_for_wallet};
+    use crate::fixtures::{shielded_output, wallet_at_checkpoint};
+
+    #[test]
+    fn transparent_transaction() {
+        let mut tx = TransactionData::new();
+        tx.vout.push(TxOut {
+            value: Amount::from_u64(5000).unwrap(),
+            script_pubkey: Script(vec![0x6a]),
+        });
+        tx.expiry_height = 700_000;
+        let tx = tx.freeze().unwrap();
+        let mut raw = vec![];
+        tx.write(&mut raw).unwrap();
+
+        let info = parse_transaction(&raw).unwrap();
+        assert!(info.overwintered);
+        assert_eq!(info.version, 4);
+        assert_eq!(info.expiry_height, 700_000);
+        assert!(info.consensus_branch_id.is_some());
+        assert!(info.transparent_inputs.is_empty());
+        assert_eq!(info.transparent_outputs.len(), 1);
+        assert_eq!(info.transparent_outputs[0].value, 5000);
+        assert_eq!(info.transparent_outputs[0].script_pubkey, "6a");
+        assert!(info.sapling_nullifiers.is_empty());
+        assert!(info.sapling_outputs.is_empty());
+        assert_eq!(info.value_balance, 0);
+        assert!(!info.has_binding_sig);
+        assert_eq!(info.decrypted_outputs, None);
+
+        let mut txid = tx.txid().0.to_vec();
+        txid.reverse();
+        assert_eq!(info.txid, hex::encode(txid));
+    }
+
+    #[test]
+    fn shielded_outputs_are_decrypted_for_the_wallet() {
+        let wallet = wallet_at_checkpoint().unwrap();
+        let (_, to) = wallet.extfvk.default_address().unwrap();
+        let memo = Memo::from_bytes(b"inspected").unwrap();
+
+        // The second output is to an unrelated key, so it must not be reported.
+        let other = spending_key(&[1; 32], 1, 0);
+        let (_, other_to) = other.default_address().unwrap();
+        let mut tx = TransactionData::new();
+        tx.shielded_outputs
+            .push(shielded_output(other.expsk.ovk, &other_to, 10_000, Memo::default(), 1).unwrap());
+        tx.shielded_outputs
+            .push(shielded_output(wallet.extfvk.fvk.ovk, &to, 25_000, memo, 2).unwrap());
+        tx.binding_sig = Some(Signature::read(&[0u8; 64][..]).unwrap());
+        let mut raw = vec![];
+        tx.freeze().unwrap().write(&mut raw).unwrap();
+
+        let info = parse_transaction_for_wallet(&raw, &wallet.db_data).unwrap();
+        assert_eq!(info.sapling_outputs.len(), 2);
+        let outputs = info.decrypted_outputs.unwrap();
+        assert_eq!(outputs.len(), 1);
+        assert_eq!(outputs[0].index, 1);
+        assert_eq!(outputs[0].account, 0);
+        assert!(!outputs[0].outgoing);
+        assert_eq!(outputs[0].value, 25_000);
+        assert_eq!(
+            outputs[0].memo.as_ref().map(String::as_str),
+            Some("inspected")
+        );
+    }
+
+    #[test]
+    fn garbage_is_rejected() {
+        assert!(parse_transaction(&[0xff; 10]).is_err());
+    }
+}
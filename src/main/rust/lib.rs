@@ -1,49 +1,48 @@
 #[macro_use]
 extern crate log;
 
+pub mod checkpoint;
+mod consolidate;
+#[cfg(any(test, feature = "darkside"))]
+pub mod darkside;
+mod enhance;
+mod expiry;
+#[cfg(any(test, feature = "fixtures"))]
+pub mod fixtures;
+mod history;
+pub mod inspect;
+#[cfg(feature = "lightwalletd")]
+pub mod lightwalletd;
+#[cfg(any(all(test, feature = "lightwalletd"), feature = "mock-lightwalletd"))]
+pub mod mock_lightwalletd;
+mod offline;
+mod reorg;
+mod selection;
+mod sweep;
+pub mod sync;
+mod transact;
+#[cfg(feature = "lightwalletd")]
+pub mod updater;
 mod utils;
+pub mod wallet;
 
 use android_logger::Filter;
 use failure::format_err;
 use jni::{
-    objects::{JClass, JString},
-    sys::{jboolean, jbyteArray, jint, jlong, jobjectArray, jstring, JNI_FALSE, JNI_TRUE},
+    objects::{JClass, JObject, JString},
+    sys::{
+        jboolean, jbyteArray, jint, jintArray, jlong, jlongArray, jobjectArray, jsize, jstring,
+        JNI_FALSE, JNI_TRUE,
+    },
     JNIEnv,
 };
 use log::Level;
-use std::convert::TryFrom;
 use std::panic;
 use std::path::Path;
 use std::ptr;
-use zcash_client_backend::{
-    encoding::{
-        decode_extended_spending_key, encode_extended_full_viewing_key,
-        encode_extended_spending_key, encode_payment_address,
-    },
-    keys::spending_key,
-};
-use zcash_client_sqlite::{
-    address::RecipientAddress,
-    chain::{rewind_to_height, validate_combined_chain},
-    error::ErrorKind,
-    init::{init_accounts_table, init_blocks_table, init_data_database},
-    query::{
-        get_address, get_balance, get_received_memo_as_utf8, get_sent_memo_as_utf8,
-        get_verified_balance,
-    },
-    scan::{decrypt_and_store_transaction, scan_cached_blocks},
-    transact::create_to_address,
-};
-
-use zcash_primitives::{
-    block::BlockHash,
-    consensus::BranchId,
-    note_encryption::Memo,
-    transaction::{components::Amount, Transaction},
-    zip32::{ExtendedFullViewingKey, ExtendedSpendingKey},
-};
-use zcash_proofs::prover::LocalTxProver;
 
+use crate::checkpoint::{Checkpoint, NETWORK};
+use crate::sync::Synchronizer;
 use crate::utils::exception::unwrap_exc_or;
 
 #[cfg(feature = "mainnet")]
@@ -52,18 +51,6 @@ use zcash_primitives::consensus::MainNetwork as Network;
 #[cfg(not(feature = "mainnet"))]
 use zcash_primitives::consensus::TestNetwork as Network;
 
-#[cfg(feature = "mainnet")]
-use zcash_client_backend::constants::mainnet::{
-    COIN_TYPE, HRP_SAPLING_EXTENDED_FULL_VIEWING_KEY, HRP_SAPLING_EXTENDED_SPENDING_KEY,
-    HRP_SAPLING_PAYMENT_ADDRESS,
-};
-#[cfg(not(feature = "mainnet"))]
-use zcash_client_backend::constants::testnet::{
-    COIN_TYPE, HRP_SAPLING_EXTENDED_FULL_VIEWING_KEY, HRP_SAPLING_EXTENDED_SPENDING_KEY,
-    HRP_SAPLING_PAYMENT_ADDRESS,
-};
-use zcash_client_backend::encoding::decode_extended_full_viewing_key;
-
 #[no_mangle]
 pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_initLogs(
     _env: JNIEnv<'_>,
@@ -88,9 +75,7 @@ pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_initDataDb(
     let res = panic::catch_unwind(|| {
         let db_data = utils::java_string_to_rust(&env, db_data);
 
-        init_data_database(&db_data)
-            .map(|()| JNI_TRUE)
-            .map_err(|e| format_err!("Error while initializing data DB: {}", e))
+        wallet::init_data_db(&db_data).map(|()| JNI_TRUE)
     });
     unwrap_exc_or(&env, res, JNI_FALSE)
 }
@@ -112,29 +97,9 @@ pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_initAccount
             return Err(format_err!("accounts argument must be positive"));
         };
 
-        let extsks: Vec<_> = (0..accounts)
-            .map(|account| spending_key(&seed, COIN_TYPE, account))
-            .collect();
-        let extfvks: Vec<_> = extsks.iter().map(ExtendedFullViewingKey::from).collect();
-
-        match init_accounts_table(&db_data, &extfvks) {
-            Ok(()) => {
-                // Return the ExtendedSpendingKeys for the created accounts
-                Ok(utils::rust_vec_to_java(
-                    &env,
-                    extsks,
-                    "java/lang/String",
-                    |env, extsk| {
-                        env.new_string(encode_extended_spending_key(
-                            HRP_SAPLING_EXTENDED_SPENDING_KEY,
-                            &extsk,
-                        ))
-                    },
-                    |env| env.new_string(""),
-                ))
-            }
-            Err(e) => Err(format_err!("Error while initializing accounts: {}", e)),
-        }
+        // Return the ExtendedSpendingKeys for the created accounts
+        wallet::init_accounts_table(&db_data, &seed, accounts)
+            .map(|extsks| utils::rust_string_vec_to_java(&env, extsks))
     });
     unwrap_exc_or(&env, res, ptr::null_mut())
 }
@@ -154,22 +119,8 @@ pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_deriveExten
             return Err(format_err!("accounts argument must be greater than zero"));
         };
 
-        let extsks: Vec<_> = (0..accounts)
-            .map(|account| spending_key(&seed, COIN_TYPE, account))
-            .collect();
-
-        Ok(utils::rust_vec_to_java(
-            &env,
-            extsks,
-            "java/lang/String",
-            |env, extsk| {
-                env.new_string(encode_extended_spending_key(
-                    HRP_SAPLING_EXTENDED_SPENDING_KEY,
-                    &extsk,
-                ))
-            },
-            |env| env.new_string(""),
-        ))
+        let extsks = wallet::derive_extended_spending_keys(&seed, accounts);
+        Ok(utils::rust_string_vec_to_java(&env, extsks))
     });
     unwrap_exc_or(&env, res, ptr::null_mut())
 }
@@ -189,22 +140,8 @@ pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_deriveExten
             return Err(format_err!("accounts argument must be greater than zero"));
         };
 
-        let extfvks: Vec<_> = (0..accounts)
-            .map(|account| ExtendedFullViewingKey::from(&spending_key(&seed, COIN_TYPE, account)))
-            .collect();
-
-        Ok(utils::rust_vec_to_java(
-            &env,
-            extfvks,
-            "java/lang/String",
-            |env, extfvk| {
-                env.new_string(encode_extended_full_viewing_key(
-                    HRP_SAPLING_EXTENDED_FULL_VIEWING_KEY,
-                    &extfvk,
-                ))
-            },
-            |env| env.new_string(""),
-        ))
+        let extfvks = wallet::derive_extended_full_viewing_keys(&seed, accounts);
+        Ok(utils::rust_string_vec_to_java(&env, extfvks))
     });
     unwrap_exc_or(&env, res, ptr::null_mut())
 }
@@ -224,13 +161,9 @@ pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_deriveAddre
             return Err(format_err!("accountIndex argument must be positive"));
         };
 
-        let address = spending_key(&seed, COIN_TYPE, account_index)
-            .default_address()
-            .unwrap()
-            .1;
-        let address_str = encode_payment_address(HRP_SAPLING_PAYMENT_ADDRESS, &address);
+        let address = wallet::derive_address_from_seed(&seed, account_index);
         let output = env
-            .new_string(address_str)
+            .new_string(address)
             .expect("Couldn't create Java string!");
         Ok(output.into_inner())
     });
@@ -245,26 +178,10 @@ pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_deriveAddre
 ) -> jstring {
     let res = panic::catch_unwind(|| {
         let extfvk_string = utils::java_string_to_rust(&env, extfvk_string);
-        let extfvk = match decode_extended_full_viewing_key(
-            HRP_SAPLING_EXTENDED_SPENDING_KEY,
-            &extfvk_string,
-        ) {
-            Ok(Some(extfvk)) => extfvk,
-            Ok(None) => {
-                return Err(format_err!("Deriving viewing key from string returned no results. Encoding was valid but type was incorrect."));
-            }
-            Err(e) => {
-                return Err(format_err!(
-                    "Error while deriving viewing key from string input: {}",
-                    e
-                ));
-            }
-        };
 
-        let address = extfvk.default_address().unwrap().1;
-        let address_str = encode_payment_address(HRP_SAPLING_PAYMENT_ADDRESS, &address);
+        let address = wallet::derive_address_from_viewing_key(&extfvk_string)?;
         let output = env
-            .new_string(address_str)
+            .new_string(address)
             .expect("Couldn't create Java string!");
         Ok(output.into_inner())
     });
@@ -279,29 +196,11 @@ pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_deriveExten
 ) -> jobjectArray {
     let res = panic::catch_unwind(|| {
         let extsk_string = utils::java_string_to_rust(&env, extsk_string);
-        let extfvk = match decode_extended_spending_key(
-            HRP_SAPLING_EXTENDED_SPENDING_KEY,
-            &extsk_string,
-        ) {
-            Ok(Some(extsk)) => ExtendedFullViewingKey::from(&extsk),
-            Ok(None) => {
-                return Err(format_err!("Deriving viewing key from spending key returned no results. Encoding was valid but type was incorrect."));
-            }
-            Err(e) => {
-                return Err(format_err!(
-                    "Error while deriving viewing key from spending key: {}",
-                    e
-                ));
-            }
-        };
 
+        let extfvk = wallet::derive_extended_full_viewing_key(&extsk_string)?;
         let output = env
-            .new_string(encode_extended_full_viewing_key(
-                HRP_SAPLING_EXTENDED_FULL_VIEWING_KEY,
-                &extfvk,
-            ))
+            .new_string(extfvk)
             .expect("Couldn't create Java string!");
-
         Ok(output.into_inner())
     });
     unwrap_exc_or(&env, res, ptr::null_mut())
@@ -316,31 +215,96 @@ pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_initBlocksT
     hash_string: JString<'_>,
     time: jlong,
     sapling_tree_string: JString<'_>,
+    expected_height: jint,
+    expected_root: JString<'_>,
 ) -> jboolean {
     let res = panic::catch_unwind(|| {
         let db_data = utils::java_string_to_rust(&env, db_data);
-        let hash = {
-            let mut hash = hex::decode(utils::java_string_to_rust(&env, hash_string)).unwrap();
-            hash.reverse();
-            BlockHash::from_slice(&hash)
-        };
         let time = if time >= 0 && time <= jlong::from(u32::max_value()) {
             time as u32
         } else {
             return Err(format_err!("time argument must fit in a u32"));
         };
-        let sapling_tree =
-            hex::decode(utils::java_string_to_rust(&env, sapling_tree_string)).unwrap();
+        let checkpoint = Checkpoint {
+            network: NETWORK.to_owned(),
+            height,
+            hash: utils::java_string_to_rust(&env, hash_string),
+            time,
+            tree: utils::java_string_to_rust(&env, sapling_tree_string),
+        };
+        let expected_height = if expected_height >= 0 {
+            Some(expected_height)
+        } else {
+            None
+        };
+        let expected_root = if expected_root.is_null() {
+            None
+        } else {
+            Some(utils::java_string_to_rust(&env, expected_root))
+        };
 
-        debug!("initializing blocks table with height {}", height);
-        match init_blocks_table(&db_data, height, hash, time, &sapling_tree) {
-            Ok(()) => Ok(JNI_TRUE),
-            Err(e) => Err(format_err!("Error while initializing blocks table: {}", e)),
-        }
+        wallet::init_blocks_table(
+            &db_data,
+            &checkpoint,
+            expected_height,
+            expected_root.as_ref().map(String::as_str),
+        )
+        .map(|()| JNI_TRUE)
     });
     unwrap_exc_or(&env, res, JNI_FALSE)
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_initBlocksTableFromCheckpoint(
+    env: JNIEnv<'_>,
+    _: JClass<'_>,
+    db_data: JString<'_>,
+    checkpoint: jbyteArray,
+) -> jint {
+    let res = panic::catch_unwind(|| {
+        let db_data = utils::java_string_to_rust(&env, db_data);
+        let checkpoint = env.convert_byte_array(checkpoint).unwrap();
+
+        wallet::init_blocks_table_from_checkpoint_data(&db_data, &checkpoint)
+    });
+    unwrap_exc_or(&env, res, -1)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_initBlocksTableFromBirthday(
+    env: JNIEnv<'_>,
+    _: JClass<'_>,
+    db_data: JString<'_>,
+    birthday_height: jint,
+) -> jint {
+    let res = panic::catch_unwind(|| {
+        let db_data = utils::java_string_to_rust(&env, db_data);
+
+        wallet::init_blocks_table_from_birthday(&db_data, birthday_height)
+    });
+    unwrap_exc_or(&env, res, -1)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_initBlocksTableFromBirthdayTime(
+    env: JNIEnv<'_>,
+    _: JClass<'_>,
+    db_data: JString<'_>,
+    birthday_time: jlong,
+) -> jint {
+    let res = panic::catch_unwind(|| {
+        let db_data = utils::java_string_to_rust(&env, db_data);
+        let time = if birthday_time >= 0 && birthday_time <= jlong::from(u32::max_value()) {
+            birthday_time as u32
+        } else {
+            return Err(format_err!("time argument must fit in a u32"));
+        };
+
+        wallet::init_blocks_table_from_birthday_time(&db_data, time)
+    });
+    unwrap_exc_or(&env, res, -1)
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_getAddress(
     env: JNIEnv<'_>,
@@ -356,13 +320,9 @@ pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_getAddress(
             return Err(format_err!("account argument must be positive"));
         };
 
-        match get_address(&db_data, account) {
-            Ok(addr) => {
-                let output = env.new_string(addr).expect("Couldn't create Java string!");
-                Ok(output.into_inner())
-            }
-            Err(e) => Err(format_err!("Error while fetching address: {}", e)),
-        }
+        let addr = wallet::get_address(&db_data, account)?;
+        let output = env.new_string(addr).expect("Couldn't create Java string!");
+        Ok(output.into_inner())
     });
     unwrap_exc_or(&env, res, ptr::null_mut())
 }
@@ -376,13 +336,8 @@ pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_isValidShie
     let res = panic::catch_unwind(|| {
         let addr = utils::java_string_to_rust(&env, addr);
 
-        match RecipientAddress::from_str(&addr) {
-            Some(addr) => match addr {
-                RecipientAddress::Shielded(_) => Ok(JNI_TRUE),
-                RecipientAddress::Transparent(_) => Ok(JNI_FALSE),
-            },
-            None => Err(format_err!("Address is for the wrong network")),
-        }
+        wallet::is_valid_shielded_address(&addr)
+            .map(|valid| if valid { JNI_TRUE } else { JNI_FALSE })
     });
     unwrap_exc_or(&env, res, JNI_FALSE)
 }
@@ -396,13 +351,8 @@ pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_isValidTran
     let res = panic::catch_unwind(|| {
         let addr = utils::java_string_to_rust(&env, addr);
 
-        match RecipientAddress::from_str(&addr) {
-            Some(addr) => match addr {
-                RecipientAddress::Shielded(_) => Ok(JNI_FALSE),
-                RecipientAddress::Transparent(_) => Ok(JNI_TRUE),
-            },
-            None => Err(format_err!("Address is for the wrong network")),
-        }
+        wallet::is_valid_transparent_address(&addr)
+            .map(|valid| if valid { JNI_TRUE } else { JNI_FALSE })
     });
     unwrap_exc_or(&env, res, JNI_FALSE)
 }
@@ -422,10 +372,7 @@ pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_getBalance(
             return Err(format_err!("account argument must be positive"));
         };
 
-        match get_balance(&db_data, account) {
-            Ok(balance) => Ok(balance.into()),
-            Err(e) => Err(format_err!("Error while fetching balance: {}", e)),
-        }
+        wallet::get_balance(&db_data, account)
     });
     unwrap_exc_or(&env, res, -1)
 }
@@ -445,10 +392,7 @@ pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_getVerified
             return Err(format_err!("account argument must be positive"));
         };
 
-        match get_verified_balance(&db_data, account) {
-            Ok(balance) => Ok(balance.into()),
-            Err(e) => Err(format_err!("Error while fetching verified balance: {}", e)),
-        }
+        wallet::get_verified_balance(&db_data, account)
     });
     unwrap_exc_or(&env, res, -1)
 }
@@ -463,11 +407,7 @@ pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_getReceived
     let res = panic::catch_unwind(|| {
         let db_data = utils::java_string_to_rust(&env, db_data);
 
-        let memo = match get_received_memo_as_utf8(db_data, id_note) {
-            Ok(memo) => memo.unwrap_or_default(),
-            Err(e) => return Err(format_err!("Error while fetching memo: {}", e)),
-        };
-
+        let memo = wallet::get_received_memo_as_utf8(&db_data, id_note)?;
         let output = env.new_string(memo).expect("Couldn't create Java string!");
         Ok(output.into_inner())
     });
@@ -484,11 +424,7 @@ pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_getSentMemo
     let res = panic::catch_unwind(|| {
         let db_data = utils::java_string_to_rust(&env, db_data);
 
-        let memo = match get_sent_memo_as_utf8(db_data, id_note) {
-            Ok(memo) => memo.unwrap_or_default(),
-            Err(e) => return Err(format_err!("Error while fetching memo: {}", e)),
-        };
-
+        let memo = wallet::get_sent_memo_as_utf8(&db_data, id_note)?;
         let output = env.new_string(memo).expect("Couldn't create Java string!");
         Ok(output.into_inner())
     });
@@ -506,15 +442,7 @@ pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_validateCom
         let db_cache = utils::java_string_to_rust(&env, db_cache);
         let db_data = utils::java_string_to_rust(&env, db_data);
 
-        if let Err(e) = validate_combined_chain(&db_cache, &db_data) {
-            match e.kind() {
-                ErrorKind::InvalidChain(upper_bound, _) => Ok(*upper_bound),
-                _ => Err(format_err!("Error while validating chain: {}", e)),
-            }
-        } else {
-            // All blocks are valid, so "highest invalid block height" is below genesis.
-            Ok(-1)
-        }
+        wallet::validate_combined_chain(&db_cache, &db_data)
     });
     unwrap_exc_or(&env, res, 0)
 }
@@ -529,18 +457,56 @@ pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_rewindToHei
     let res = panic::catch_unwind(|| {
         let db_data = utils::java_string_to_rust(&env, db_data);
 
-        match rewind_to_height(&db_data, height) {
-            Ok(()) => Ok(JNI_TRUE),
-            Err(e) => Err(format_err!(
-                "Error while rewinding data DB to height {}: {}",
-                height,
-                e
-            )),
-        }
+        wallet::rewind_to_height(&db_data, height).map(|()| JNI_TRUE)
     });
     unwrap_exc_or(&env, res, JNI_FALSE)
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_handleChainReorg(
+    env: JNIEnv<'_>,
+    _: JClass<'_>,
+    db_cache: JString<'_>,
+    db_data: JString<'_>,
+    max_rewind: jint,
+    new_blocks: jobjectArray,
+) -> jint {
+    let res = panic::catch_unwind(|| {
+        let db_cache = utils::java_string_to_rust(&env, db_cache);
+        let db_data = utils::java_string_to_rust(&env, db_data);
+        let max_rewind = if max_rewind > 0 {
+            max_rewind as u32
+        } else {
+            return Err(format_err!("maxRewind argument must be positive"));
+        };
+        let new_blocks = utils::java_bytes_vec_to_rust(&env, new_blocks);
+
+        wallet::handle_chain_reorg(&db_cache, &db_data, max_rewind, &new_blocks)
+    });
+    unwrap_exc_or(&env, res, 0)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_syncWallet(
+    env: JNIEnv<'_>,
+    _: JClass<'_>,
+    db_cache: JString<'_>,
+    db_data: JString<'_>,
+    client: JObject<'_>,
+    listener: JObject<'_>,
+) -> jint {
+    let res = panic::catch_unwind(|| {
+        let db_cache = utils::java_string_to_rust(&env, db_cache);
+        let db_data = utils::java_string_to_rust(&env, db_data);
+        let client = utils::client::JavaBlockchainClient::new(&env, client);
+
+        Synchronizer::new(client, db_cache, db_data)
+            .sync(|status| utils::client::report_status(&env, listener, &status))
+            .map(|height| height as jint)
+    });
+    unwrap_exc_or(&env, res, -1)
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_scanBlocks(
     env: JNIEnv<'_>,
@@ -552,10 +518,7 @@ pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_scanBlocks(
         let db_cache = utils::java_string_to_rust(&env, db_cache);
         let db_data = utils::java_string_to_rust(&env, db_data);
 
-        match scan_cached_blocks(&db_cache, &db_data, None) {
-            Ok(()) => Ok(JNI_TRUE),
-            Err(e) => Err(format_err!("Error while scanning blocks: {}", e)),
-        }
+        wallet::scan_blocks(&db_cache, &db_data, None).map(|()| JNI_TRUE)
     });
     unwrap_exc_or(&env, res, JNI_FALSE)
 }
@@ -573,10 +536,7 @@ pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_scanBlockBa
         let db_cache = utils::java_string_to_rust(&env, db_cache);
         let db_data = utils::java_string_to_rust(&env, db_data);
 
-        match scan_cached_blocks(&db_cache, &db_data, Some(limit)) {
-            Ok(()) => Ok(JNI_TRUE),
-            Err(e) => Err(format_err!("Error while scanning blocks: {}", e)),
-        }
+        wallet::scan_blocks(&db_cache, &db_data, Some(limit)).map(|()| JNI_TRUE)
     });
     unwrap_exc_or(&env, res, JNI_FALSE)
 }
@@ -591,16 +551,225 @@ pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_decryptAndS
     let res = panic::catch_unwind(|| {
         let db_data = utils::java_string_to_rust(&env, db_data);
         let tx_bytes = env.convert_byte_array(tx).unwrap();
-        let tx = Transaction::read(&tx_bytes[..])?;
 
-        match decrypt_and_store_transaction(&db_data, &tx) {
-            Ok(()) => Ok(JNI_TRUE),
-            Err(e) => Err(format_err!("Error while decrypting transaction: {}", e)),
-        }
+        wallet::decrypt_and_store_transaction(&db_data, &tx_bytes).map(|()| JNI_TRUE)
     });
     unwrap_exc_or(&env, res, JNI_FALSE)
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_getTransactionsToEnhance(
+    env: JNIEnv<'_>,
+    _: JClass<'_>,
+    db_data: JString<'_>,
+) -> jobjectArray {
+    let res = panic::catch_unwind(|| {
+        let db_data = utils::java_string_to_rust(&env, db_data);
+
+        wallet::transactions_to_enhance(&db_data)
+            .map(|txids| utils::rust_bytes_vec_to_java(&env, txids))
+    });
+    unwrap_exc_or(&env, res, ptr::null_mut())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_enhanceTransactions(
+    env: JNIEnv<'_>,
+    _: JClass<'_>,
+    db_data: JString<'_>,
+    txs: jobjectArray,
+    heights: jintArray,
+) -> jobjectArray {
+    let res = panic::catch_unwind(|| {
+        let db_data = utils::java_string_to_rust(&env, db_data);
+        let txs = utils::java_bytes_vec_to_rust(&env, txs);
+        let mut heights_buf = vec![0; txs.len()];
+        env.get_int_array_region(heights, 0, &mut heights_buf)
+            .map_err(|e| format_err!("heights argument must match transactions: {}", e))?;
+        let batch = txs
+            .into_iter()
+            .zip(heights_buf)
+            .map(|(tx, height)| (tx, if height > 0 { height as u32 } else { 0 }))
+            .collect::<Vec<_>>();
+
+        // Return the txids of the transactions that could not be enhanced, or an empty array
+        // for those that could not be parsed.
+        let failed = wallet::enhance_transactions(&db_data, &batch)?
+            .into_iter()
+            .map(|failure| {
+                error!(
+                    "Failed to enhance transaction {} of the batch: {}",
+                    failure.index, failure.error
+                );
+                failure.txid.unwrap_or_default()
+            })
+            .collect();
+        Ok(utils::rust_bytes_vec_to_java(&env, failed))
+    });
+    unwrap_exc_or(&env, res, ptr::null_mut())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_parseTransaction(
+    env: JNIEnv<'_>,
+    _: JClass<'_>,
+    db_data: JString<'_>,
+    tx: jbyteArray,
+) -> jstring {
+    let res = panic::catch_unwind(|| {
+        let tx_bytes = env.convert_byte_array(tx).unwrap();
+
+        // Only decrypt outputs if a data DB is given
+        let info = if db_data.is_null() {
+            wallet::parse_transaction(&tx_bytes)?
+        } else {
+            let db_data = utils::java_string_to_rust(&env, db_data);
+            wallet::parse_transaction_for_wallet(&tx_bytes, &db_data)?
+        };
+        let output = env
+            .new_string(serde_json::to_string(&info)?)
+            .expect("Couldn't create Java string!");
+        Ok(output.into_inner())
+    });
+    unwrap_exc_or(&env, res, ptr::null_mut())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_getUnminedTransactions(
+    env: JNIEnv<'_>,
+    _: JClass<'_>,
+    db_data: JString<'_>,
+    chain_tip: jint,
+) -> jstring {
+    let res = panic::catch_unwind(|| {
+        let db_data = utils::java_string_to_rust(&env, db_data);
+        let chain_tip = if chain_tip >= 0 {
+            chain_tip as u32
+        } else {
+            return Err(format_err!("chainTip argument must be positive"));
+        };
+
+        let unmined = wallet::unmined_transactions(&db_data, chain_tip)?;
+        let output = env
+            .new_string(serde_json::to_string(&unmined)?)
+            .expect("Couldn't create Java string!");
+        Ok(output.into_inner())
+    });
+    unwrap_exc_or(&env, res, ptr::null_mut())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_getTransactionHistory(
+    env: JNIEnv<'_>,
+    _: JClass<'_>,
+    db_data: JString<'_>,
+    account: jint,
+    chain_tip: jint,
+    direction: jint,
+    min_height: jint,
+    max_height: jint,
+    memo_query: JString<'_>,
+    offset: jint,
+    limit: jint,
+) -> jstring {
+    let res = panic::catch_unwind(|| {
+        let db_data = utils::java_string_to_rust(&env, db_data);
+        let account = if account >= 0 {
+            account as u32
+        } else {
+            return Err(format_err!("account argument must be positive"));
+        };
+        let chain_tip = if chain_tip >= 0 {
+            chain_tip as u32
+        } else {
+            return Err(format_err!("chainTip argument must be positive"));
+        };
+        let filter = wallet::HistoryFilter {
+            direction: wallet::Direction::from_code(direction)?,
+            min_height: if min_height >= 0 {
+                Some(min_height as u32)
+            } else {
+                None
+            },
+            max_height: if max_height >= 0 {
+                Some(max_height as u32)
+            } else {
+                None
+            },
+            memo: if memo_query.is_null() {
+                None
+            } else {
+                Some(utils::java_string_to_rust(&env, memo_query))
+            },
+        };
+        let offset = if offset >= 0 {
+            offset as usize
+        } else {
+            return Err(format_err!("offset argument must be positive"));
+        };
+        let limit = if limit > 0 {
+            limit as usize
+        } else {
+            return Err(format_err!("limit argument must be greater than zero"));
+        };
+
+        let page =
+            wallet::transaction_history(&db_data, account, chain_tip, &filter, offset, limit)?;
+        let output = env
+            .new_string(serde_json::to_string(&page)?)
+            .expect("Couldn't create Java string!");
+        Ok(output.into_inner())
+    });
+    unwrap_exc_or(&env, res, ptr::null_mut())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_expireTransactions(
+    env: JNIEnv<'_>,
+    _: JClass<'_>,
+    db_data: JString<'_>,
+    chain_tip: jint,
+) -> jlongArray {
+    let res = panic::catch_unwind(|| {
+        let db_data = utils::java_string_to_rust(&env, db_data);
+        let chain_tip = if chain_tip >= 0 {
+            chain_tip as u32
+        } else {
+            return Err(format_err!("chainTip argument must be positive"));
+        };
+
+        let expired = wallet::expire_transactions(&db_data, chain_tip)?;
+        let output = env
+            .new_long_array(expired.len() as jsize)
+            .expect("Couldn't create Java array!");
+        env.set_long_array_region(output, 0, &expired)
+            .expect("Couldn't set Java array elements!");
+        Ok(output)
+    });
+    unwrap_exc_or(&env, res, ptr::null_mut())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_getTransactionsToRebroadcast(
+    env: JNIEnv<'_>,
+    _: JClass<'_>,
+    db_data: JString<'_>,
+    chain_tip: jint,
+) -> jobjectArray {
+    let res = panic::catch_unwind(|| {
+        let db_data = utils::java_string_to_rust(&env, db_data);
+        let chain_tip = if chain_tip >= 0 {
+            chain_tip as u32
+        } else {
+            return Err(format_err!("chainTip argument must be positive"));
+        };
+
+        wallet::transactions_to_rebroadcast(&db_data, chain_tip)
+            .map(|txs| utils::rust_bytes_vec_to_java(&env, txs))
+    });
+    unwrap_exc_or(&env, res, ptr::null_mut())
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_createToAddress(
     env: JNIEnv<'_>,
@@ -612,6 +781,10 @@ pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_createToAdd
     to: JString<'_>,
     value: jlong,
     memo: jbyteArray,
+    fee: jlong,
+    expiry_delta: jint,
+    note_selection: jint,
+    max_inputs: jint,
     spend_params: JString<'_>,
     output_params: JString<'_>,
 ) -> jlong {
@@ -624,54 +797,287 @@ pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_createToAdd
         };
         let extsk = utils::java_string_to_rust(&env, extsk);
         let to = utils::java_string_to_rust(&env, to);
-        let value =
-            Amount::from_i64(value).map_err(|()| format_err!("Invalid amount, out of range"))?;
-        if value.is_negative() {
-            return Err(format_err!("Amount is negative"));
-        }
         let memo_bytes = env.convert_byte_array(memo).unwrap();
+        let options =
+            utils::java_transaction_options(fee, expiry_delta, note_selection, max_inputs)?;
         let spend_params = utils::java_string_to_rust(&env, spend_params);
         let output_params = utils::java_string_to_rust(&env, output_params);
 
-        let extsk = match decode_extended_spending_key(HRP_SAPLING_EXTENDED_SPENDING_KEY, &extsk) {
-            Ok(Some(extsk)) => extsk,
-            Ok(None) => {
-                return Err(format_err!("ExtendedSpendingKey is for the wrong network"));
-            }
-            Err(e) => {
-                return Err(format_err!("Invalid ExtendedSpendingKey: {}", e));
-            }
+        wallet::create_to_address(
+            &db_data,
+            consensus_branch_id as u32,
+            account,
+            &extsk,
+            &to,
+            value,
+            &memo_bytes,
+            options,
+            Path::new(&spend_params),
+            Path::new(&output_params),
+        )
+    });
+    unwrap_exc_or(&env, res, -1)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_consolidateNotes(
+    env: JNIEnv<'_>,
+    _: JClass<'_>,
+    db_data: JString<'_>,
+    consensus_branch_id: jlong,
+    account: jint,
+    extsk: JString<'_>,
+    max_inputs: jint,
+    fee: jlong,
+    expiry_delta: jint,
+    spend_params: JString<'_>,
+    output_params: JString<'_>,
+) -> jstring {
+    let res = panic::catch_unwind(|| {
+        let db_data = utils::java_string_to_rust(&env, db_data);
+        let account = if account >= 0 {
+            account as u32
+        } else {
+            return Err(format_err!("account argument must be positive"));
+        };
+        let extsk = utils::java_string_to_rust(&env, extsk);
+        let max_inputs = if max_inputs >= 0 {
+            max_inputs as usize
+        } else {
+            return Err(format_err!("maxInputs argument must be positive"));
         };
+        let options = utils::java_transaction_options(fee, expiry_delta, 0, -1)?;
+        let spend_params = utils::java_string_to_rust(&env, spend_params);
+        let output_params = utils::java_string_to_rust(&env, output_params);
 
-        let to = match RecipientAddress::from_str(&to) {
-            Some(to) => to,
-            None => {
-                return Err(format_err!("Address is for the wrong network"));
-            }
+        let consolidation = wallet::consolidate_notes(
+            &db_data,
+            consensus_branch_id as u32,
+            account,
+            &extsk,
+            max_inputs,
+            options,
+            Path::new(&spend_params),
+            Path::new(&output_params),
+        )?;
+        let output = env
+            .new_string(serde_json::to_string(&consolidation)?)
+            .expect("Couldn't create Java string!");
+        Ok(output.into_inner())
+    });
+    unwrap_exc_or(&env, res, ptr::null_mut())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_getMaxSendable(
+    env: JNIEnv<'_>,
+    _: JClass<'_>,
+    db_data: JString<'_>,
+    account: jint,
+    to: JString<'_>,
+    fee: jlong,
+    max_inputs: jint,
+) -> jstring {
+    let res = panic::catch_unwind(|| {
+        let db_data = utils::java_string_to_rust(&env, db_data);
+        let account = if account >= 0 {
+            account as u32
+        } else {
+            return Err(format_err!("account argument must be positive"));
+        };
+        let to = utils::java_string_to_rust(&env, to);
+        let options = utils::java_transaction_options(fee, -1, 0, -1)?;
+        let max_inputs = if max_inputs >= 0 {
+            Some(max_inputs as usize)
+        } else {
+            None
+        };
+
+        let max_sendable = wallet::max_sendable(&db_data, account, &to, max_inputs, options)?;
+        let output = env
+            .new_string(serde_json::to_string(&max_sendable)?)
+            .expect("Couldn't create Java string!");
+        Ok(output.into_inner())
+    });
+    unwrap_exc_or(&env, res, ptr::null_mut())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_sweepToAddress(
+    env: JNIEnv<'_>,
+    _: JClass<'_>,
+    db_data: JString<'_>,
+    consensus_branch_id: jlong,
+    account: jint,
+    extsk: JString<'_>,
+    to: JString<'_>,
+    memo: jbyteArray,
+    fee: jlong,
+    expiry_delta: jint,
+    max_inputs: jint,
+    spend_params: JString<'_>,
+    output_params: JString<'_>,
+) -> jlong {
+    let res = panic::catch_unwind(|| {
+        let db_data = utils::java_string_to_rust(&env, db_data);
+        let account = if account >= 0 {
+            account as u32
+        } else {
+            return Err(format_err!("account argument must be positive"));
+        };
+        let extsk = utils::java_string_to_rust(&env, extsk);
+        let to = utils::java_string_to_rust(&env, to);
+        let memo_bytes = env.convert_byte_array(memo).unwrap();
+        let options = utils::java_transaction_options(fee, expiry_delta, 0, -1)?;
+        let max_inputs = if max_inputs >= 0 {
+            Some(max_inputs as usize)
+        } else {
+            None
         };
+        let spend_params = utils::java_string_to_rust(&env, spend_params);
+        let output_params = utils::java_string_to_rust(&env, output_params);
+
+        wallet::sweep_to_address(
+            &db_data,
+            consensus_branch_id as u32,
+            account,
+            &extsk,
+            &to,
+            &memo_bytes,
+            max_inputs,
+            options,
+            Path::new(&spend_params),
+            Path::new(&output_params),
+        )
+    });
+    unwrap_exc_or(&env, res, -1)
+}
 
-        let memo = Memo::from_bytes(&memo_bytes);
+#[no_mangle]
+pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_proposeTransfer(
+    env: JNIEnv<'_>,
+    _: JClass<'_>,
+    db_data: JString<'_>,
+    account: jint,
+    value: jlong,
+    fee: jlong,
+    note_selection: jint,
+    max_inputs: jint,
+) -> jstring {
+    let res = panic::catch_unwind(|| {
+        let db_data = utils::java_string_to_rust(&env, db_data);
+        let account = if account >= 0 {
+            account as u32
+        } else {
+            return Err(format_err!("account argument must be positive"));
+        };
+        let options = utils::java_transaction_options(fee, -1, note_selection, max_inputs)?;
 
-        let prover = LocalTxProver::new(Path::new(&spend_params), Path::new(&output_params));
+        let proposal = wallet::propose_transfer(&db_data, account, value, options)?;
+        let output = env
+            .new_string(serde_json::to_string(&proposal)?)
+            .expect("Couldn't create Java string!");
+        Ok(output.into_inner())
+    });
+    unwrap_exc_or(&env, res, ptr::null_mut())
+}
 
-        let branch_id = match BranchId::try_from(consensus_branch_id as u32) {
-            Ok(branch) => branch,
-            Err(e) => {
-                return Err(format_err!("Invalid consensus branch id: {}", e));
-            }
+#[no_mangle]
+pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_createUnsignedTransaction(
+    env: JNIEnv<'_>,
+    _: JClass<'_>,
+    db_data: JString<'_>,
+    consensus_branch_id: jlong,
+    account: jint,
+    to: JString<'_>,
+    value: jlong,
+    memo: jbyteArray,
+    fee: jlong,
+    expiry_delta: jint,
+    note_selection: jint,
+    max_inputs: jint,
+) -> jstring {
+    let res = panic::catch_unwind(|| {
+        let db_data = utils::java_string_to_rust(&env, db_data);
+        let account = if account >= 0 {
+            account as u32
+        } else {
+            return Err(format_err!("account argument must be positive"));
         };
+        let to = utils::java_string_to_rust(&env, to);
+        let memo_bytes = env.convert_byte_array(memo).unwrap();
+        let options =
+            utils::java_transaction_options(fee, expiry_delta, note_selection, max_inputs)?;
 
-        // let branch = if
-        create_to_address(
+        let unsigned = wallet::create_unsigned_transaction(
             &db_data,
-            branch_id,
-            prover,
-            (account, &extsk),
+            consensus_branch_id as u32,
+            account,
             &to,
             value,
-            memo,
+            &memo_bytes,
+            options,
+        )?;
+        let output = env
+            .new_string(unsigned.to_json()?)
+            .expect("Couldn't create Java string!");
+        Ok(output.into_inner())
+    });
+    unwrap_exc_or(&env, res, ptr::null_mut())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_signTransaction(
+    env: JNIEnv<'_>,
+    _: JClass<'_>,
+    unsigned: JString<'_>,
+    extsk: JString<'_>,
+    spend_params: JString<'_>,
+    output_params: JString<'_>,
+) -> jbyteArray {
+    let res = panic::catch_unwind(|| {
+        let unsigned = utils::java_string_to_rust(&env, unsigned);
+        let extsk = utils::java_string_to_rust(&env, extsk);
+        let spend_params = utils::java_string_to_rust(&env, spend_params);
+        let output_params = utils::java_string_to_rust(&env, output_params);
+
+        let raw = wallet::sign_transaction(
+            &wallet::UnsignedTransaction::from_json(&unsigned)?,
+            &extsk,
+            Path::new(&spend_params),
+            Path::new(&output_params),
+        )?;
+        Ok(env
+            .byte_array_from_slice(&raw)
+            .expect("Couldn't create Java array!"))
+    });
+    unwrap_exc_or(&env, res, ptr::null_mut())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_recordSignedTransaction(
+    env: JNIEnv<'_>,
+    _: JClass<'_>,
+    db_data: JString<'_>,
+    unsigned: JString<'_>,
+    tx: jbyteArray,
+    spend_params: JString<'_>,
+    output_params: JString<'_>,
+) -> jlong {
+    let res = panic::catch_unwind(|| {
+        let db_data = utils::java_string_to_rust(&env, db_data);
+        let unsigned = utils::java_string_to_rust(&env, unsigned);
+        let tx_bytes = env.convert_byte_array(tx).unwrap();
+        let spend_params = utils::java_string_to_rust(&env, spend_params);
+        let output_params = utils::java_string_to_rust(&env, output_params);
+
+        wallet::record_signed_transaction(
+            &db_data,
+            &wallet::UnsignedTransaction::from_json(&unsigned)?,
+            &tx_bytes,
+            Path::new(&spend_params),
+            Path::new(&output_params),
         )
-        .map_err(|e| format_err!("Error while creating transaction: {}", e))
     });
     unwrap_exc_or(&env, res, -1)
 }
@@ -682,11 +1088,6 @@ pub unsafe extern "C" fn Java_cash_z_ecc_android_sdk_jni_RustBackend_branchIdFor
     _: JClass<'_>,
     height: jint,
 ) -> jint {
-    let res = panic::catch_unwind(|| {
-        let branch: BranchId = BranchId::for_height::<Network>(height as u32);
-        let branch_id:u32 = u32::from(branch);
-        debug!("For height {} found consensus branch {:?}", height, branch);
-        Ok(branch_id as i32)
-    });
+    let res = panic::catch_unwind(|| Ok(wallet::branch_id_for_height(height as u32) as i32));
     unwrap_exc_or(&env, res, -1)
 }
@@ -0,0 +1,307 @@
+//! A client for lightwalletd's `CompactTxStreamer` gRPC service.
+//!
+//! The Android SDK talks to lightwalletd from Kotlin, but Rust tests and desktop tools such as
+//! `update-sapling-tree` need to do so directly. This module is only built with the
+//! `lightwalletd` feature.
+
+use futures::{Future, Stream};
+use grpc::ClientStub;
+use httpbis::ClientTlsOption;
+use std::fmt;
+use std::io;
+use std::net::ToSocketAddrs;
+use std::sync::Arc;
+use std::time::Duration;
+use tls_api::{TlsConnector, TlsConnectorBuilder};
+use tokio_timer::{TimeoutError, Timer, TimerError};
+use zcash_client_backend::proto::compact_formats::CompactBlock;
+
+use self::proto::service::{
+    BlockID, BlockRange, ChainSpec, Empty, LightdInfo, RawTransaction, TxFilter,
+};
+use self::proto::service_grpc::{CompactTxStreamer, CompactTxStreamerClient};
+
+/// Messages and client stubs generated from `src/main/proto`. They are checked in, and can be
+/// regenerated by building with the `regenerate-protos` feature.
+#[allow(
+    bare_trait_objects,
+    clippy::all,
+    dead_code,
+    missing_docs,
+    non_camel_case_types,
+    non_snake_case,
+    non_upper_case_globals,
+    unused_imports
+)]
+pub mod proto {
+    pub use zcash_client_backend::proto::compact_formats;
+
+    pub mod darkside;
+    pub mod darkside_grpc;
+    pub mod service;
+    pub mod service_grpc;
+}
+
+#[cfg(feature = "mainnet")]
+pub const DEFAULT_HOST: &str = "lightwalletd.z.cash";
+#[cfg(not(feature = "mainnet"))]
+pub const DEFAULT_HOST: &str = "lightwalletd.testnet.z.cash";
+
+pub const DEFAULT_PORT: u16 = 9067;
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Where and how to connect to lightwalletd.
+#[derive(Clone, Debug)]
+pub struct LightwalletdConfig {
+    pub host: String,
+    pub port: u16,
+    /// Whether to use TLS. Only local test servers should be used without it.
+    pub tls: bool,
+    /// How long to wait for a response. For `GetBlockRange` this bounds the wait for each
+    /// block, so that a long range is not cut off while blocks are still arriving.
+    pub timeout: Duration,
+}
+
+impl LightwalletdConfig {
+    /// Configures a TLS connection to the given server, with the default timeout.
+    pub fn new(host: &str, port: u16) -> Self {
+        LightwalletdConfig {
+            host: host.to_owned(),
+            port,
+            tls: true,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+}
+
+impl Default for LightwalletdConfig {
+    /// Configures a connection to the public server for the network this library was built for.
+    fn default() -> Self {
+        LightwalletdConfig::new(DEFAULT_HOST, DEFAULT_PORT)
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Grpc(grpc::Error),
+    Io(io::Error),
+    Tls(tls_api::Error),
+    /// The timeout could not be scheduled.
+    Timer(TimerError),
+    /// The host name did not resolve to any address.
+    UnknownHost(String),
+    /// The server did not respond within the configured timeout.
+    Timeout,
+    /// The server refused to relay a transaction.
+    TransactionRejected(i32, String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Grpc(e) => write!(f, "gRPC error: {:?}", e),
+            Error::Io(e) => write!(f, "I/O error: {}", e),
+            Error::Tls(e) => write!(f, "TLS error: {:?}", e),
+            Error::Timer(e) => write!(f, "Timer error: {:?}", e),
+            Error::UnknownHost(host) => write!(f, "Could not resolve host {}", host),
+            Error::Timeout => write!(f, "Timed out waiting for lightwalletd"),
+            Error::TransactionRejected(code, message) => {
+                write!(f, "Transaction rejected with code {}: {}", code, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<grpc::Error> for Error {
+    fn from(e: grpc::Error) -> Self {
+        Error::Grpc(e)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<tls_api::Error> for Error {
+    fn from(e: tls_api::Error) -> Self {
+        Error::Tls(e)
+    }
+}
+
+impl<T> From<TimeoutError<T>> for Error {
+    fn from(e: TimeoutError<T>) -> Self {
+        match e {
+            TimeoutError::TimedOut(_) => Error::Timeout,
+            TimeoutError::Timer(_, e) => Error::Timer(e),
+        }
+    }
+}
+
+/// Blocks on lightwalletd responses for at most a configured time.
+///
+/// A request that times out is dropped, which cancels it, so nothing is left running. All
+/// the timeouts of one client share a single timer thread.
+#[derive(Clone)]
+pub(crate) struct Timeouts {
+    timer: Timer,
+    timeout: Duration,
+}
+
+impl Timeouts {
+    pub(crate) fn new(timeout: Duration) -> Self {
+        Timeouts {
+            timer: tokio_timer::wheel()
+                .max_timeout(timeout)
+                .thread_name("lightwalletd-timer")
+                .build(),
+            timeout,
+        }
+    }
+
+    /// Waits for a single response.
+    pub(crate) fn wait<T, F>(&self, response: F) -> Result<T, Error>
+    where
+        F: Future<Item = T, Error = grpc::Error>,
+    {
+        self.timer
+            .timeout(response.map_err(Error::from), self.timeout)
+            .wait()
+    }
+
+    /// Collects a streamed response, waiting at most the timeout for each message.
+    pub(crate) fn collect<T, S>(&self, stream: S) -> Result<Vec<T>, Error>
+    where
+        S: Stream<Item = T, Error = grpc::Error>,
+    {
+        self.timer
+            .timeout_stream(stream.map_err(Error::from), self.timeout)
+            .collect()
+            .wait()
+    }
+}
+
+fn block_id(height: u64) -> BlockID {
+    let mut id = BlockID::new();
+    id.set_height(height);
+    id
+}
+
+/// Sets up a gRPC client for the configured server. The connection itself is made lazily.
+pub(crate) fn grpc_client(config: &LightwalletdConfig) -> Result<grpc::Client, Error> {
+    let client_conf = Default::default();
+
+    if config.tls {
+        // If the host is behind a load balancer which resolves to multiple IP
+        // addresses, we need to select one ourselves and then use it directly, as a
+        // workaround for https://github.com/stepancheg/rust-http2/issues/7
+        let socket = (config.host.as_str(), config.port)
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| Error::UnknownHost(config.host.clone()))?;
+
+        let tls = {
+            let mut tls_connector = tls_api_rustls::TlsConnector::builder()?;
+
+            if tls_api_rustls::TlsConnector::supports_alpn() {
+                tls_connector.set_alpn_protocols(&[b"h2"])?;
+            }
+
+            let tls_connector = tls_connector.build()?;
+
+            let tls_connector = Arc::new(tls_connector);
+            ClientTlsOption::Tls(config.host.clone(), tls_connector)
+        };
+
+        Ok(grpc::Client::new_expl::<tls_api_rustls::TlsConnector>(
+            &socket,
+            &config.host,
+            tls,
+            client_conf,
+        )?)
+    } else {
+        Ok(grpc::Client::new_plain(
+            &config.host,
+            config.port,
+            client_conf,
+        )?)
+    }
+}
+
+/// A blocking client for a single lightwalletd server.
+pub struct LightwalletdClient {
+    client: CompactTxStreamerClient,
+    timeouts: Timeouts,
+}
+
+impl LightwalletdClient {
+    /// Sets up a client for the configured server. The connection itself is made lazily.
+    pub fn connect(config: &LightwalletdConfig) -> Result<Self, Error> {
+        Ok(LightwalletdClient {
+            client: CompactTxStreamerClient::with_client(Arc::new(grpc_client(config)?)),
+            timeouts: Timeouts::new(config.timeout),
+        })
+    }
+
+    /// Returns the tip of the server's best chain (`GetLatestBlock`).
+    pub fn latest_block(&self) -> Result<BlockID, Error> {
+        let response = self
+            .client
+            .get_latest_block(grpc::RequestOptions::new(), ChainSpec::new())
+            .drop_metadata();
+        self.timeouts.wait(response)
+    }
+
+    /// Returns the compact blocks in the inclusive range `start..=end` (`GetBlockRange`).
+    pub fn block_range(&self, start: u64, end: u64) -> Result<Vec<CompactBlock>, Error> {
+        let mut range = BlockRange::new();
+        range.set_start(block_id(start));
+        range.set_end(block_id(end));
+        let blocks = self
+            .client
+            .get_block_range(grpc::RequestOptions::new(), range)
+            .drop_metadata();
+        self.timeouts.collect(blocks)
+    }
+
+    /// Returns a full transaction by its txid, in internal byte order (`GetTransaction`).
+    pub fn transaction(&self, txid: &[u8]) -> Result<RawTransaction, Error> {
+        let mut filter = TxFilter::new();
+        filter.set_hash(txid.to_vec());
+        let response = self
+            .client
+            .get_transaction(grpc::RequestOptions::new(), filter)
+            .drop_metadata();
+        self.timeouts.wait(response)
+    }
+
+    /// Submits a serialized transaction to the network (`SendTransaction`).
+    pub fn send_transaction(&self, tx: &[u8]) -> Result<(), Error> {
+        let mut raw = RawTransaction::new();
+        raw.set_data(tx.to_vec());
+        let response = self
+            .client
+            .send_transaction(grpc::RequestOptions::new(), raw)
+            .drop_metadata();
+        let response = self.timeouts.wait(response)?;
+        if response.get_errorCode() != 0 {
+            return Err(Error::TransactionRejected(
+                response.get_errorCode(),
+                response.get_errorMessage().to_owned(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the server's version and view of the chain (`GetLightdInfo`).
+    pub fn lightd_info(&self) -> Result<LightdInfo, Error> {
+        let response = self
+            .client
+            .get_lightd_info(grpc::RequestOptions::new(), Empty::new())
+            .drop_metadata();
+        self.timeouts.wait(response)
+    }
+}
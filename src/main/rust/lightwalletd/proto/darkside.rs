@@ -0,0 +1,1255 @@
+// This file is generated by rust-protobuf 2.8.2. Do not edit
+// @generated
+
+// https://github.com/Manishearth/rust-clippy/issues/702
+#![allow(unknown_lints)]
+#![allow(clippy::all)]
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+#![allow(box_pointers)]
+#![allow(dead_code)]
+#![allow(missing_docs)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(trivial_casts)]
+#![allow(unsafe_code)]
+#![allow(unused_imports)]
+#![allow(unused_results)]
+//! Generated file from `darkside.proto`
+
+use protobuf::Message as Message_imported_for_functions;
+use protobuf::ProtobufEnum as ProtobufEnum_imported_for_functions;
+
+/// Generated files are compatible only with the same version
+/// of protobuf runtime.
+const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_2_8_2;
+
+#[derive(PartialEq,Clone,Default)]
+pub struct DarksideMetaState {
+    // message fields
+    pub saplingActivation: i32,
+    pub branchID: ::std::string::String,
+    pub chainName: ::std::string::String,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a DarksideMetaState {
+    fn default() -> &'a DarksideMetaState {
+        <DarksideMetaState as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl DarksideMetaState {
+    pub fn new() -> DarksideMetaState {
+        ::std::default::Default::default()
+    }
+
+    // int32 saplingActivation = 1;
+
+
+    pub fn get_saplingActivation(&self) -> i32 {
+        self.saplingActivation
+    }
+    pub fn clear_saplingActivation(&mut self) {
+        self.saplingActivation = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_saplingActivation(&mut self, v: i32) {
+        self.saplingActivation = v;
+    }
+
+    // string branchID = 2;
+
+
+    pub fn get_branchID(&self) -> &str {
+        &self.branchID
+    }
+    pub fn clear_branchID(&mut self) {
+        self.branchID.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_branchID(&mut self, v: ::std::string::String) {
+        self.branchID = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_branchID(&mut self) -> &mut ::std::string::String {
+        &mut self.branchID
+    }
+
+    // Take field
+    pub fn take_branchID(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.branchID, ::std::string::String::new())
+    }
+
+    // string chainName = 3;
+
+
+    pub fn get_chainName(&self) -> &str {
+        &self.chainName
+    }
+    pub fn clear_chainName(&mut self) {
+        self.chainName.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_chainName(&mut self, v: ::std::string::String) {
+        self.chainName = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_chainName(&mut self) -> &mut ::std::string::String {
+        &mut self.chainName
+    }
+
+    // Take field
+    pub fn take_chainName(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.chainName, ::std::string::String::new())
+    }
+}
+
+impl ::protobuf::Message for DarksideMetaState {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int32()?;
+                    self.saplingActivation = tmp;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.branchID)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.chainName)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.saplingActivation != 0 {
+            my_size += ::protobuf::rt::value_size(1, self.saplingActivation, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.branchID.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.branchID);
+        }
+        if !self.chainName.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.chainName);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if self.saplingActivation != 0 {
+            os.write_int32(1, self.saplingActivation)?;
+        }
+        if !self.branchID.is_empty() {
+            os.write_string(2, &self.branchID)?;
+        }
+        if !self.chainName.is_empty() {
+            os.write_string(3, &self.chainName)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> DarksideMetaState {
+        DarksideMetaState::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt32>(
+                    "saplingActivation",
+                    |m: &DarksideMetaState| { &m.saplingActivation },
+                    |m: &mut DarksideMetaState| { &mut m.saplingActivation },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "branchID",
+                    |m: &DarksideMetaState| { &m.branchID },
+                    |m: &mut DarksideMetaState| { &mut m.branchID },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "chainName",
+                    |m: &DarksideMetaState| { &m.chainName },
+                    |m: &mut DarksideMetaState| { &mut m.chainName },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<DarksideMetaState>(
+                    "DarksideMetaState",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static DarksideMetaState {
+        static mut instance: ::protobuf::lazy::Lazy<DarksideMetaState> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const DarksideMetaState,
+        };
+        unsafe {
+            instance.get(DarksideMetaState::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for DarksideMetaState {
+    fn clear(&mut self) {
+        self.saplingActivation = 0;
+        self.branchID.clear();
+        self.chainName.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for DarksideMetaState {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for DarksideMetaState {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct DarksideBlock {
+    // message fields
+    pub block: ::std::string::String,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a DarksideBlock {
+    fn default() -> &'a DarksideBlock {
+        <DarksideBlock as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl DarksideBlock {
+    pub fn new() -> DarksideBlock {
+        ::std::default::Default::default()
+    }
+
+    // string block = 1;
+
+
+    pub fn get_block(&self) -> &str {
+        &self.block
+    }
+    pub fn clear_block(&mut self) {
+        self.block.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_block(&mut self, v: ::std::string::String) {
+        self.block = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_block(&mut self) -> &mut ::std::string::String {
+        &mut self.block
+    }
+
+    // Take field
+    pub fn take_block(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.block, ::std::string::String::new())
+    }
+}
+
+impl ::protobuf::Message for DarksideBlock {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.block)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.block.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.block);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.block.is_empty() {
+            os.write_string(1, &self.block)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> DarksideBlock {
+        DarksideBlock::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "block",
+                    |m: &DarksideBlock| { &m.block },
+                    |m: &mut DarksideBlock| { &mut m.block },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<DarksideBlock>(
+                    "DarksideBlock",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static DarksideBlock {
+        static mut instance: ::protobuf::lazy::Lazy<DarksideBlock> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const DarksideBlock,
+        };
+        unsafe {
+            instance.get(DarksideBlock::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for DarksideBlock {
+    fn clear(&mut self) {
+        self.block.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for DarksideBlock {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for DarksideBlock {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct DarksideBlocksURL {
+    // message fields
+    pub url: ::std::string::String,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a DarksideBlocksURL {
+    fn default() -> &'a DarksideBlocksURL {
+        <DarksideBlocksURL as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl DarksideBlocksURL {
+    pub fn new() -> DarksideBlocksURL {
+        ::std::default::Default::default()
+    }
+
+    // string url = 1;
+
+
+    pub fn get_url(&self) -> &str {
+        &self.url
+    }
+    pub fn clear_url(&mut self) {
+        self.url.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_url(&mut self, v: ::std::string::String) {
+        self.url = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_url(&mut self) -> &mut ::std::string::String {
+        &mut self.url
+    }
+
+    // Take field
+    pub fn take_url(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.url, ::std::string::String::new())
+    }
+}
+
+impl ::protobuf::Message for DarksideBlocksURL {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.url)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.url.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.url);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.url.is_empty() {
+            os.write_string(1, &self.url)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> DarksideBlocksURL {
+        DarksideBlocksURL::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "url",
+                    |m: &DarksideBlocksURL| { &m.url },
+                    |m: &mut DarksideBlocksURL| { &mut m.url },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<DarksideBlocksURL>(
+                    "DarksideBlocksURL",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static DarksideBlocksURL {
+        static mut instance: ::protobuf::lazy::Lazy<DarksideBlocksURL> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const DarksideBlocksURL,
+        };
+        unsafe {
+            instance.get(DarksideBlocksURL::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for DarksideBlocksURL {
+    fn clear(&mut self) {
+        self.url.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for DarksideBlocksURL {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for DarksideBlocksURL {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct DarksideTransactionsURL {
+    // message fields
+    pub height: i32,
+    pub url: ::std::string::String,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a DarksideTransactionsURL {
+    fn default() -> &'a DarksideTransactionsURL {
+        <DarksideTransactionsURL as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl DarksideTransactionsURL {
+    pub fn new() -> DarksideTransactionsURL {
+        ::std::default::Default::default()
+    }
+
+    // int32 height = 1;
+
+
+    pub fn get_height(&self) -> i32 {
+        self.height
+    }
+    pub fn clear_height(&mut self) {
+        self.height = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_height(&mut self, v: i32) {
+        self.height = v;
+    }
+
+    // string url = 2;
+
+
+    pub fn get_url(&self) -> &str {
+        &self.url
+    }
+    pub fn clear_url(&mut self) {
+        self.url.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_url(&mut self, v: ::std::string::String) {
+        self.url = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_url(&mut self) -> &mut ::std::string::String {
+        &mut self.url
+    }
+
+    // Take field
+    pub fn take_url(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.url, ::std::string::String::new())
+    }
+}
+
+impl ::protobuf::Message for DarksideTransactionsURL {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int32()?;
+                    self.height = tmp;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.url)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.height != 0 {
+            my_size += ::protobuf::rt::value_size(1, self.height, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.url.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.url);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if self.height != 0 {
+            os.write_int32(1, self.height)?;
+        }
+        if !self.url.is_empty() {
+            os.write_string(2, &self.url)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> DarksideTransactionsURL {
+        DarksideTransactionsURL::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt32>(
+                    "height",
+                    |m: &DarksideTransactionsURL| { &m.height },
+                    |m: &mut DarksideTransactionsURL| { &mut m.height },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "url",
+                    |m: &DarksideTransactionsURL| { &m.url },
+                    |m: &mut DarksideTransactionsURL| { &mut m.url },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<DarksideTransactionsURL>(
+                    "DarksideTransactionsURL",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static DarksideTransactionsURL {
+        static mut instance: ::protobuf::lazy::Lazy<DarksideTransactionsURL> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const DarksideTransactionsURL,
+        };
+        unsafe {
+            instance.get(DarksideTransactionsURL::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for DarksideTransactionsURL {
+    fn clear(&mut self) {
+        self.height = 0;
+        self.url.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for DarksideTransactionsURL {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for DarksideTransactionsURL {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct DarksideHeight {
+    // message fields
+    pub height: i32,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a DarksideHeight {
+    fn default() -> &'a DarksideHeight {
+        <DarksideHeight as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl DarksideHeight {
+    pub fn new() -> DarksideHeight {
+        ::std::default::Default::default()
+    }
+
+    // int32 height = 1;
+
+
+    pub fn get_height(&self) -> i32 {
+        self.height
+    }
+    pub fn clear_height(&mut self) {
+        self.height = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_height(&mut self, v: i32) {
+        self.height = v;
+    }
+}
+
+impl ::protobuf::Message for DarksideHeight {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int32()?;
+                    self.height = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.height != 0 {
+            my_size += ::protobuf::rt::value_size(1, self.height, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if self.height != 0 {
+            os.write_int32(1, self.height)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> DarksideHeight {
+        DarksideHeight::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt32>(
+                    "height",
+                    |m: &DarksideHeight| { &m.height },
+                    |m: &mut DarksideHeight| { &mut m.height },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<DarksideHeight>(
+                    "DarksideHeight",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static DarksideHeight {
+        static mut instance: ::protobuf::lazy::Lazy<DarksideHeight> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const DarksideHeight,
+        };
+        unsafe {
+            instance.get(DarksideHeight::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for DarksideHeight {
+    fn clear(&mut self) {
+        self.height = 0;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for DarksideHeight {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for DarksideHeight {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct DarksideEmptyBlocks {
+    // message fields
+    pub height: i32,
+    pub nonce: i32,
+    pub count: i32,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a DarksideEmptyBlocks {
+    fn default() -> &'a DarksideEmptyBlocks {
+        <DarksideEmptyBlocks as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl DarksideEmptyBlocks {
+    pub fn new() -> DarksideEmptyBlocks {
+        ::std::default::Default::default()
+    }
+
+    // int32 height = 1;
+
+
+    pub fn get_height(&self) -> i32 {
+        self.height
+    }
+    pub fn clear_height(&mut self) {
+        self.height = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_height(&mut self, v: i32) {
+        self.height = v;
+    }
+
+    // int32 nonce = 2;
+
+
+    pub fn get_nonce(&self) -> i32 {
+        self.nonce
+    }
+    pub fn clear_nonce(&mut self) {
+        self.nonce = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_nonce(&mut self, v: i32) {
+        self.nonce = v;
+    }
+
+    // int32 count = 3;
+
+
+    pub fn get_count(&self) -> i32 {
+        self.count
+    }
+    pub fn clear_count(&mut self) {
+        self.count = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_count(&mut self, v: i32) {
+        self.count = v;
+    }
+}
+
+impl ::protobuf::Message for DarksideEmptyBlocks {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int32()?;
+                    self.height = tmp;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int32()?;
+                    self.nonce = tmp;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int32()?;
+                    self.count = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.height != 0 {
+            my_size += ::protobuf::rt::value_size(1, self.height, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.nonce != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.nonce, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.count != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.count, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if self.height != 0 {
+            os.write_int32(1, self.height)?;
+        }
+        if self.nonce != 0 {
+            os.write_int32(2, self.nonce)?;
+        }
+        if self.count != 0 {
+            os.write_int32(3, self.count)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> DarksideEmptyBlocks {
+        DarksideEmptyBlocks::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt32>(
+                    "height",
+                    |m: &DarksideEmptyBlocks| { &m.height },
+                    |m: &mut DarksideEmptyBlocks| { &mut m.height },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt32>(
+                    "nonce",
+                    |m: &DarksideEmptyBlocks| { &m.nonce },
+                    |m: &mut DarksideEmptyBlocks| { &mut m.nonce },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt32>(
+                    "count",
+                    |m: &DarksideEmptyBlocks| { &m.count },
+                    |m: &mut DarksideEmptyBlocks| { &mut m.count },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<DarksideEmptyBlocks>(
+                    "DarksideEmptyBlocks",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static DarksideEmptyBlocks {
+        static mut instance: ::protobuf::lazy::Lazy<DarksideEmptyBlocks> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const DarksideEmptyBlocks,
+        };
+        unsafe {
+            instance.get(DarksideEmptyBlocks::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for DarksideEmptyBlocks {
+    fn clear(&mut self) {
+        self.height = 0;
+        self.nonce = 0;
+        self.count = 0;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for DarksideEmptyBlocks {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for DarksideEmptyBlocks {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+static file_descriptor_proto_data: &'static [u8] = b"\
+    \n\x0edarkside.proto\x12\x15cash.z.wallet.sdk.rpc\x1a\rservice.proto\"{\
+    \n\x11DarksideMetaState\x12,\n\x11saplingActivation\x18\x01\x20\x01(\x05\
+    R\x11saplingActivation\x12\x1a\n\x08branchID\x18\x02\x20\x01(\tR\x08bran\
+    chID\x12\x1c\n\tchainName\x18\x03\x20\x01(\tR\tchainName\"%\n\rDarksideB\
+    lock\x12\x14\n\x05block\x18\x01\x20\x01(\tR\x05block\"%\n\x11DarksideBlo\
+    cksURL\x12\x10\n\x03url\x18\x01\x20\x01(\tR\x03url\"C\n\x17DarksideTrans\
+    actionsURL\x12\x16\n\x06height\x18\x01\x20\x01(\x05R\x06height\x12\x10\n\
+    \x03url\x18\x02\x20\x01(\tR\x03url\"(\n\x0eDarksideHeight\x12\x16\n\x06h\
+    eight\x18\x01\x20\x01(\x05R\x06height\"Y\n\x13DarksideEmptyBlocks\x12\
+    \x16\n\x06height\x18\x01\x20\x01(\x05R\x06height\x12\x14\n\x05nonce\x18\
+    \x02\x20\x01(\x05R\x05nonce\x12\x14\n\x05count\x18\x03\x20\x01(\x05R\x05\
+    count2\xc8\x06\n\x10DarksideStreamer\x12O\n\x05Reset\x12(.cash.z.wallet.\
+    sdk.rpc.DarksideMetaState\x1a\x1c.cash.z.wallet.sdk.rpc.Empty\x12Y\n\x11\
+    StageBlocksStream\x12$.cash.z.wallet.sdk.rpc.DarksideBlock\x1a\x1c.cash.\
+    z.wallet.sdk.rpc.Empty(\x01\x12U\n\x0bStageBlocks\x12(.cash.z.wallet.sdk\
+    .rpc.DarksideBlocksURL\x1a\x1c.cash.z.wallet.sdk.rpc.Empty\x12]\n\x11Sta\
+    geBlocksCreate\x12*.cash.z.wallet.sdk.rpc.DarksideEmptyBlocks\x1a\x1c.ca\
+    sh.z.wallet.sdk.rpc.Empty\x12`\n\x17StageTransactionsStream\x12%.cash.z.\
+    wallet.sdk.rpc.RawTransaction\x1a\x1c.cash.z.wallet.sdk.rpc.Empty(\x01\
+    \x12a\n\x11StageTransactions\x12..cash.z.wallet.sdk.rpc.DarksideTransact\
+    ionsURL\x1a\x1c.cash.z.wallet.sdk.rpc.Empty\x12R\n\x0bApplyStaged\x12%.c\
+    ash.z.wallet.sdk.rpc.DarksideHeight\x1a\x1c.cash.z.wallet.sdk.rpc.Empty\
+    \x12`\n\x17GetIncomingTransactions\x12\x1c.cash.z.wallet.sdk.rpc.Empty\
+    \x1a%.cash.z.wallet.sdk.rpc.RawTransaction0\x01\x12W\n\x19ClearIncomingT\
+    ransactions\x12\x1c.cash.z.wallet.sdk.rpc.Empty\x1a\x1c.cash.z.wallet.sd\
+    k.rpc.EmptyB\x10Z\x0b.;walletrpc\xba\x02\0b\x06proto3\
+";
+
+static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
+    lock: ::protobuf::lazy::ONCE_INIT,
+    ptr: 0 as *const ::protobuf::descriptor::FileDescriptorProto,
+};
+
+fn parse_descriptor_proto() -> ::protobuf::descriptor::FileDescriptorProto {
+    ::protobuf::parse_from_bytes(file_descriptor_proto_data).unwrap()
+}
+
+pub fn file_descriptor_proto() -> &'static ::protobuf::descriptor::FileDescriptorProto {
+    unsafe {
+        file_descriptor_proto_lazy.get(|| {
+            parse_descriptor_proto()
+        })
+    }
+}
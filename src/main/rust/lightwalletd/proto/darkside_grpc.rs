@@ -0,0 +1,280 @@
+// This file is generated. Do not edit
+// @generated
+
+// https://github.com/Manishearth/rust-clippy/issues/702
+#![allow(unknown_lints)]
+#![allow(clippy::all)]
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+#![allow(box_pointers)]
+#![allow(dead_code)]
+#![allow(missing_docs)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(trivial_casts)]
+#![allow(unsafe_code)]
+#![allow(unused_imports)]
+#![allow(unused_results)]
+
+
+// interface
+
+pub trait DarksideStreamer {
+    fn reset(&self, o: ::grpc::RequestOptions, p: super::darkside::DarksideMetaState) -> ::grpc::SingleResponse<super::service::Empty>;
+
+    fn stage_blocks_stream(&self, o: ::grpc::RequestOptions, p: ::grpc::StreamingRequest<super::darkside::DarksideBlock>) -> ::grpc::SingleResponse<super::service::Empty>;
+
+    fn stage_blocks(&self, o: ::grpc::RequestOptions, p: super::darkside::DarksideBlocksURL) -> ::grpc::SingleResponse<super::service::Empty>;
+
+    fn stage_blocks_create(&self, o: ::grpc::RequestOptions, p: super::darkside::DarksideEmptyBlocks) -> ::grpc::SingleResponse<super::service::Empty>;
+
+    fn stage_transactions_stream(&self, o: ::grpc::RequestOptions, p: ::grpc::StreamingRequest<super::service::RawTransaction>) -> ::grpc::SingleResponse<super::service::Empty>;
+
+    fn stage_transactions(&self, o: ::grpc::RequestOptions, p: super::darkside::DarksideTransactionsURL) -> ::grpc::SingleResponse<super::service::Empty>;
+
+    fn apply_staged(&self, o: ::grpc::RequestOptions, p: super::darkside::DarksideHeight) -> ::grpc::SingleResponse<super::service::Empty>;
+
+    fn get_incoming_transactions(&self, o: ::grpc::RequestOptions, p: super::service::Empty) -> ::grpc::StreamingResponse<super::service::RawTransaction>;
+
+    fn clear_incoming_transactions(&self, o: ::grpc::RequestOptions, p: super::service::Empty) -> ::grpc::SingleResponse<super::service::Empty>;
+}
+
+// client
+
+pub struct DarksideStreamerClient {
+    grpc_client: ::std::sync::Arc<::grpc::Client>,
+    method_Reset: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::darkside::DarksideMetaState, super::service::Empty>>,
+    method_StageBlocksStream: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::darkside::DarksideBlock, super::service::Empty>>,
+    method_StageBlocks: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::darkside::DarksideBlocksURL, super::service::Empty>>,
+    method_StageBlocksCreate: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::darkside::DarksideEmptyBlocks, super::service::Empty>>,
+    method_StageTransactionsStream: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::service::RawTransaction, super::service::Empty>>,
+    method_StageTransactions: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::darkside::DarksideTransactionsURL, super::service::Empty>>,
+    method_ApplyStaged: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::darkside::DarksideHeight, super::service::Empty>>,
+    method_GetIncomingTransactions: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::service::Empty, super::service::RawTransaction>>,
+    method_ClearIncomingTransactions: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::service::Empty, super::service::Empty>>,
+}
+
+impl ::grpc::ClientStub for DarksideStreamerClient {
+    fn with_client(grpc_client: ::std::sync::Arc<::grpc::Client>) -> Self {
+        DarksideStreamerClient {
+            grpc_client: grpc_client,
+            method_Reset: ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                name: "/cash.z.wallet.sdk.rpc.DarksideStreamer/Reset".to_string(),
+                streaming: ::grpc::rt::GrpcStreaming::Unary,
+                req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+            }),
+            method_StageBlocksStream: ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                name: "/cash.z.wallet.sdk.rpc.DarksideStreamer/StageBlocksStream".to_string(),
+                streaming: ::grpc::rt::GrpcStreaming::ClientStreaming,
+                req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+            }),
+            method_StageBlocks: ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                name: "/cash.z.wallet.sdk.rpc.DarksideStreamer/StageBlocks".to_string(),
+                streaming: ::grpc::rt::GrpcStreaming::Unary,
+                req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+            }),
+            method_StageBlocksCreate: ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                name: "/cash.z.wallet.sdk.rpc.DarksideStreamer/StageBlocksCreate".to_string(),
+                streaming: ::grpc::rt::GrpcStreaming::Unary,
+                req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+            }),
+            method_StageTransactionsStream: ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                name: "/cash.z.wallet.sdk.rpc.DarksideStreamer/StageTransactionsStream".to_string(),
+                streaming: ::grpc::rt::GrpcStreaming::ClientStreaming,
+                req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+            }),
+            method_StageTransactions: ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                name: "/cash.z.wallet.sdk.rpc.DarksideStreamer/StageTransactions".to_string(),
+                streaming: ::grpc::rt::GrpcStreaming::Unary,
+                req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+            }),
+            method_ApplyStaged: ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                name: "/cash.z.wallet.sdk.rpc.DarksideStreamer/ApplyStaged".to_string(),
+                streaming: ::grpc::rt::GrpcStreaming::Unary,
+                req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+            }),
+            method_GetIncomingTransactions: ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                name: "/cash.z.wallet.sdk.rpc.DarksideStreamer/GetIncomingTransactions".to_string(),
+                streaming: ::grpc::rt::GrpcStreaming::ServerStreaming,
+                req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+            }),
+            method_ClearIncomingTransactions: ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                name: "/cash.z.wallet.sdk.rpc.DarksideStreamer/ClearIncomingTransactions".to_string(),
+                streaming: ::grpc::rt::GrpcStreaming::Unary,
+                req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+            }),
+        }
+    }
+}
+
+impl DarksideStreamer for DarksideStreamerClient {
+    fn reset(&self, o: ::grpc::RequestOptions, p: super::darkside::DarksideMetaState) -> ::grpc::SingleResponse<super::service::Empty> {
+        self.grpc_client.call_unary(o, p, self.method_Reset.clone())
+    }
+
+    fn stage_blocks_stream(&self, o: ::grpc::RequestOptions, p: ::grpc::StreamingRequest<super::darkside::DarksideBlock>) -> ::grpc::SingleResponse<super::service::Empty> {
+        self.grpc_client.call_client_streaming(o, p, self.method_StageBlocksStream.clone())
+    }
+
+    fn stage_blocks(&self, o: ::grpc::RequestOptions, p: super::darkside::DarksideBlocksURL) -> ::grpc::SingleResponse<super::service::Empty> {
+        self.grpc_client.call_unary(o, p, self.method_StageBlocks.clone())
+    }
+
+    fn stage_blocks_create(&self, o: ::grpc::RequestOptions, p: super::darkside::DarksideEmptyBlocks) -> ::grpc::SingleResponse<super::service::Empty> {
+        self.grpc_client.call_unary(o, p, self.method_StageBlocksCreate.clone())
+    }
+
+    fn stage_transactions_stream(&self, o: ::grpc::RequestOptions, p: ::grpc::StreamingRequest<super::service::RawTransaction>) -> ::grpc::SingleResponse<super::service::Empty> {
+        self.grpc_client.call_client_streaming(o, p, self.method_StageTransactionsStream.clone())
+    }
+
+    fn stage_transactions(&self, o: ::grpc::RequestOptions, p: super::darkside::DarksideTransactionsURL) -> ::grpc::SingleResponse<super::service::Empty> {
+        self.grpc_client.call_unary(o, p, self.method_StageTransactions.clone())
+    }
+
+    fn apply_staged(&self, o: ::grpc::RequestOptions, p: super::darkside::DarksideHeight) -> ::grpc::SingleResponse<super::service::Empty> {
+        self.grpc_client.call_unary(o, p, self.method_ApplyStaged.clone())
+    }
+
+    fn get_incoming_transactions(&self, o: ::grpc::RequestOptions, p: super::service::Empty) -> ::grpc::StreamingResponse<super::service::RawTransaction> {
+        self.grpc_client.call_server_streaming(o, p, self.method_GetIncomingTransactions.clone())
+    }
+
+    fn clear_incoming_transactions(&self, o: ::grpc::RequestOptions, p: super::service::Empty) -> ::grpc::SingleResponse<super::service::Empty> {
+        self.grpc_client.call_unary(o, p, self.method_ClearIncomingTransactions.clone())
+    }
+}
+
+// server
+
+pub struct DarksideStreamerServer;
+
+
+impl DarksideStreamerServer {
+    pub fn new_service_def<H : DarksideStreamer + 'static + Sync + Send + 'static>(handler: H) -> ::grpc::rt::ServerServiceDefinition {
+        let handler_arc = ::std::sync::Arc::new(handler);
+        ::grpc::rt::ServerServiceDefinition::new("/cash.z.wallet.sdk.rpc.DarksideStreamer",
+            vec![
+                ::grpc::rt::ServerMethod::new(
+                    ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                        name: "/cash.z.wallet.sdk.rpc.DarksideStreamer/Reset".to_string(),
+                        streaming: ::grpc::rt::GrpcStreaming::Unary,
+                        req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                        resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                    }),
+                    {
+                        let handler_copy = handler_arc.clone();
+                        ::grpc::rt::MethodHandlerUnary::new(move |o, p| handler_copy.reset(o, p))
+                    },
+                ),
+                ::grpc::rt::ServerMethod::new(
+                    ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                        name: "/cash.z.wallet.sdk.rpc.DarksideStreamer/StageBlocksStream".to_string(),
+                        streaming: ::grpc::rt::GrpcStreaming::ClientStreaming,
+                        req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                        resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                    }),
+                    {
+                        let handler_copy = handler_arc.clone();
+                        ::grpc::rt::MethodHandlerClientStreaming::new(move |o, p| handler_copy.stage_blocks_stream(o, p))
+                    },
+                ),
+                ::grpc::rt::ServerMethod::new(
+                    ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                        name: "/cash.z.wallet.sdk.rpc.DarksideStreamer/StageBlocks".to_string(),
+                        streaming: ::grpc::rt::GrpcStreaming::Unary,
+                        req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                        resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                    }),
+                    {
+                        let handler_copy = handler_arc.clone();
+                        ::grpc::rt::MethodHandlerUnary::new(move |o, p| handler_copy.stage_blocks(o, p))
+                    },
+                ),
+                ::grpc::rt::ServerMethod::new(
+                    ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                        name: "/cash.z.wallet.sdk.rpc.DarksideStreamer/StageBlocksCreate".to_string(),
+                        streaming: ::grpc::rt::GrpcStreaming::Unary,
+                        req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                        resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                    }),
+                    {
+                        let handler_copy = handler_arc.clone();
+                        ::grpc::rt::MethodHandlerUnary::new(move |o, p| handler_copy.stage_blocks_create(o, p))
+                    },
+                ),
+                ::grpc::rt::ServerMethod::new(
+                    ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                        name: "/cash.z.wallet.sdk.rpc.DarksideStreamer/StageTransactionsStream".to_string(),
+                        streaming: ::grpc::rt::GrpcStreaming::ClientStreaming,
+                        req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                        resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                    }),
+                    {
+                        let handler_copy = handler_arc.clone();
+                        ::grpc::rt::MethodHandlerClientStreaming::new(move |o, p| handler_copy.stage_transactions_stream(o, p))
+                    },
+                ),
+                ::grpc::rt::ServerMethod::new(
+                    ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                        name: "/cash.z.wallet.sdk.rpc.DarksideStreamer/StageTransactions".to_string(),
+                        streaming: ::grpc::rt::GrpcStreaming::Unary,
+                        req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                        resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                    }),
+                    {
+                        let handler_copy = handler_arc.clone();
+                        ::grpc::rt::MethodHandlerUnary::new(move |o, p| handler_copy.stage_transactions(o, p))
+                    },
+                ),
+                ::grpc::rt::ServerMethod::new(
+                    ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                        name: "/cash.z.wallet.sdk.rpc.DarksideStreamer/ApplyStaged".to_string(),
+                        streaming: ::grpc::rt::GrpcStreaming::Unary,
+                        req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                        resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                    }),
+                    {
+                        let handler_copy = handler_arc.clone();
+                        ::grpc::rt::MethodHandlerUnary::new(move |o, p| handler_copy.apply_staged(o, p))
+                    },
+                ),
+                ::grpc::rt::ServerMethod::new(
+                    ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                        name: "/cash.z.wallet.sdk.rpc.DarksideStreamer/GetIncomingTransactions".to_string(),
+                        streaming: ::grpc::rt::GrpcStreaming::ServerStreaming,
+                        req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                        resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                    }),
+                    {
+                        let handler_copy = handler_arc.clone();
+                        ::grpc::rt::MethodHandlerServerStreaming::new(move |o, p| handler_copy.get_incoming_transactions(o, p))
+                    },
+                ),
+                ::grpc::rt::ServerMethod::new(
+                    ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                        name: "/cash.z.wallet.sdk.rpc.DarksideStreamer/ClearIncomingTransactions".to_string(),
+                        streaming: ::grpc::rt::GrpcStreaming::Unary,
+                        req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                        resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                    }),
+                    {
+                        let handler_copy = handler_arc.clone();
+                        ::grpc::rt::MethodHandlerUnary::new(move |o, p| handler_copy.clear_incoming_transactions(o, p))
+                    },
+                ),
+            ],
+        )
+    }
+}
@@ -3,7 +3,7 @@
 
 // https://github.com/Manishearth/rust-clippy/issues/702
 #![allow(unknown_lints)]
-#![allow(clippy)]
+#![allow(clippy::all)]
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 
@@ -31,6 +31,12 @@ pub trait CompactTxStreamer {
     fn get_transaction(&self, o: ::grpc::RequestOptions, p: super::service::TxFilter) -> ::grpc::SingleResponse<super::service::RawTransaction>;
 
     fn send_transaction(&self, o: ::grpc::RequestOptions, p: super::service::RawTransaction) -> ::grpc::SingleResponse<super::service::SendResponse>;
+
+    fn get_address_txids(&self, o: ::grpc::RequestOptions, p: super::service::TransparentAddressBlockFilter) -> ::grpc::StreamingResponse<super::service::RawTransaction>;
+
+    fn get_lightd_info(&self, o: ::grpc::RequestOptions, p: super::service::Empty) -> ::grpc::SingleResponse<super::service::LightdInfo>;
+
+    fn ping(&self, o: ::grpc::RequestOptions, p: super::service::Duration) -> ::grpc::SingleResponse<super::service::PingResponse>;
 }
 
 // client
@@ -42,6 +48,9 @@ pub struct CompactTxStreamerClient {
     method_GetBlockRange: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::service::BlockRange, super::compact_formats::CompactBlock>>,
     method_GetTransaction: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::service::TxFilter, super::service::RawTransaction>>,
     method_SendTransaction: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::service::RawTransaction, super::service::SendResponse>>,
+    method_GetAddressTxids: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::service::TransparentAddressBlockFilter, super::service::RawTransaction>>,
+    method_GetLightdInfo: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::service::Empty, super::service::LightdInfo>>,
+    method_Ping: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::service::Duration, super::service::PingResponse>>,
 }
 
 impl ::grpc::ClientStub for CompactTxStreamerClient {
@@ -78,6 +87,24 @@ impl ::grpc::ClientStub for CompactTxStreamerClient {
                 req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
                 resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
             }),
+            method_GetAddressTxids: ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                name: "/cash.z.wallet.sdk.rpc.CompactTxStreamer/GetAddressTxids".to_string(),
+                streaming: ::grpc::rt::GrpcStreaming::ServerStreaming,
+                req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+            }),
+            method_GetLightdInfo: ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                name: "/cash.z.wallet.sdk.rpc.CompactTxStreamer/GetLightdInfo".to_string(),
+                streaming: ::grpc::rt::GrpcStreaming::Unary,
+                req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+            }),
+            method_Ping: ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                name: "/cash.z.wallet.sdk.rpc.CompactTxStreamer/Ping".to_string(),
+                streaming: ::grpc::rt::GrpcStreaming::Unary,
+                req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+            }),
         }
     }
 }
@@ -102,6 +129,18 @@ impl CompactTxStreamer for CompactTxStreamerClient {
     fn send_transaction(&self, o: ::grpc::RequestOptions, p: super::service::RawTransaction) -> ::grpc::SingleResponse<super::service::SendResponse> {
         self.grpc_client.call_unary(o, p, self.method_SendTransaction.clone())
     }
+
+    fn get_address_txids(&self, o: ::grpc::RequestOptions, p: super::service::TransparentAddressBlockFilter) -> ::grpc::StreamingResponse<super::service::RawTransaction> {
+        self.grpc_client.call_server_streaming(o, p, self.method_GetAddressTxids.clone())
+    }
+
+    fn get_lightd_info(&self, o: ::grpc::RequestOptions, p: super::service::Empty) -> ::grpc::SingleResponse<super::service::LightdInfo> {
+        self.grpc_client.call_unary(o, p, self.method_GetLightdInfo.clone())
+    }
+
+    fn ping(&self, o: ::grpc::RequestOptions, p: super::service::Duration) -> ::grpc::SingleResponse<super::service::PingResponse> {
+        self.grpc_client.call_unary(o, p, self.method_Ping.clone())
+    }
 }
 
 // server
@@ -174,6 +213,42 @@ impl CompactTxStreamerServer {
                         ::grpc::rt::MethodHandlerUnary::new(move |o, p| handler_copy.send_transaction(o, p))
                     },
                 ),
+                ::grpc::rt::ServerMethod::new(
+                    ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                        name: "/cash.z.wallet.sdk.rpc.CompactTxStreamer/GetAddressTxids".to_string(),
+                        streaming: ::grpc::rt::GrpcStreaming::ServerStreaming,
+                        req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                        resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                    }),
+                    {
+                        let handler_copy = handler_arc.clone();
+                        ::grpc::rt::MethodHandlerServerStreaming::new(move |o, p| handler_copy.get_address_txids(o, p))
+                    },
+                ),
+                ::grpc::rt::ServerMethod::new(
+                    ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                        name: "/cash.z.wallet.sdk.rpc.CompactTxStreamer/GetLightdInfo".to_string(),
+                        streaming: ::grpc::rt::GrpcStreaming::Unary,
+                        req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                        resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                    }),
+                    {
+                        let handler_copy = handler_arc.clone();
+                        ::grpc::rt::MethodHandlerUnary::new(move |o, p| handler_copy.get_lightd_info(o, p))
+                    },
+                ),
+                ::grpc::rt::ServerMethod::new(
+                    ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                        name: "/cash.z.wallet.sdk.rpc.CompactTxStreamer/Ping".to_string(),
+                        streaming: ::grpc::rt::GrpcStreaming::Unary,
+                        req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                        resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                    }),
+                    {
+                        let handler_copy = handler_arc.clone();
+                        ::grpc::rt::MethodHandlerUnary::new(move |o, p| handler_copy.ping(o, p))
+                    },
+                ),
             ],
         )
     }
@@ -0,0 +1,474 @@
+//! An in-process lightwalletd for tests.
+//!
+//! [`MockServer`] implements the generated `CompactTxStreamer` service on localhost, serving
+//! whatever chain the test gives it: fixture blocks, blocks from the chain generator, or a
+//! chain that the test changes to simulate a reorg. Faults such as dropped streams, wrong
+//! hashes, gaps and slow responses can be switched on with [`Faults`], so that client code
+//! like [`LightwalletdClient`] and `update-sapling-tree` can be tested without the network.
+
+use failure::{format_err, Error};
+use futures::{future, stream, sync::oneshot, Future};
+use std::collections::HashMap;
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::Duration;
+use zcash_client_backend::proto::compact_formats::CompactBlock;
+
+use crate::checkpoint::NETWORK;
+use crate::lightwalletd::proto::service::{
+    self, BlockID, BlockRange, ChainSpec, Empty, LightdInfo, PingResponse, RawTransaction,
+    SendResponse, TransparentAddressBlockFilter, TxFilter,
+};
+use crate::lightwalletd::proto::service_grpc::{CompactTxStreamer, CompactTxStreamerServer};
+use crate::lightwalletd::LightwalletdConfig;
+
+/// Misbehaviour for the [`MockServer`] to simulate.
+#[derive(Clone, Debug, Default)]
+pub struct Faults {
+    /// End `GetBlockRange` streams with an error after this many blocks.
+    pub drop_stream_after: Option<usize>,
+    /// Heights whose blocks are served with a corrupted hash.
+    pub wrong_hashes: Vec<u64>,
+    /// Heights whose blocks are left out of `GetBlockRange` responses.
+    pub gaps: Vec<u64>,
+    /// How long to wait before every response.
+    pub delay: Option<Duration>,
+    /// Number of requests to fail outright before answering normally again.
+    pub failed_requests: usize,
+    /// Error code and message with which to reject every `SendTransaction`.
+    pub reject_transactions: Option<(i32, String)>,
+}
+
+#[derive(Default)]
+struct State {
+    blocks: Vec<CompactBlock>,
+    /// Mined transactions by txid, with the height they were mined at.
+    transactions: HashMap<Vec<u8>, (Vec<u8>, u64)>,
+    sent_transactions: Vec<Vec<u8>>,
+    faults: Faults,
+}
+
+type ResponseFuture<T> = Box<dyn Future<Item = T, Error = grpc::Error> + Send>;
+
+/// Resolves to `res`, after the configured delay if there is one.
+fn delayed<T: Send + 'static>(
+    res: Result<T, grpc::Error>,
+    delay: Option<Duration>,
+) -> ResponseFuture<T> {
+    match delay {
+        None => Box::new(future::result(res)),
+        Some(delay) => {
+            let (tx, rx) = oneshot::channel();
+            thread::spawn(move || {
+                thread::sleep(delay);
+                let _ = tx.send(res);
+            });
+            Box::new(rx.then(|res| match res {
+                Ok(res) => res,
+                Err(_) => Err(grpc::Error::Other("mock server shut down")),
+            }))
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Handler {
+    state: Arc<Mutex<State>>,
+}
+
+impl Handler {
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state
+            .lock()
+            .expect("mock server state is not poisoned")
+    }
+
+    /// Runs `f` against the state, applying the faults common to all requests.
+    fn respond<T, F>(&self, f: F) -> ResponseFuture<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut State) -> Result<T, grpc::Error>,
+    {
+        let mut state = self.state();
+        let res = if state.faults.failed_requests > 0 {
+            state.faults.failed_requests -= 1;
+            Err(grpc::Error::Other("injected failure"))
+        } else {
+            f(&mut state)
+        };
+        delayed(res, state.faults.delay)
+    }
+
+    fn single<T, F>(&self, f: F) -> grpc::SingleResponse<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut State) -> Result<T, grpc::Error>,
+    {
+        grpc::SingleResponse::no_metadata(self.respond(f))
+    }
+
+    fn streaming<T, F>(&self, f: F) -> grpc::StreamingResponse<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut State) -> Result<Vec<Result<T, grpc::Error>>, grpc::Error>,
+    {
+        grpc::StreamingResponse::no_metadata(
+            self.respond(f).map(stream::iter_result).flatten_stream(),
+        )
+    }
+}
+
+impl CompactTxStreamer for Handler {
+    fn get_latest_block(
+        &self,
+        _: grpc::RequestOptions,
+        _: ChainSpec,
+    ) -> grpc::SingleResponse<BlockID> {
+        self.single(|state| match state.blocks.last() {
+            Some(tip) => {
+                let mut id = BlockID::new();
+                id.set_height(tip.height);
+                id.set_hash(tip.hash.clone());
+                Ok(id)
+            }
+            None => Err(grpc::Error::Other("no blocks")),
+        })
+    }
+
+    fn get_block(&self, _: grpc::RequestOptions, p: BlockID) -> grpc::SingleResponse<CompactBlock> {
+        self.single(move |state| {
+            state
+                .blocks
+                .iter()
+                .find(|block| block.height == p.get_height())
+                .cloned()
+                .ok_or(grpc::Error::Other("block not found"))
+        })
+    }
+
+    fn get_block_range(
+        &self,
+        _: grpc::RequestOptions,
+        p: BlockRange,
+    ) -> grpc::StreamingResponse<CompactBlock> {
+        self.streaming(move |state| {
+            let start = p.get_start().get_height();
+            let end = p.get_end().get_height();
+            let faults = &state.faults;
+            let mut blocks: Vec<_> = state
+                .blocks
+                .iter()
+                .filter(|block| block.height >= start && block.height <= end)
+                .filter(|block| !faults.gaps.contains(&block.height))
+                .map(|block| {
+                    let mut block = block.clone();
+                    if faults.wrong_hashes.contains(&block.height) {
+                        block.hash[0] ^= 0xff;
+                    }
+                    Ok(block)
+                })
+                .collect();
+            if let Some(count) = faults.drop_stream_after {
+                if count < blocks.len() {
+                    blocks.truncate(count);
+                    blocks.push(Err(grpc::Error::Other("stream dropped")));
+                }
+            }
+            Ok(blocks)
+        })
+    }
+
+    fn get_transaction(
+        &self,
+        _: grpc::RequestOptions,
+        p: TxFilter,
+    ) -> grpc::SingleResponse<RawTransaction> {
+        self.single(move |state| match state.transactions.get(p.get_hash()) {
+            Some((data, height)) => {
+                let mut tx = RawTransaction::new();
+                tx.set_data(data.clone());
+                tx.set_height(*height);
+                Ok(tx)
+            }
+            None => Err(grpc::Error::Other("transaction not found")),
+        })
+    }
+
+    fn send_transaction(
+        &self,
+        _: grpc::RequestOptions,
+        p: RawTransaction,
+    ) -> grpc::SingleResponse<SendResponse> {
+        self.single(move |state| {
+            let mut response = SendResponse::new();
+            match state.faults.reject_transactions {
+                Some((code, ref message)) => {
+                    response.set_errorCode(code);
+                    response.set_errorMessage(message.clone());
+                }
+                None => state.sent_transactions.push(p.get_data().to_vec()),
+            }
+            Ok(response)
+        })
+    }
+
+    fn get_address_txids(
+        &self,
+        _: grpc::RequestOptions,
+        _: TransparentAddressBlockFilter,
+    ) -> grpc::StreamingResponse<RawTransaction> {
+        self.streaming(|_| Ok(vec![]))
+    }
+
+    fn get_lightd_info(
+        &self,
+        _: grpc::RequestOptions,
+        _: Empty,
+    ) -> grpc::SingleResponse<LightdInfo> {
+        self.single(|state| {
+            let mut info = LightdInfo::new();
+            info.set_version("mock".to_owned());
+            info.set_vendor("zcash-android-wallet-sdk".to_owned());
+            info.set_chainName(if NETWORK == "mainnet" { "main" } else { "test" }.to_owned());
+            if let Some(first) = state.blocks.first() {
+                info.set_saplingActivationHeight(first.height);
+            }
+            if let Some(tip) = state.blocks.last() {
+                info.set_blockHeight(tip.height);
+            }
+            Ok(info)
+        })
+    }
+
+    fn ping(
+        &self,
+        _: grpc::RequestOptions,
+        _: service::Duration,
+    ) -> grpc::SingleResponse<PingResponse> {
+        self.single(|_| Ok(PingResponse::new()))
+    }
+}
+
+/// A `CompactTxStreamer` server on a free localhost port, stopped when dropped.
+pub struct MockServer {
+    _server: grpc::Server,
+    port: u16,
+    handler: Handler,
+}
+
+impl MockServer {
+    /// Starts a server for the given chain, which must be in height order.
+    pub fn start(blocks: Vec<CompactBlock>) -> Result<Self, Error> {
+        // Reserve a free port, then let the server bind it.
+        let port = TcpListener::bind("127.0.0.1:0")?.local_addr()?.port();
+
+        let handler = Handler {
+            state: Arc::new(Mutex::new(State {
+                blocks,
+                ..State::default()
+            })),
+        };
+        let mut builder = grpc::ServerBuilder::new_plain();
+        builder
+            .http
+            .set_addr(("127.0.0.1", port))
+            .map_err(|e| format_err!("Could not bind mock server: {:?}", e))?;
+        builder.add_service(CompactTxStreamerServer::new_service_def(handler.clone()));
+        let server = builder
+            .build()
+            .map_err(|e| format_err!("Could not start mock server: {:?}", e))?;
+
+        Ok(MockServer {
+            _server: server,
+            port,
+            handler,
+        })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Returns a client configuration for this server.
+    pub fn config(&self) -> LightwalletdConfig {
+        let mut config = LightwalletdConfig::new("127.0.0.1", self.port);
+        config.tls = false;
+        config
+    }
+
+    /// Replaces the served chain, e.g. with a fork to simulate a reorg.
+    pub fn set_blocks(&self, blocks: Vec<CompactBlock>) {
+        self.handler.state().blocks = blocks;
+    }
+
+    /// Makes a full transaction available through `GetTransaction`.
+    pub fn add_transaction(&self, txid: &[u8], data: Vec<u8>, height: u64) {
+        self.handler
+            .state()
+            .transactions
+            .insert(txid.to_vec(), (data, height));
+    }
+
+    pub fn set_faults(&self, faults: Faults) {
+        self.handler.state().faults = faults;
+    }
+
+    /// Returns the transactions received through `SendTransaction`.
+    pub fn sent_transactions(&self) -> Vec<Vec<u8>> {
+        self.handler.state().sent_transactions.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rusqlite::Connection;
+    use std::time::Duration;
+    use zcash_client_backend::proto::compact_formats::CompactBlock;
+    use zcash_client_sqlite::init::init_data_database;
+
+    use super::{Faults, MockServer};
+    use crate::checkpoint::{init_blocks_table_from_checkpoint, CheckpointRegistry};
+    use crate::lightwalletd::{Error, LightwalletdClient};
+    use crate::sync::Synchronizer;
+
+    fn chain(start: u64, count: u64) -> Vec<CompactBlock> {
+        let mut blocks: Vec<CompactBlock> = vec![];
+        for height in start..start + count {
+            let mut block = CompactBlock::new();
+            block.set_height(height);
+            block.set_hash(vec![height as u8; 32]);
+            if let Some(prev) = blocks.last() {
+                block.set_prevHash(prev.hash.clone());
+            }
+            blocks.push(block);
+        }
+        blocks
+    }
+
+    fn heights(blocks: &[CompactBlock]) -> Vec<u64> {
+        blocks.iter().map(|block| block.height).collect()
+    }
+
+    #[test]
+    fn serves_blocks() {
+        let server = MockServer::start(chain(1000, 20)).unwrap();
+        let client = LightwalletdClient::connect(&server.config()).unwrap();
+
+        assert_eq!(client.latest_block().unwrap().get_height(), 1019);
+        let blocks = client.block_range(1005, 1009).unwrap();
+        assert_eq!(heights(&blocks), vec![1005, 1006, 1007, 1008, 1009]);
+        assert_eq!(client.lightd_info().unwrap().get_blockHeight(), 1019);
+    }
+
+    #[test]
+    fn injects_block_faults() {
+        let server = MockServer::start(chain(1000, 20)).unwrap();
+        let client = LightwalletdClient::connect(&server.config()).unwrap();
+
+        server.set_faults(Faults {
+            gaps: vec![1002],
+            wrong_hashes: vec![1003],
+            ..Faults::default()
+        });
+        let blocks = client.block_range(1000, 1004).unwrap();
+        assert_eq!(heights(&blocks), vec![1000, 1001, 1003, 1004]);
+        assert_ne!(blocks[2].hash, vec![(1003 % 256) as u8; 32]);
+
+        server.set_faults(Faults {
+            drop_stream_after: Some(2),
+            ..Faults::default()
+        });
+        match client.block_range(1000, 1004) {
+            Err(Error::Grpc(_)) => (),
+            res => panic!("unexpected result {:?}", res.map(|b| heights(&b))),
+        }
+    }
+
+    #[test]
+    fn fails_requests_then_recovers() {
+        let server = MockServer::start(chain(1000, 5)).unwrap();
+        let client = LightwalletdClient::connect(&server.config()).unwrap();
+
+        server.set_faults(Faults {
+            failed_requests: 1,
+            ..Faults::default()
+        });
+        assert!(client.latest_block().is_err());
+        assert_eq!(client.latest_block().unwrap().get_height(), 1004);
+    }
+
+    #[test]
+    fn slow_responses_time_out() {
+        let server = MockServer::start(chain(1000, 5)).unwrap();
+        let mut config = server.config();
+        config.timeout = Duration::from_millis(100);
+        let client = LightwalletdClient::connect(&config).unwrap();
+
+        server.set_faults(Faults {
+            delay: Some(Duration::from_secs(2)),
+            ..Faults::default()
+        });
+        match client.latest_block() {
+            Err(Error::Timeout) => (),
+            res => panic!("unexpected result {:?}", res),
+        }
+    }
+
+    #[test]
+    fn sends_and_fetches_transactions() {
+        let server = MockServer::start(chain(1000, 5)).unwrap();
+        let client = LightwalletdClient::connect(&server.config()).unwrap();
+
+        client.send_transaction(&[1, 2, 3]).unwrap();
+        assert_eq!(server.sent_transactions(), vec![vec![1, 2, 3]]);
+
+        server.add_transaction(&[7; 32], vec![4, 5, 6], 1003);
+        let tx = client.transaction(&[7; 32]).unwrap();
+        assert_eq!(tx.get_data(), &[4, 5, 6]);
+        assert_eq!(tx.get_height(), 1003);
+
+        server.set_faults(Faults {
+            reject_transactions: Some((-26, "bad-txns".to_owned())),
+            ..Faults::default()
+        });
+        match client.send_transaction(&[1, 2, 3]) {
+            Err(Error::TransactionRejected(-26, _)) => (),
+            res => panic!("unexpected result {:?}", res),
+        }
+    }
+
+    #[test]
+    fn synchronizer_survives_faults() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_cache = dir.path().join("cache.db");
+        let db_data = dir.path().join("data.db");
+        Connection::open(&db_cache)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE compactblocks (height INTEGER PRIMARY KEY, data BLOB NOT NULL)",
+            )
+            .unwrap();
+        init_data_database(&db_data).unwrap();
+        let registry = CheckpointRegistry::bundled().unwrap();
+        let checkpoint = registry.checkpoints().last().unwrap();
+        init_blocks_table_from_checkpoint(&db_data, checkpoint).unwrap();
+
+        // Serve empty blocks on top of the checkpoint block.
+        let birthday = checkpoint.height as u64;
+        let mut blocks = chain(birthday + 1, 150);
+        blocks[0].set_prevHash(checkpoint.block_hash().unwrap().0.to_vec());
+        let server = MockServer::start(blocks).unwrap();
+        let client = LightwalletdClient::connect(&server.config()).unwrap();
+        let sync = Synchronizer::new(client, &db_cache, &db_data);
+
+        server.set_faults(Faults {
+            drop_stream_after: Some(10),
+            ..Faults::default()
+        });
+        assert!(sync.sync(|_| ()).is_err());
+
+        server.set_faults(Faults::default());
+        assert_eq!(sync.sync(|_| ()).unwrap() as u64, birthday + 150);
+    }
+}
@@ -0,0 +1,667 @@
+//! Offline signing of transactions, for spending keys kept on a device that never syncs.
+//!
+//! The watch-only wallet, which holds the data DB but only viewing keys, selects notes and
+//! packages everything needed to spend them with [`create_unsigned_transaction`]. The package
+//! is serialized as JSON and carried to the signing device, where [`sign_transaction`] creates
+//! the proofs and signatures with the spending key and no data DB. The finished transaction is
+//! carried back, and [`record_signed_transaction`] verifies its proofs and signatures and
+//! checks that it does what the package said before storing it as if it had been created by
+//! `create_to_address`.
+
+use bellman::groth16::{PreparedVerifyingKey, Proof};
+use failure::{format_err, Error};
+use ff::{PrimeField, PrimeFieldRepr};
+use pairing::bls12_381::Bls12;
+use rusqlite::{types::ToSql, Connection};
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::path::Path;
+use zcash_client_backend::{
+    decrypt::decrypt_transaction, encoding::encode_extended_full_viewing_key,
+};
+use zcash_client_sqlite::address::RecipientAddress;
+use zcash_primitives::{
+    consensus::BranchId,
+    merkle_tree::{Hashable, IncrementalWitness},
+    note_encryption::Memo,
+    redjubjub::PublicKey,
+    sapling::Node,
+    transaction::{signature_hash_data, Transaction, SIGHASH_ALL},
+    zip32::ExtendedFullViewingKey,
+    JUBJUB,
+};
+use zcash_proofs::{load_parameters, prover::LocalTxProver, sapling::SaplingVerificationContext};
+
+use crate::transact::{
+    build_transaction, check_balance, read_note, select_notes, store_sent_transaction,
+    target_and_anchor_heights, tracked_extfvk, SpendableNote, TransactionOptions,
+};
+use crate::wallet::{decode_extfvk, decode_extsk, parse_address, parse_value};
+
+#[cfg(feature = "mainnet")]
+use zcash_client_backend::constants::mainnet::HRP_SAPLING_EXTENDED_FULL_VIEWING_KEY;
+#[cfg(not(feature = "mainnet"))]
+use zcash_client_backend::constants::testnet::HRP_SAPLING_EXTENDED_FULL_VIEWING_KEY;
+
+/// The version of the unsigned transaction format written by this crate.
+pub const UNSIGNED_TRANSACTION_VERSION: u32 = 1;
+
+/// The BLAKE2b hashes of the Sapling parameter files, which `LocalTxProver` also checks.
+const SAPLING_SPEND_HASH: &str = "8270785a1a0d0bc77196f000ee6d221c9c9894f55307bd9357c3f0105d31ca63991ab91324160d8f53e2bbd3c2633a6eb8bdf5205d822e7f3f73edac51b2b70c";
+const SAPLING_OUTPUT_HASH: &str = "657e3d38dbb5cb5e7dd2970e8b03d69b4787dd907285b5a7f0790dcc8072f60bf593b32cc2d1c030e00ff5ae64bf84c5c3beb84ddc841d48264b4a171744d028";
+
+/// A note to be spent by an unsigned transaction. Byte strings are hex encoded.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UnsignedSpend {
+    pub diversifier: String,
+    pub value: u64,
+    /// The note's commitment randomness, in little-endian order.
+    pub rcm: String,
+    /// The note's incremental witness at the anchor height.
+    pub witness: String,
+    pub nullifier: String,
+}
+
+/// Everything the signing device needs to create a transaction, and the watch-only wallet
+/// needs to check it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UnsignedTransaction {
+    pub version: u32,
+    pub consensus_branch_id: u32,
+    pub account: u32,
+    /// The account's viewing key, which the spending key must match.
+    pub extfvk: String,
+    pub target_height: u32,
+    pub anchor_height: u32,
+    /// The root of the note commitment tree at the anchor height, hex encoded.
+    pub anchor: String,
+    pub fee: i64,
+    pub expiry_delta: u32,
+    pub spends: Vec<UnsignedSpend>,
+    pub to: String,
+    pub value: i64,
+    /// The memo, hex encoded, or `None` for no memo.
+    pub memo: Option<String>,
+}
+
+impl UnsignedTransaction {
+    pub fn to_json(&self) -> Result<String, Error> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let unsigned: UnsignedTransaction = serde_json::from_str(json)
+            .map_err(|e| format_err!("Invalid unsigned transaction: {}", e))?;
+        if unsigned.version != UNSIGNED_TRANSACTION_VERSION {
+            return Err(format_err!(
+                "Unsupported unsigned transaction version {}",
+                unsigned.version
+            ));
+        }
+        Ok(unsigned)
+    }
+
+    fn options(&self) -> Result<TransactionOptions, Error> {
+        TransactionOptions::new(Some(self.fee), Some(self.expiry_delta))
+    }
+
+    fn memo(&self) -> Result<Option<Memo>, Error> {
+        match &self.memo {
+            Some(memo) => Memo::from_bytes(&hex::decode(memo)?)
+                .map(Some)
+                .ok_or_else(|| format_err!("Memo is too long")),
+            None => Ok(None),
+        }
+    }
+}
+
+fn node_to_hex(node: Node) -> Result<String, Error> {
+    let mut bytes = vec![];
+    node.write(&mut bytes)?;
+    Ok(hex::encode(bytes))
+}
+
+/// Selects notes to pay `value` zatoshis to `to` from the account, and packages them for
+/// signing. Nothing is written to the data DB, so the notes stay spendable until the signed
+/// transaction is recorded.
+pub fn create_unsigned_transaction<P: AsRef<Path>>(
+    db_data: P,
+    consensus_branch_id: u32,
+    account: u32,
+    to: &str,
+    value: i64,
+    memo: &[u8],
+    options: TransactionOptions,
+) -> Result<UnsignedTransaction, Error> {
+    BranchId::try_from(consensus_branch_id)
+        .map_err(|e| format_err!("Invalid consensus branch id: {}", e))?;
+    parse_address(to)?;
+    parse_value(value)?;
+    let memo = Memo::from_bytes(memo);
+
+    let data = Connection::open(db_data)?;
+    let encoded_extfvk = tracked_extfvk(&data, account)?;
+    let extfvk = decode_extfvk(&encoded_extfvk)?;
+    let (target_height, anchor_height) = target_and_anchor_heights(&data)?;
+
+    let target_value = value + i64::from(options.fee);
+    let notes = select_notes(
+        &data,
+        &extfvk,
+        account,
+        anchor_height,
+        target_value,
+        options.strategy,
+    )?;
+    let anchor = match notes.first() {
+        Some(selected) => node_to_hex(selected.witness.root())?,
+        None => return Err(format_err!("No notes to spend")),
+    };
+
+    let mut spends = vec![];
+    for selected in &notes {
+        let mut rcm = vec![];
+        selected.note.r.into_repr().write_le(&mut rcm)?;
+        let mut witness = vec![];
+        selected.witness.write(&mut witness)?;
+        spends.push(UnsignedSpend {
+            diversifier: hex::encode(selected.diversifier.0),
+            value: selected.note.value,
+            rcm: hex::encode(rcm),
+            witness: hex::encode(witness),
+            nullifier: hex::encode(&selected.nf),
+        });
+    }
+
+    Ok(UnsignedTransaction {
+        version: UNSIGNED_TRANSACTION_VERSION,
+        consensus_branch_id,
+        account,
+        extfvk: encoded_extfvk,
+        target_height,
+        anchor_height,
+        anchor,
+        fee: i64::from(options.fee),
+        expiry_delta: options.expiry_delta,
+        spends,
+        to: to.to_string(),
+        value,
+        memo: memo.map(|memo| hex::encode(memo.as_bytes())),
+    })
+}
+
+/// Creates the transaction described by `unsigned` with the spending key, returning its raw
+/// bytes. This needs the Sapling parameters but no data DB.
+pub fn sign_transaction(
+    unsigned: &UnsignedTransaction,
+    extsk: &str,
+    spend_params: &Path,
+    output_params: &Path,
+) -> Result<Vec<u8>, Error> {
+    let extsk = decode_extsk(extsk)?;
+    let extfvk = ExtendedFullViewingKey::from(&extsk);
+    if encode_extended_full_viewing_key(HRP_SAPLING_EXTENDED_FULL_VIEWING_KEY, &extfvk)
+        != unsigned.extfvk
+    {
+        return Err(format_err!(
+            "Incorrect ExtendedSpendingKey for account {}",
+            unsigned.account
+        ));
+    }
+    let branch_id = BranchId::try_from(unsigned.consensus_branch_id)
+        .map_err(|e| format_err!("Invalid consensus branch id: {}", e))?;
+    let to = parse_address(&unsigned.to)?;
+    let value = parse_value(unsigned.value)?;
+    let options = unsigned.options()?;
+
+    let mut notes = vec![];
+    for spend in &unsigned.spends {
+        let (diversifier, note) = read_note(
+            &extfvk,
+            &hex::decode(&spend.diversifier)?,
+            spend.value,
+            &hex::decode(&spend.rcm)?,
+        )?;
+        let witness = IncrementalWitness::<Node>::read(&hex::decode(&spend.witness)?[..])?;
+        // Every spend must prove membership in the same tree.
+        if node_to_hex(witness.root())? != unsigned.anchor {
+            return Err(format_err!("Witness does not match the anchor"));
+        }
+        notes.push(SpendableNote {
+            diversifier,
+            note,
+            witness,
+            nf: hex::decode(&spend.nullifier)?,
+        });
+    }
+    check_balance(&notes, value, options)?;
+
+    let (tx, _) = build_transaction(
+        branch_id,
+        LocalTxProver::new(spend_params, output_params),
+        &extsk,
+        notes,
+        &to,
+        value,
+        unsigned.memo()?,
+        unsigned.target_height,
+        options,
+    )?;
+    let mut raw = vec![];
+    tx.write(&mut raw)?;
+    Ok(raw)
+}
+
+/// Checks the proofs and signatures of a transaction.
+pub(crate) trait TxVerifier {
+    fn verify(&self, tx: &Transaction, consensus_branch_id: BranchId) -> Result<(), Error>;
+}
+
+/// Verifies Sapling proofs with the verifying keys from the Sapling parameters.
+pub(crate) struct LocalTxVerifier {
+    spend_vk: PreparedVerifyingKey<Bls12>,
+    output_vk: PreparedVerifyingKey<Bls12>,
+}
+
+impl LocalTxVerifier {
+    /// Loads the verifying keys, panicking like `LocalTxProver::new` if the parameters are
+    /// missing or corrupt.
+    pub(crate) fn new(spend_params: &Path, output_params: &Path) -> Self {
+        let (_, spend_vk, _, output_vk, _) = load_parameters(
+            spend_params,
+            SAPLING_SPEND_HASH,
+            output_params,
+            SAPLING_OUTPUT_HASH,
+            None,
+            None,
+        );
+        LocalTxVerifier {
+            spend_vk,
+            output_vk,
+        }
+    }
+}
+
+impl TxVerifier for LocalTxVerifier {
+    fn verify(&self, tx: &Transaction, consensus_branch_id: BranchId) -> Result<(), Error> {
+        let mut sighash = [0u8; 32];
+        sighash.copy_from_slice(&signature_hash_data(
+            tx,
+            consensus_branch_id,
+            SIGHASH_ALL,
+            None,
+        ));
+
+        let mut ctx = SaplingVerificationContext::new();
+        for (i, spend) in tx.shielded_spends.iter().enumerate() {
+            let spend_auth_sig = spend
+                .spend_auth_sig
+                .ok_or_else(|| format_err!("Spend {} is not signed", i))?;
+            let zkproof = Proof::read(&spend.zkproof[..])
+                .map_err(|e| format_err!("Spend {} has an invalid proof: {}", i, e))?;
+            if !ctx.check_spend(
+                spend.cv.clone(),
+                spend.anchor,
+                &spend.nullifier,
+                PublicKey(spend.rk.0.clone()),
+                &sighash,
+                spend_auth_sig,
+                zkproof,
+                &self.spend_vk,
+                &JUBJUB,
+            ) {
+                return Err(format_err!("Spend {} failed verification", i));
+            }
+        }
+        for (i, output) in tx.shielded_outputs.iter().enumerate() {
+            let zkproof = Proof::read(&output.zkproof[..])
+                .map_err(|e| format_err!("Output {} has an invalid proof: {}", i, e))?;
+            if !ctx.check_output(
+                output.cv.clone(),
+                output.cmu,
+                output.ephemeral_key.clone(),
+                zkproof,
+                &self.output_vk,
+                &JUBJUB,
+            ) {
+                return Err(format_err!("Output {} failed verification", i));
+            }
+        }
+
+        match tx.binding_sig {
+            Some(binding_sig) => {
+                if !ctx.final_check(tx.value_balance, &sighash, binding_sig, &JUBJUB) {
+                    return Err(format_err!("Binding signature failed verification"));
+                }
+            }
+            None if tx.shielded_spends.is_empty() && tx.shielded_outputs.is_empty() => {}
+            None => return Err(format_err!("Transaction has no binding signature")),
+        }
+        Ok(())
+    }
+}
+
+/// Verifies the proofs and signatures of a signed transaction with the Sapling parameters,
+/// checks that it does what `unsigned` describes, paying the recipient and returning the
+/// change to the account, and stores it with the notes it spends marked as spent, returning
+/// its row ID.
+pub fn record_signed_transaction<P: AsRef<Path>>(
+    db_data: P,
+    unsigned: &UnsignedTransaction,
+    raw: &[u8],
+    spend_params: &Path,
+    output_params: &Path,
+) -> Result<i64, Error> {
+    record_verified_transaction(
+        db_data,
+        LocalTxVerifier::new(spend_params, output_params),
+        unsigned,
+        raw,
+    )
+}
+
+fn record_verified_transaction<P: AsRef<Path>>(
+    db_data: P,
+    verifier: impl TxVerifier,
+    unsigned: &UnsignedTransaction,
+    raw: &[u8],
+) -> Result<i64, Error> {
+    let tx = Transaction::read(raw).map_err(|e| format_err!("Invalid transaction: {}", e))?;
+    let branch_id = BranchId::try_from(unsigned.consensus_branch_id)
+        .map_err(|e| format_err!("Invalid consensus branch id: {}", e))?;
+    verifier.verify(&tx, branch_id)?;
+    let extfvk = decode_extfvk(&unsigned.extfvk)?;
+    let to = parse_address(&unsigned.to)?;
+    let value = parse_value(unsigned.value)?;
+    let memo = unsigned.memo()?;
+    let options = unsigned.options()?;
+
+    let mut data = Connection::open(db_data)?;
+    if tracked_extfvk(&data, unsigned.account)? != unsigned.extfvk {
+        return Err(format_err!(
+            "Viewing key does not match account {}",
+            unsigned.account
+        ));
+    }
+
+    if tx.expiry_height != unsigned.target_height + unsigned.expiry_delta {
+        return Err(format_err!("Transaction has the wrong expiry height"));
+    }
+
+    // It must spend exactly the packaged notes, at the packaged anchor.
+    let mut nullifiers: Vec<String> = tx
+        .shielded_spends
+        .iter()
+        .map(|spend| hex::encode(spend.nullifier))
+        .collect();
+    let mut expected: Vec<String> = unsigned
+        .spends
+        .iter()
+        .map(|spend| spend.nullifier.clone())
+        .collect();
+    nullifiers.sort();
+    expected.sort();
+    if nullifiers != expected {
+        return Err(format_err!("Transaction spends the wrong notes"));
+    }
+    for spend in &tx.shielded_spends {
+        let mut anchor = vec![];
+        spend.anchor.into_repr().write_le(&mut anchor)?;
+        if hex::encode(anchor) != unsigned.anchor {
+            return Err(format_err!("Transaction spends from the wrong anchor"));
+        }
+    }
+    for nullifier in &expected {
+        let unspent: i64 = data.query_row(
+            "SELECT COUNT(*) FROM received_notes WHERE nf = ? AND account = ? AND spent IS NULL",
+            &[&hex::decode(nullifier)? as &dyn ToSql, &unsigned.account],
+            |row| row.get(0),
+        )?;
+        if unspent == 0 {
+            return Err(format_err!("Note {} is no longer spendable", nullifier));
+        }
+    }
+
+    // Nothing may leave the wallet but the payment and the fee.
+    let transparent_value: i64 = tx.vout.iter().map(|output| i64::from(output.value)).sum();
+    let expected_transparent = match &to {
+        RecipientAddress::Shielded(_) => 0,
+        RecipientAddress::Transparent(_) => unsigned.value,
+    };
+    if transparent_value != expected_transparent {
+        return Err(format_err!("Transaction has the wrong transparent outputs"));
+    }
+    if i64::from(tx.value_balance) != unsigned.fee + transparent_value {
+        return Err(format_err!("Transaction pays the wrong fee"));
+    }
+
+    // Outputs to the account are decrypted with its incoming viewing key, and those to others
+    // are recovered with its outgoing one.
+    let mut outputs = decrypt_transaction(&tx, &[extfvk]);
+    let output_index = match &to {
+        RecipientAddress::Shielded(address) => {
+            let payment = outputs.iter().position(|output| {
+                output.to == *address
+                    && output.note.value == unsigned.value as u64
+                    && memo.as_ref().map_or(true, |memo| output.memo == *memo)
+            });
+            payment.map(|position| outputs.remove(position).index)
+        }
+        RecipientAddress::Transparent(address) => tx.vout.iter().position(|output| {
+            output.script_pubkey.0 == address.script().0 && output.value == value
+        }),
+    }
+    .ok_or_else(|| format_err!("Transaction does not pay the recipient"))?;
+
+    // The rest of the spent value must come back to the account as change. The builder pads
+    // the outputs with dummies no key can decrypt, which the balance checks above leave
+    // without value.
+    let spent: i64 = unsigned.spends.iter().map(|spend| spend.value as i64).sum();
+    let change = spent - unsigned.value - unsigned.fee;
+    match outputs.as_slice() {
+        [] if change == 0 => {}
+        [output] if !output.outgoing && output.note.value as i64 == change => {}
+        _ => {
+            return Err(format_err!(
+                "Transaction does not return the change of {} to the account",
+                change
+            ))
+        }
+    }
+
+    store_sent_transaction(
+        &mut data,
+        &tx,
+        unsigned.account,
+        &to,
+        value,
+        memo.as_ref(),
+        output_index as i64,
+        options.fee,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use failure::{format_err, Error};
+    use ff::{PrimeField, PrimeFieldRepr};
+    use pairing::bls12_381::{Fr, FrRepr};
+    use zcash_client_backend::keys::spending_key;
+    use zcash_client_sqlite::address::RecipientAddress;
+    use zcash_primitives::{
+        consensus::BranchId,
+        note_encryption::Memo,
+        redjubjub::{PublicKey, Signature},
+        transaction::{
+            components::{Amount, OutputDescription, SpendDescription, GROTH_PROOF_SIZE},
+            Transaction, TransactionData,
+        },
+        zip32::ExtendedFullViewingKey,
+    };
+
+    use super::{
+        create_unsigned_transaction, record_verified_transaction, TxVerifier, UnsignedTransaction,
+    };
+    use crate::fixtures::{shielded_output, wallet_at_checkpoint, write_cache_db, TestWallet};
+    use crate::transact::TransactionOptions;
+    use crate::wallet;
+
+    /// Stands in for the Sapling verifying keys, which the placeholder proofs and
+    /// signatures of these tests would never satisfy.
+    struct MockVerifier {
+        valid: bool,
+    }
+
+    impl TxVerifier for MockVerifier {
+        fn verify(&self, _: &Transaction, _: BranchId) -> Result<(), Error> {
+            if self.valid {
+                Ok(())
+            } else {
+                Err(format_err!("Invalid proof"))
+            }
+        }
+    }
+
+    const VALID: MockVerifier = MockVerifier { valid: true };
+
+    /// Returns a wallet whose account has scanned notes of 30,000 and 40,000 zatoshis, and
+    /// the consensus branch ID at its birthday.
+    fn setup() -> (TestWallet, u32) {
+        let mut wallet = wallet_at_checkpoint().unwrap();
+        wallet.chain.receive(&wallet.extfvk, 30_000).unwrap();
+        wallet.chain.receive(&wallet.extfvk, 40_000).unwrap();
+        wallet.chain.mine_blocks(11);
+        write_cache_db(&wallet.db_cache, wallet.chain.blocks()).unwrap();
+        wallet::scan_blocks(&wallet.db_cache, &wallet.db_data, None).unwrap();
+
+        let branch_id = wallet::branch_id_for_height(wallet.checkpoint.height as u32);
+        (wallet, branch_id)
+    }
+
+    /// Returns a transaction spending the packaged notes into `outputs`, with placeholder
+    /// proofs and signatures.
+    fn signed_transaction(
+        unsigned: &UnsignedTransaction,
+        outputs: Vec<OutputDescription>,
+    ) -> Vec<u8> {
+        let mut anchor = FrRepr::default();
+        anchor
+            .read_le(&hex::decode(&unsigned.anchor).unwrap()[..])
+            .unwrap();
+        let anchor = Fr::from_repr(anchor).unwrap();
+        let point = outputs[0].cv.clone();
+
+        let mut tx = TransactionData::new();
+        tx.expiry_height = unsigned.target_height + unsigned.expiry_delta;
+        tx.value_balance = Amount::from_i64(unsigned.fee).unwrap();
+        for spend in &unsigned.spends {
+            let mut nullifier = [0; 32];
+            nullifier.copy_from_slice(&hex::decode(&spend.nullifier).unwrap());
+            tx.shielded_spends.push(SpendDescription {
+                cv: point.clone(),
+                anchor,
+                nullifier,
+                rk: PublicKey(point.clone()),
+                zkproof: [0; GROTH_PROOF_SIZE],
+                spend_auth_sig: Some(Signature::read(&[0u8; 64][..]).unwrap()),
+            });
+        }
+        tx.shielded_outputs = outputs;
+        tx.binding_sig = Some(Signature::read(&[0u8; 64][..]).unwrap());
+
+        let mut raw = vec![];
+        tx.freeze().unwrap().write(&mut raw).unwrap();
+        raw
+    }
+
+    #[test]
+    fn unsigned_transactions_describe_the_spend() {
+        let (wallet, branch_id) = setup();
+        let db_data = &wallet.db_data;
+
+        let to = RecipientAddress::from(wallet.extfvk.default_address().unwrap().1).to_string();
+        let options = TransactionOptions::new(Some(5_000), Some(40)).unwrap();
+
+        assert!(
+            create_unsigned_transaction(db_data, branch_id, 0, &to, 70_000, b"", options).is_err()
+        );
+        let unsigned =
+            create_unsigned_transaction(db_data, branch_id, 0, &to, 50_000, b"hi", options)
+                .unwrap();
+        assert_eq!(unsigned.spends.len(), 2);
+        assert_eq!(unsigned.spends[0].value, 30_000);
+        assert_eq!(unsigned.fee, 5_000);
+        assert_eq!(unsigned.expiry_delta, 40);
+        assert_eq!(
+            unsigned.target_height as u64,
+            wallet.chain.tip_height().unwrap() + 1
+        );
+        assert!(unsigned.memo.as_ref().unwrap().starts_with("6869"));
+
+        let json = unsigned.to_json().unwrap();
+        assert_eq!(UnsignedTransaction::from_json(&json).unwrap(), unsigned);
+        assert!(
+            UnsignedTransaction::from_json(&json.replace("\"version\":1", "\"version\":2"))
+                .is_err()
+        );
+
+        // A transaction that does not spend the packaged notes is not recorded.
+        let mut tx = TransactionData::new();
+        tx.expiry_height = unsigned.target_height + unsigned.expiry_delta;
+        tx.value_balance = Amount::from_i64(unsigned.fee).unwrap();
+        let mut raw = vec![];
+        tx.freeze().unwrap().write(&mut raw).unwrap();
+        assert!(record_verified_transaction(db_data, VALID, &unsigned, &raw).is_err());
+        assert_eq!(wallet::get_balance(db_data, 0).unwrap(), 70_000);
+    }
+
+    #[test]
+    fn redirected_change_is_rejected() {
+        let (wallet, branch_id) = setup();
+        let (_, address) = wallet.extfvk.default_address().unwrap();
+        let to = RecipientAddress::from(address.clone()).to_string();
+        let options = TransactionOptions::new(Some(5_000), None).unwrap();
+        let unsigned =
+            create_unsigned_transaction(&wallet.db_data, branch_id, 0, &to, 50_000, b"hi", options)
+                .unwrap();
+        let ovk = wallet.extfvk.fvk.ovk;
+        let payment =
+            || shielded_output(ovk, &address, 50_000, Memo::from_bytes(b"hi").unwrap(), 1).unwrap();
+
+        // The 15,000 zatoshis of change go to another wallet.
+        let other = ExtendedFullViewingKey::from(&spending_key(&[1; 32], 1, 0));
+        let (_, other_address) = other.default_address().unwrap();
+        let redirected =
+            shielded_output(other.fvk.ovk, &other_address, 15_000, Memo::default(), 2).unwrap();
+        let raw = signed_transaction(&unsigned, vec![payment(), redirected]);
+        assert!(record_verified_transaction(&wallet.db_data, VALID, &unsigned, &raw).is_err());
+        assert_eq!(wallet::get_balance(&wallet.db_data, 0).unwrap(), 70_000);
+
+        let change = shielded_output(ovk, &address, 15_000, Memo::default(), 2).unwrap();
+        let raw = signed_transaction(&unsigned, vec![payment(), change]);
+        record_verified_transaction(&wallet.db_data, VALID, &unsigned, &raw).unwrap();
+        assert_eq!(wallet::get_balance(&wallet.db_data, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn unverified_transactions_are_rejected() {
+        let (wallet, branch_id) = setup();
+        let (_, address) = wallet.extfvk.default_address().unwrap();
+        let to = RecipientAddress::from(address.clone()).to_string();
+        let options = TransactionOptions::new(Some(5_000), None).unwrap();
+        let unsigned =
+            create_unsigned_transaction(&wallet.db_data, branch_id, 0, &to, 65_000, b"", options)
+                .unwrap();
+
+        // The transaction is otherwise exactly what was packaged.
+        let ovk = wallet.extfvk.fvk.ovk;
+        let payment = shielded_output(ovk, &address, 65_000, Memo::default(), 1).unwrap();
+        let raw = signed_transaction(&unsigned, vec![payment]);
+        let invalid = MockVerifier { valid: false };
+        assert!(record_verified_transaction(&wallet.db_data, invalid, &unsigned, &raw).is_err());
+        assert_eq!(wallet::get_balance(&wallet.db_data, 0).unwrap(), 70_000);
+
+        record_verified_transaction(&wallet.db_data, VALID, &unsigned, &raw).unwrap();
+        assert_eq!(wallet::get_balance(&wallet.db_data, 0).unwrap(), 0);
+    }
+}
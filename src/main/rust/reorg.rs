@@ -0,0 +1,579 @@
+//! Automatic recovery from chain reorganizations.
+//!
+//! Clients previously had to loop over `validateCombinedChain` and `rewindToHeight`
+//! themselves, guessing a rewind distance on each failure. [`handle_chain_reorg`] instead
+//! locates the fork point by comparing the block hashes stored in the data DB against the
+//! new chain, and rewinds both databases to it in one transaction.
+
+use failure::{format_err, Error};
+use protobuf::{parse_from_bytes, Message};
+use rusqlite::{types::ToSql, Connection, OptionalExtension, Statement, NO_PARAMS};
+use std::cmp;
+use std::path::Path;
+use zcash_client_backend::proto::compact_formats::CompactBlock;
+use zcash_client_sqlite::{chain::validate_combined_chain, error::ErrorKind};
+use zcash_primitives::consensus::{NetworkUpgrade, Parameters};
+
+use crate::Network;
+
+/// Returns the height of the highest block the wallet can never be rewound past.
+///
+/// This is the height of the earliest block in the data DB (the wallet birthday inserted by
+/// `initBlocksTable`), or the block before Sapling activation if no blocks exist yet.
+fn rewind_floor(data: &Connection) -> Result<i32, Error> {
+    let sapling_activation = Network::activation_height(NetworkUpgrade::Sapling)
+        .expect("Sapling activation height must be known") as i32;
+    let birthday: Option<i32> =
+        data.query_row("SELECT MIN(height) FROM blocks", NO_PARAMS, |row| {
+            row.get(0)
+        })?;
+    Ok(cmp::max(birthday.unwrap_or(0), sapling_activation - 1))
+}
+
+fn last_scanned_height(data: &Connection) -> Result<Option<i32>, Error> {
+    Ok(
+        data.query_row("SELECT MAX(height) FROM blocks", NO_PARAMS, |row| {
+            row.get(0)
+        })?,
+    )
+}
+
+/// Attaches the cache DB to `data` as `cache`, so that both can be changed in one
+/// transaction.
+fn attach_cache(data: &Connection, db_cache: &Path) -> Result<(), Error> {
+    let path = db_cache
+        .to_str()
+        .ok_or_else(|| format_err!("Cache DB path is not valid UTF-8"))?;
+    data.execute("ATTACH DATABASE ? AS cache", &[path])?;
+    Ok(())
+}
+
+/// Fetches and parses the cached block at `height`, if present.
+fn cached_block(stmt: &mut Statement<'_>, height: i32) -> Result<Option<CompactBlock>, Error> {
+    let data: Option<Vec<u8>> = stmt.query_row(&[height], |row| row.get(0)).optional()?;
+    match data {
+        Some(data) => Ok(Some(parse_from_bytes::<CompactBlock>(&data)?)),
+        None => Ok(None),
+    }
+}
+
+/// Fetches the blocks of the new chain from `height` down to `lower_bound`, where the block
+/// at `height` has hash `new_hash`, checking that each links to the one above it.
+///
+/// Returns them from the highest down, or `None` if `fetch_block` cannot supply one of them.
+fn fetch_new_blocks<F>(
+    mut height: i32,
+    mut new_hash: Vec<u8>,
+    lower_bound: i32,
+    mut fetch_block: F,
+) -> Result<Option<Vec<CompactBlock>>, Error>
+where
+    F: FnMut(u32) -> Result<Option<CompactBlock>, Error>,
+{
+    let mut blocks = vec![];
+    while height >= lower_bound {
+        match fetch_block(height as u32)? {
+            Some(block) if block.hash == new_hash => {
+                new_hash = block.prevHash.clone();
+                blocks.push(block);
+                height -= 1;
+            }
+            Some(_) => {
+                return Err(format_err!(
+                    "Block {} of the new chain does not link to the block above it",
+                    height
+                ))
+            }
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(blocks))
+}
+
+/// Walks down the new chain from `height`, where its block has hash `new_hash`, comparing
+/// each of its blocks against the scanned block at the same height.
+///
+/// The new chain's blocks are taken from the cache when they link up, and otherwise from
+/// `fetch_block`, since the cache may still hold the blocks of the old chain at heights that
+/// have been scanned. Returns the highest height at which both chains have the same block,
+/// or `None` if that is below `lower_bound` or the new chain cannot be followed that far.
+fn find_fork_point<F>(
+    data: &Connection,
+    mut height: i32,
+    mut new_hash: Vec<u8>,
+    lower_bound: i32,
+    mut fetch_block: F,
+) -> Result<Option<i32>, Error>
+where
+    F: FnMut(u32) -> Result<Option<CompactBlock>, Error>,
+{
+    let mut stmt_cached = data.prepare("SELECT data FROM cache.compactblocks WHERE height = ?")?;
+    let mut stmt_scanned = data.prepare("SELECT hash FROM main.blocks WHERE height = ?")?;
+
+    while height >= lower_bound {
+        let scanned_hash: Option<Vec<u8>> = stmt_scanned
+            .query_row(&[height], |row| row.get(0))
+            .optional()?;
+        if scanned_hash.as_ref() == Some(&new_hash) {
+            return Ok(Some(height));
+        }
+
+        let block = match cached_block(&mut stmt_cached, height)? {
+            Some(block) if block.hash == new_hash => Some(block),
+            _ => fetch_block(height as u32)?,
+        };
+        match block {
+            Some(block) if block.hash == new_hash => {
+                new_hash = block.prevHash;
+                height -= 1;
+            }
+            Some(_) => {
+                return Err(format_err!(
+                    "Block {} of the new chain does not link to the block above it",
+                    height
+                ))
+            }
+            None => return Ok(None),
+        }
+    }
+
+    Ok(None)
+}
+
+/// Replaces the cached blocks from `last_scanned + 1` up to the height of the highest of
+/// `blocks` with them, or drops every cached block above `last_scanned` if `blocks` is
+/// `None`, so that the cache never has a gap in it.
+fn replace_stale_blocks(
+    data: &mut Connection,
+    last_scanned: i32,
+    error_height: i32,
+    blocks: Option<Vec<CompactBlock>>,
+) -> Result<(), Error> {
+    let tx = data.transaction()?;
+    match blocks {
+        Some(blocks) => {
+            tx.execute(
+                "DELETE FROM cache.compactblocks WHERE height > ? AND height <= ?",
+                &[last_scanned, error_height],
+            )?;
+            let mut insert =
+                tx.prepare("INSERT INTO cache.compactblocks (height, data) VALUES (?, ?)")?;
+            for block in blocks {
+                let data = block.write_to_bytes()?;
+                insert.execute(&[&(block.height as i64) as &dyn ToSql, &data])?;
+            }
+        }
+        None => {
+            tx.execute(
+                "DELETE FROM cache.compactblocks WHERE height > ?",
+                &[last_scanned],
+            )?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// Rewinds the data DB to `height` and truncates the attached cache DB to it, in a single
+/// transaction so that a failure leaves both unchanged.
+///
+/// This does what `zcash_client_sqlite::chain::rewind_to_height` does, and also marks notes
+/// spent by transactions above `height` that the wallet did not create as unspent again;
+/// rescanning the new chain marks them spent if those transactions were mined in it.
+fn rewind(data: &mut Connection, height: i32) -> Result<(), Error> {
+    let tx = data.transaction()?;
+    tx.execute(
+        "UPDATE received_notes SET spent = NULL
+        WHERE spent IN (
+            SELECT id_tx FROM transactions WHERE block > ? AND created IS NULL
+        )",
+        &[height],
+    )?;
+    tx.execute("DELETE FROM sapling_witnesses WHERE block > ?", &[height])?;
+    tx.execute(
+        "UPDATE transactions SET block = NULL, tx_index = NULL WHERE block > ?",
+        &[height],
+    )?;
+    tx.execute("DELETE FROM blocks WHERE height > ?", &[height])?;
+    tx.execute(
+        "DELETE FROM cache.compactblocks WHERE height > ?",
+        &[height],
+    )?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// What [`recover_from_reorg`] found, and what it changed.
+#[derive(Debug, PartialEq)]
+pub(crate) enum Recovery {
+    /// The combined chain is valid, and nothing was changed.
+    Valid,
+    /// Only cached blocks above the last scanned block, at this height, were stale. They were
+    /// replaced, or dropped along with every cached block above the last scanned one.
+    CacheRepaired(i32),
+    /// Both databases were rewound to the fork point at this height.
+    Rewound(i32),
+    /// The fork point from the block at `invalid_height` is below `lower_bound`, or the new
+    /// chain could not be followed down to it. Nothing was changed.
+    ForkNotFound {
+        invalid_height: i32,
+        lower_bound: i32,
+    },
+}
+
+/// Validates the combined chain and, if a reorg is detected, rewinds the data DB and
+/// truncates the cache DB to the fork point.
+///
+/// `fetch_block` is called for blocks of the new chain that are not in the cache, and may
+/// return `None` if they cannot be downloaded. The fork point is only searched for down to
+/// `max_rewind` blocks below the invalid block, and never below the wallet birthday, so a
+/// misbehaving server cannot force a rescan of the entire wallet history.
+///
+/// If the break is above the last scanned block, only the cache is stale: the cached blocks
+/// from the last scanned block up to the break are replaced with those of the new chain, or
+/// if `fetch_block` cannot supply them, all blocks above the last scanned one are dropped.
+pub(crate) fn recover_from_reorg<P: AsRef<Path>, F>(
+    db_cache: P,
+    db_data: P,
+    max_rewind: u32,
+    mut fetch_block: F,
+) -> Result<Recovery, Error>
+where
+    F: FnMut(u32) -> Result<Option<CompactBlock>, Error>,
+{
+    if max_rewind == 0 {
+        return Err(format_err!("maxRewind must be at least 1"));
+    }
+
+    let (error_height, new_hash) = match validate_combined_chain(&db_cache, &db_data) {
+        Ok(()) => return Ok(Recovery::Valid),
+        Err(e) => match e.kind() {
+            ErrorKind::InvalidChain(height, hash) => (*height, hash.0.to_vec()),
+            _ => return Err(format_err!("Error while validating chain: {}", e)),
+        },
+    };
+
+    let mut data = Connection::open(&db_data)?;
+    attach_cache(&data, db_cache.as_ref())?;
+    let floor = rewind_floor(&data)?;
+    let last_scanned = last_scanned_height(&data)?.unwrap_or(floor);
+
+    if error_height > last_scanned {
+        // The cached blocks above the break are assumed to be from the new chain, and those
+        // from the last scanned block up to it from the old one.
+        let blocks = fetch_new_blocks(error_height, new_hash, last_scanned + 1, &mut fetch_block)?;
+        debug!(
+            "Cached chain invalid at height {}; replacing blocks above {}",
+            error_height, last_scanned
+        );
+        replace_stale_blocks(&mut data, last_scanned, error_height, blocks)?;
+        return Ok(Recovery::CacheRepaired(last_scanned));
+    }
+
+    if error_height <= floor {
+        return Err(format_err!(
+            "Chain is invalid at height {}, which is at or below the wallet birthday {}",
+            error_height,
+            floor
+        ));
+    }
+    let max_rewind = cmp::min(max_rewind, i32::max_value() as u32) as i32;
+    let lower_bound = cmp::max(error_height.saturating_sub(max_rewind), floor);
+
+    match find_fork_point(&data, error_height, new_hash, lower_bound, fetch_block)? {
+        Some(fork_height) => {
+            debug!(
+                "Chain invalid at height {}; rewinding to fork point {}",
+                error_height, fork_height
+            );
+            rewind(&mut data, fork_height)?;
+            Ok(Recovery::Rewound(fork_height))
+        }
+        None => Ok(Recovery::ForkNotFound {
+            invalid_height: error_height,
+            lower_bound,
+        }),
+    }
+}
+
+/// Recovers from a reorg as [`recover_from_reorg`] does, except that if the fork point cannot
+/// be located, both databases are rewound to the lowest permitted height and the caller
+/// should download blocks from there again.
+///
+/// Returns the height that the databases were rewound or repaired to, or -1 if the chain is
+/// valid and nothing was changed.
+pub(crate) fn handle_chain_reorg<P: AsRef<Path>, F>(
+    db_cache: P,
+    db_data: P,
+    max_rewind: u32,
+    fetch_block: F,
+) -> Result<i32, Error>
+where
+    F: FnMut(u32) -> Result<Option<CompactBlock>, Error>,
+{
+    match recover_from_reorg(&db_cache, &db_data, max_rewind, fetch_block)? {
+        Recovery::Valid => Ok(-1),
+        Recovery::CacheRepaired(height) | Recovery::Rewound(height) => Ok(height),
+        Recovery::ForkNotFound {
+            invalid_height,
+            lower_bound,
+        } => {
+            warn!(
+                "Unable to locate fork point for invalid block {} within {} blocks; rewinding to {}",
+                invalid_height, max_rewind, lower_bound
+            );
+            let mut data = Connection::open(&db_data)?;
+            attach_cache(&data, db_cache.as_ref())?;
+            rewind(&mut data, lower_bound)?;
+            Ok(lower_bound)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rusqlite::{Connection, NO_PARAMS};
+    use std::path::Path;
+    use zcash_client_backend::proto::compact_formats::CompactBlock;
+    use zcash_client_sqlite::{query::get_balance, scan::scan_cached_blocks};
+    use zcash_primitives::transaction::components::Amount;
+
+    use super::handle_chain_reorg;
+    use crate::fixtures::{wallet_at_checkpoint, write_cache_db, TestWallet};
+
+    struct Wallet {
+        wallet: TestWallet,
+        tip: u64,
+        /// The chain that replaces the last 5 scanned blocks with 8 new ones.
+        fork: Vec<CompactBlock>,
+    }
+
+    /// Scans a chain of 10 blocks with a note in the 6th, and builds a fork of it that
+    /// replaces the block with the note.
+    fn setup() -> Wallet {
+        let mut wallet = wallet_at_checkpoint().unwrap();
+        wallet.chain.mine_blocks(5);
+        let mut fork = wallet.chain.fork(1);
+        wallet.chain.receive(&wallet.extfvk, 10_000).unwrap();
+        wallet.chain.mine_blocks(5);
+        fork.mine_blocks(8);
+
+        write_cache_db(&wallet.db_cache, wallet.chain.blocks()).unwrap();
+        scan_cached_blocks(&wallet.db_cache, &wallet.db_data, None).unwrap();
+        assert_eq!(
+            get_balance(&wallet.db_data, 0).unwrap(),
+            Amount::from_u64(10_000).unwrap()
+        );
+
+        Wallet {
+            tip: wallet.chain.tip_height().unwrap(),
+            fork: fork.into_blocks(),
+            wallet,
+        }
+    }
+
+    fn max_height(db: &Path, table: &str) -> i64 {
+        Connection::open(db)
+            .unwrap()
+            .query_row(
+                &format!("SELECT MAX(height) FROM {}", table),
+                NO_PARAMS,
+                |row| row.get(0),
+            )
+            .unwrap()
+    }
+
+    fn find_block(blocks: &[CompactBlock], height: u32) -> Option<CompactBlock> {
+        blocks
+            .iter()
+            .find(|block| block.height == u64::from(height))
+            .cloned()
+    }
+
+    /// Checks that the wallet can scan the fork after the rewind.
+    fn scan_fork(wallet: &Wallet) {
+        let db_cache = &wallet.wallet.db_cache;
+        let db_data = &wallet.wallet.db_data;
+        write_cache_db(db_cache, &wallet.fork).unwrap();
+        assert_eq!(
+            handle_chain_reorg(db_cache, db_data, 100, |_| Ok(None)).unwrap(),
+            -1
+        );
+        scan_cached_blocks(db_cache, db_data, None).unwrap();
+        assert_eq!(max_height(db_data, "blocks") as u64, wallet.tip + 3);
+        assert_eq!(get_balance(db_data, 0).unwrap(), Amount::zero());
+    }
+
+    #[test]
+    fn finds_fork_through_cached_blocks() {
+        let wallet = setup();
+        let db_cache = &wallet.wallet.db_cache;
+        let db_data = &wallet.wallet.db_data;
+        // The client downloaded the whole fork, replacing the scanned blocks in the cache.
+        write_cache_db(db_cache, &wallet.fork).unwrap();
+
+        let fork_height = handle_chain_reorg(db_cache, db_data, 100, |height| {
+            panic!("block {} should be in the cache", height)
+        })
+        .unwrap();
+
+        assert_eq!(fork_height as u64, wallet.tip - 5);
+        assert_eq!(max_height(db_data, "blocks"), fork_height.into());
+        assert_eq!(max_height(db_cache, "compactblocks"), fork_height.into());
+        scan_fork(&wallet);
+    }
+
+    #[test]
+    fn finds_fork_through_fetched_blocks() {
+        let wallet = setup();
+        // Only the blocks above the scanned chain were downloaded, so the cache still holds
+        // the old chain below them.
+        let above_tip: Vec<_> = wallet
+            .fork
+            .iter()
+            .filter(|block| block.height > wallet.tip)
+            .cloned()
+            .collect();
+        write_cache_db(&wallet.wallet.db_cache, &above_tip).unwrap();
+
+        let mut fetched = vec![];
+        let fork_height = handle_chain_reorg(
+            &wallet.wallet.db_cache,
+            &wallet.wallet.db_data,
+            100,
+            |height| {
+                fetched.push(height);
+                Ok(find_block(&wallet.fork, height))
+            },
+        )
+        .unwrap();
+
+        assert_eq!(fork_height as u64, wallet.tip - 5);
+        let tip = wallet.tip as u32;
+        assert_eq!(fetched, vec![tip, tip - 1, tip - 2, tip - 3, tip - 4]);
+        scan_fork(&wallet);
+    }
+
+    #[test]
+    fn rewind_is_limited_without_the_new_chain() {
+        let wallet = setup();
+        let above_tip: Vec<_> = wallet
+            .fork
+            .iter()
+            .filter(|block| block.height > wallet.tip)
+            .cloned()
+            .collect();
+        write_cache_db(&wallet.wallet.db_cache, &above_tip).unwrap();
+
+        // The fork point cannot be found, so the rewind goes back max_rewind blocks.
+        let fork_height =
+            handle_chain_reorg(&wallet.wallet.db_cache, &wallet.wallet.db_data, 2, |_| {
+                Ok(None)
+            })
+            .unwrap();
+        assert_eq!(fork_height as u64, wallet.tip - 2);
+        assert_eq!(
+            max_height(&wallet.wallet.db_data, "blocks"),
+            fork_height.into()
+        );
+        assert_eq!(
+            max_height(&wallet.wallet.db_cache, "compactblocks"),
+            fork_height.into()
+        );
+    }
+
+    #[test]
+    fn max_rewind_must_be_positive() {
+        let wallet = setup();
+        let db_cache = &wallet.wallet.db_cache;
+        let db_data = &wallet.wallet.db_data;
+        write_cache_db(db_cache, &wallet.fork).unwrap();
+
+        assert!(handle_chain_reorg(db_cache, db_data, 0, |_| Ok(None)).is_err());
+        assert_eq!(max_height(db_data, "blocks") as u64, wallet.tip);
+    }
+
+    #[test]
+    fn spends_above_the_fork_point_are_undone() {
+        let mut wallet = wallet_at_checkpoint().unwrap();
+        let note = wallet.chain.receive(&wallet.extfvk, 10_000).unwrap();
+        wallet.chain.mine_blocks(3);
+        let mut fork = wallet.chain.fork(1);
+        wallet.chain.spend(&note.nf);
+        wallet.chain.mine_blocks(3);
+        write_cache_db(&wallet.db_cache, wallet.chain.blocks()).unwrap();
+        scan_cached_blocks(&wallet.db_cache, &wallet.db_data, None).unwrap();
+        assert_eq!(get_balance(&wallet.db_data, 0).unwrap(), Amount::zero());
+
+        // The spend is not in the new chain.
+        fork.mine_blocks(4);
+        write_cache_db(&wallet.db_cache, fork.blocks()).unwrap();
+        let fork_height =
+            handle_chain_reorg(&wallet.db_cache, &wallet.db_data, 100, |_| Ok(None)).unwrap();
+        assert_eq!(fork_height as u64, note.height + 2);
+
+        scan_cached_blocks(&wallet.db_cache, &wallet.db_data, None).unwrap();
+        assert_eq!(
+            get_balance(&wallet.db_data, 0).unwrap(),
+            Amount::from_u64(10_000).unwrap()
+        );
+    }
+
+    /// Builds a cache whose blocks above the scanned tip are 3 blocks of the old chain
+    /// followed by 3 blocks of a fork from the tip, so the break is above the scanned chain.
+    fn stale_cache() -> (TestWallet, u64, Vec<CompactBlock>) {
+        let mut wallet = wallet_at_checkpoint().unwrap();
+        wallet.chain.mine_blocks(5);
+        write_cache_db(&wallet.db_cache, wallet.chain.blocks()).unwrap();
+        scan_cached_blocks(&wallet.db_cache, &wallet.db_data, None).unwrap();
+        let tip = wallet.chain.tip_height().unwrap();
+
+        let mut fork = wallet.chain.fork(1);
+        wallet.chain.mine_blocks(3);
+        fork.mine_blocks(6);
+        let fork = fork.into_blocks();
+        write_cache_db(&wallet.db_cache, wallet.chain.blocks()).unwrap();
+        write_cache_db(&wallet.db_cache, &fork[fork.len() - 3..]).unwrap();
+        (wallet, tip, fork)
+    }
+
+    #[test]
+    fn stale_cached_blocks_are_replaced() {
+        let (wallet, tip, fork) = stale_cache();
+
+        let mut fetched = vec![];
+        let height = handle_chain_reorg(&wallet.db_cache, &wallet.db_data, 100, |height| {
+            fetched.push(height);
+            Ok(find_block(&fork, height))
+        })
+        .unwrap();
+
+        // Every stale block is replaced in one call, and nothing is rewound.
+        assert_eq!(height as u64, tip);
+        let tip = tip as u32;
+        assert_eq!(fetched, vec![tip + 3, tip + 2, tip + 1]);
+        assert_eq!(max_height(&wallet.db_data, "blocks") as u64, u64::from(tip));
+        assert_eq!(
+            handle_chain_reorg(&wallet.db_cache, &wallet.db_data, 100, |_| Ok(None)).unwrap(),
+            -1
+        );
+        scan_cached_blocks(&wallet.db_cache, &wallet.db_data, None).unwrap();
+        assert_eq!(
+            max_height(&wallet.db_data, "blocks") as u64,
+            u64::from(tip) + 6
+        );
+    }
+
+    #[test]
+    fn stale_cached_blocks_are_dropped_without_the_new_chain() {
+        let (wallet, tip, _) = stale_cache();
+
+        let height =
+            handle_chain_reorg(&wallet.db_cache, &wallet.db_data, 100, |_| Ok(None)).unwrap();
+
+        // The cache must not be left with a gap, so the valid blocks above the break go too.
+        assert_eq!(height as u64, tip);
+        assert_eq!(max_height(&wallet.db_cache, "compactblocks") as u64, tip);
+        assert_eq!(max_height(&wallet.db_data, "blocks") as u64, tip);
+    }
+}
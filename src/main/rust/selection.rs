@@ -0,0 +1,414 @@
+//! Strategies for choosing which notes a transaction spends.
+//!
+//! The choice is made on note values alone by [`NoteSelectionStrategy::select`], so that it
+//! can be tested without a wallet. [`propose_transfer`] applies it to the wallet's spendable
+//! notes and reports the result without creating anything.
+
+use failure::{format_err, Error};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::fmt;
+use std::path::Path;
+
+use crate::sweep::MAX_SWEEP_INPUTS;
+use crate::transact::{
+    spendable_notes, target_and_anchor_heights, tracked_extfvk, TransactionOptions,
+};
+use crate::wallet::{decode_extfvk, parse_value};
+
+/// The number of subsets [`NoteSelectionStrategy::MinimizeChange`] tries before settling for
+/// the best found so far.
+const MAX_CHANGE_SEARCH_TRIES: usize = 100_000;
+
+/// How to choose the notes that pay for a transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NoteSelectionStrategy {
+    /// Spend the oldest notes first. This is what `zcash_client_sqlite` does.
+    OldestFirst,
+    /// Spend the largest notes first, using as few inputs as possible.
+    LargestFirst,
+    /// Spend the notes that leave the least change, so that no new small note is created.
+    MinimizeChange,
+    /// Pay with as few notes as possible, then also spend the smallest remaining notes until
+    /// there are `max_inputs` inputs, merging them into the change.
+    ConsolidateDust { max_inputs: usize },
+}
+
+impl Default for NoteSelectionStrategy {
+    fn default() -> Self {
+        NoteSelectionStrategy::OldestFirst
+    }
+}
+
+impl fmt::Display for NoteSelectionStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NoteSelectionStrategy::OldestFirst => write!(f, "oldest-first"),
+            NoteSelectionStrategy::LargestFirst => write!(f, "largest-first"),
+            NoteSelectionStrategy::MinimizeChange => write!(f, "minimize-change"),
+            NoteSelectionStrategy::ConsolidateDust { max_inputs } => {
+                write!(f, "consolidate-dust (up to {} inputs)", max_inputs)
+            }
+        }
+    }
+}
+
+impl NoteSelectionStrategy {
+    /// Returns the strategy with the given code, as passed over JNI: 0 for oldest-first, 1 for
+    /// largest-first, 2 for minimize-change, and 3 for consolidate-dust, which also takes
+    /// `max_inputs`.
+    pub fn from_code(code: i32, max_inputs: i32) -> Result<Self, Error> {
+        match code {
+            0 => Ok(NoteSelectionStrategy::OldestFirst),
+            1 => Ok(NoteSelectionStrategy::LargestFirst),
+            2 => Ok(NoteSelectionStrategy::MinimizeChange),
+            3 if max_inputs > 0 => Ok(NoteSelectionStrategy::ConsolidateDust {
+                max_inputs: max_inputs as usize,
+            }),
+            3 => Err(format_err!("maxInputs argument must be positive")),
+            _ => Err(format_err!("Unknown note selection strategy {}", code)),
+        }
+    }
+
+    /// Chooses notes worth at least `target` from `values`, which are ordered oldest first,
+    /// returning their indices in that order.
+    pub fn select(&self, values: &[u64], target: u64) -> Result<Vec<usize>, Error> {
+        let total: u64 = values.iter().sum();
+        if total < target {
+            return Err(format_err!(
+                "Insufficient balance (have {}, need {} including fee)",
+                total,
+                target
+            ));
+        }
+
+        let mut selected = match self {
+            NoteSelectionStrategy::OldestFirst => accumulate(values, 0..values.len(), target),
+            NoteSelectionStrategy::LargestFirst => {
+                accumulate(values, by_value(values).into_iter().rev(), target)
+            }
+            NoteSelectionStrategy::MinimizeChange => minimize_change(values, target),
+            NoteSelectionStrategy::ConsolidateDust { max_inputs } => {
+                let mut selected = accumulate(values, by_value(values).into_iter().rev(), target);
+                if selected.len() > *max_inputs {
+                    return Err(format_err!(
+                        "Paying {} needs {} notes, more than {}",
+                        target,
+                        selected.len(),
+                        max_inputs
+                    ));
+                }
+                for index in by_value(values) {
+                    if selected.len() >= *max_inputs {
+                        break;
+                    }
+                    if !selected.contains(&index) {
+                        selected.push(index);
+                    }
+                }
+                selected
+            }
+        };
+        selected.sort();
+        Ok(selected)
+    }
+}
+
+/// Returns the indices of `values` from smallest to largest value, oldest first among equals.
+fn by_value(values: &[u64]) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..values.len()).collect();
+    indices.sort_by_key(|&index| values[index]);
+    indices
+}
+
+/// Takes notes in the given order until they are worth at least `target`.
+fn accumulate(values: &[u64], order: impl Iterator<Item = usize>, target: u64) -> Vec<usize> {
+    let mut selected = vec![];
+    let mut sum = 0;
+    for index in order {
+        if sum >= target {
+            break;
+        }
+        sum += values[index];
+        selected.push(index);
+    }
+    selected
+}
+
+/// Searches for the subset of notes that covers `target` with the least change, preferring
+/// fewer inputs, falling back to the largest notes if the search finds nothing.
+///
+/// Only the [`MAX_SWEEP_INPUTS`] largest notes are candidates, and the search is a
+/// depth-first one over an explicit stack, so that neither its depth nor its length grows
+/// with the number of notes in the wallet.
+fn minimize_change(values: &[u64], target: u64) -> Vec<usize> {
+    let order: Vec<usize> = by_value(values).into_iter().rev().collect();
+    let candidates = &order[..order.len().min(MAX_SWEEP_INPUTS)];
+    // remaining[i] is the value of the candidates from candidates[i] onwards.
+    let mut remaining = vec![0; candidates.len() + 1];
+    for i in (0..candidates.len()).rev() {
+        remaining[i] = remaining[i + 1] + values[candidates[i]];
+    }
+
+    // Each entry is the next candidate to decide on, and the value and number of the notes
+    // chosen so far, which are the first entries of `current`. Spending a candidate is tried
+    // before skipping it.
+    let mut stack = vec![(0, 0, 0)];
+    let mut current = vec![];
+    let mut best: Option<(u64, Vec<usize>)> = None;
+    let mut tries = 0;
+    while let Some((i, sum, len)) = stack.pop() {
+        if tries >= MAX_CHANGE_SEARCH_TRIES {
+            break;
+        }
+        tries += 1;
+        current.truncate(len);
+        if sum >= target {
+            let change = sum - target;
+            let better = match &best {
+                Some((best_change, best)) => {
+                    change < *best_change || (change == *best_change && len < best.len())
+                }
+                None => true,
+            };
+            if better {
+                best = Some((change, current.clone()));
+            }
+            continue;
+        }
+        if i == candidates.len() || sum + remaining[i] < target {
+            continue;
+        }
+        if let Some((0, _)) = best {
+            break;
+        }
+        let index = candidates[i];
+        stack.push((i + 1, sum, len));
+        current.push(index);
+        stack.push((i + 1, sum + values[index], len + 1));
+    }
+    match best {
+        Some((_, best)) => best,
+        None => accumulate(values, order.into_iter(), target),
+    }
+}
+
+/// A note chosen by [`propose_transfer`].
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ProposedNote {
+    pub id_note: i64,
+    pub value: u64,
+}
+
+/// The notes a transaction would spend, and what it would pay.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Proposal {
+    pub strategy: NoteSelectionStrategy,
+    pub account: u32,
+    pub value: i64,
+    pub fee: i64,
+    pub change: i64,
+    pub target_height: u32,
+    pub anchor_height: u32,
+    pub notes: Vec<ProposedNote>,
+}
+
+/// Chooses the notes that a transaction paying `value` zatoshis from the account would spend
+/// with the given options, without creating it.
+pub fn propose_transfer<P: AsRef<Path>>(
+    db_data: P,
+    account: u32,
+    value: i64,
+    options: TransactionOptions,
+) -> Result<Proposal, Error> {
+    parse_value(value)?;
+    let data = Connection::open(db_data)?;
+    let extfvk = decode_extfvk(&tracked_extfvk(&data, account)?)?;
+    let (target_height, anchor_height) = target_and_anchor_heights(&data)?;
+
+    let notes = spendable_notes(&data, &extfvk, account, anchor_height)?;
+    let values: Vec<u64> = notes.iter().map(|(_, note)| note.note.value).collect();
+    let target = value + i64::from(options.fee);
+    let selected = options.strategy.select(&values, target as u64)?;
+
+    let notes: Vec<ProposedNote> = selected
+        .into_iter()
+        .map(|index| ProposedNote {
+            id_note: notes[index].0,
+            value: values[index],
+        })
+        .collect();
+    let total: u64 = notes.iter().map(|note| note.value).sum();
+    Ok(Proposal {
+        strategy: options.strategy,
+        account,
+        value,
+        fee: i64::from(options.fee),
+        change: total as i64 - target,
+        target_height,
+        anchor_height,
+        notes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rusqlite::Connection;
+    use std::path::Path;
+
+    use super::NoteSelectionStrategy::{self, *};
+    use super::{propose_transfer, ProposedNote};
+    use crate::fixtures::{wallet_at_checkpoint, write_cache_db};
+    use crate::sweep::MAX_SWEEP_INPUTS;
+    use crate::transact::TransactionOptions;
+    use crate::wallet;
+
+    fn total(values: &[u64], selected: &[usize]) -> u64 {
+        selected.iter().map(|&index| values[index]).sum()
+    }
+
+    #[test]
+    fn codes_are_parsed() {
+        assert_eq!(
+            NoteSelectionStrategy::from_code(0, -1).unwrap(),
+            OldestFirst
+        );
+        assert_eq!(
+            NoteSelectionStrategy::from_code(2, -1).unwrap(),
+            MinimizeChange
+        );
+        assert_eq!(
+            NoteSelectionStrategy::from_code(3, 5).unwrap(),
+            ConsolidateDust { max_inputs: 5 }
+        );
+        assert!(NoteSelectionStrategy::from_code(3, 0).is_err());
+        assert!(NoteSelectionStrategy::from_code(4, 1).is_err());
+    }
+
+    #[test]
+    fn strategies_choose_different_notes() {
+        let values = [5_000, 1_000, 60_000, 2_000, 30_000, 25_000];
+
+        assert_eq!(OldestFirst.select(&values, 50_000).unwrap(), vec![0, 1, 2]);
+        assert_eq!(LargestFirst.select(&values, 50_000).unwrap(), vec![2]);
+        // 30_000 + 25_000 leaves less change than 60_000 alone.
+        let selected = MinimizeChange.select(&values, 50_000).unwrap();
+        assert_eq!(selected, vec![4, 5]);
+        assert_eq!(total(&values, &selected), 55_000);
+        let selected = MinimizeChange.select(&values, 56_000).unwrap();
+        assert_eq!(total(&values, &selected), 56_000);
+
+        assert_eq!(
+            ConsolidateDust { max_inputs: 3 }
+                .select(&values, 50_000)
+                .unwrap(),
+            vec![1, 2, 3]
+        );
+        assert!(ConsolidateDust { max_inputs: 1 }
+            .select(&values, 80_000)
+            .is_err());
+    }
+
+    #[test]
+    fn insufficient_balance_is_an_error() {
+        for strategy in &[OldestFirst, LargestFirst, MinimizeChange] {
+            assert!(strategy.select(&[1_000, 2_000], 3_001).is_err());
+            assert_eq!(strategy.select(&[1_000, 2_000], 3_000).unwrap(), vec![0, 1]);
+        }
+    }
+
+    #[test]
+    fn minimize_change_searches_the_largest_notes() {
+        // More notes than a transaction can spend, worth 1 to 300 zatoshis.
+        let values: Vec<u64> = (1..=300).collect();
+        assert!(values.len() > MAX_SWEEP_INPUTS);
+
+        // 300 + 299 + 298 cannot pay 1_000, but a fourth note makes it exactly.
+        let selected = MinimizeChange.select(&values, 1_000).unwrap();
+        assert_eq!(total(&values, &selected), 1_000);
+        assert_eq!(selected.len(), 4);
+
+        // The smallest notes are not candidates, even though one of them would leave no change.
+        let largest: u64 = values[values.len() - MAX_SWEEP_INPUTS..].iter().sum();
+        let selected = MinimizeChange.select(&values, largest - 1).unwrap();
+        assert_eq!(selected.len(), MAX_SWEEP_INPUTS);
+        assert_eq!(total(&values, &selected), largest);
+
+        // A payment the candidates cannot cover falls back to the largest notes first.
+        let selected = MinimizeChange.select(&values, largest + 1).unwrap();
+        assert_eq!(selected.len(), MAX_SWEEP_INPUTS + 1);
+        assert_eq!(total(&values, &selected), largest + 50);
+    }
+
+    fn id_note(db_data: &Path, value: i64) -> i64 {
+        Connection::open(db_data)
+            .unwrap()
+            .query_row(
+                "SELECT id_note FROM received_notes WHERE value = ?",
+                &[value],
+                |row| row.get(0),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn transfers_are_proposed_from_scanned_notes() {
+        let mut wallet = wallet_at_checkpoint().unwrap();
+        let db_data = &wallet.db_data;
+        let chain = &mut wallet.chain;
+        let extfvk = &wallet.extfvk;
+        chain.receive(extfvk, 5_000).unwrap();
+        chain.receive(extfvk, 60_000).unwrap();
+        chain.receive(extfvk, 30_000).unwrap();
+        chain.mine_blocks(11);
+        // Too recent to be spent at the anchor height.
+        chain.receive(extfvk, 100_000).unwrap();
+        chain.mine();
+        write_cache_db(&wallet.db_cache, chain.blocks()).unwrap();
+        wallet::scan_blocks(&wallet.db_cache, db_data, None).unwrap();
+        let tip = chain.tip_height().unwrap() as u32;
+
+        let options = TransactionOptions::new(Some(10_000), None).unwrap();
+        let proposal = propose_transfer(db_data, 0, 40_000, options).unwrap();
+        assert_eq!(proposal.strategy, OldestFirst);
+        assert_eq!(
+            proposal.notes,
+            vec![
+                ProposedNote {
+                    id_note: id_note(db_data, 5_000),
+                    value: 5_000,
+                },
+                ProposedNote {
+                    id_note: id_note(db_data, 60_000),
+                    value: 60_000,
+                },
+            ]
+        );
+        assert_eq!(proposal.value, 40_000);
+        assert_eq!(proposal.fee, 10_000);
+        assert_eq!(proposal.change, 15_000);
+        assert_eq!(proposal.target_height, tip + 1);
+        assert_eq!(proposal.anchor_height, tip + 1 - 10);
+
+        let options = options.with_strategy(MinimizeChange);
+        let proposal = propose_transfer(db_data, 0, 25_000, options).unwrap();
+        assert_eq!(
+            proposal.notes,
+            vec![
+                ProposedNote {
+                    id_note: id_note(db_data, 5_000),
+                    value: 5_000,
+                },
+                ProposedNote {
+                    id_note: id_note(db_data, 30_000),
+                    value: 30_000,
+                },
+            ]
+        );
+        assert_eq!(proposal.change, 0);
+
+        // The unverified note cannot be proposed.
+        assert!(propose_transfer(db_data, 0, 90_000, options).is_err());
+    }
+}
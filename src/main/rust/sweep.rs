@@ -0,0 +1,281 @@
+//! Sending an account's whole verified balance.
+//!
+//! [`max_sendable`] works out how much a transaction spending the account's verified notes
+//! can pay once the fee is taken out, and [`sweep_to_address`] creates that transaction.
+//! Because the notes are worth exactly the payment plus the fee, it has no change output.
+
+use failure::{format_err, Error};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::path::Path;
+use zcash_client_sqlite::address::RecipientAddress;
+use zcash_primitives::{
+    consensus::BranchId,
+    note_encryption::Memo,
+    prover::TxProver,
+    transaction::components::Amount,
+    zip32::{ExtendedFullViewingKey, ExtendedSpendingKey},
+};
+
+use crate::transact::{
+    account_extfvk, build_transaction, spendable_notes, store_sent_transaction,
+    target_and_anchor_heights, tracked_extfvk, SpendableNote, TransactionOptions,
+};
+use crate::wallet::decode_extfvk;
+
+/// The most notes a sweep spends. A v4 transaction may be at most 100,000 bytes and each
+/// Sapling spend takes 384 of them, which leaves room for the outputs.
+pub const MAX_SWEEP_INPUTS: usize = 250;
+
+/// How much a sweep of the account to a recipient would pay.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MaxSendable {
+    pub account: u32,
+    /// Either `"shielded"` or `"transparent"`.
+    pub recipient: &'static str,
+    /// The amount sent, or zero if the notes are not worth more than the fee.
+    pub value: i64,
+    pub fee: i64,
+    /// The number of notes spent.
+    pub notes: usize,
+    /// The number of verified notes left out because of the input limit.
+    pub excluded_notes: usize,
+    pub target_height: u32,
+    pub anchor_height: u32,
+}
+
+/// Returns the number of notes a sweep asked to spend at most `max_inputs` spends.
+pub(crate) fn input_limit(max_inputs: Option<usize>) -> usize {
+    max_inputs.map_or(MAX_SWEEP_INPUTS, |max| max.min(MAX_SWEEP_INPUTS))
+}
+
+/// Returns the indices of the `max_inputs` largest of `values`, in their original order.
+fn select_largest(values: &[u64], max_inputs: usize) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..values.len()).collect();
+    indices.sort_by(|&a, &b| values[b].cmp(&values[a]));
+    indices.truncate(max_inputs);
+    indices.sort();
+    indices
+}
+
+/// Returns the verified notes a sweep of the account spends, with the number left out.
+fn sweep_notes(
+    data: &Connection,
+    extfvk: &ExtendedFullViewingKey,
+    account: u32,
+    anchor_height: u32,
+    max_inputs: Option<usize>,
+) -> Result<(Vec<SpendableNote>, usize), Error> {
+    let notes = spendable_notes(data, extfvk, account, anchor_height)?;
+    let values: Vec<u64> = notes.iter().map(|(_, note)| note.note.value).collect();
+    let selected = select_largest(&values, input_limit(max_inputs));
+    let excluded = notes.len() - selected.len();
+
+    let mut notes: Vec<Option<SpendableNote>> =
+        notes.into_iter().map(|(_, note)| Some(note)).collect();
+    let notes = selected
+        .into_iter()
+        .filter_map(|index| notes[index].take())
+        .collect();
+    Ok((notes, excluded))
+}
+
+fn sweep_value(notes: &[SpendableNote], options: TransactionOptions) -> i64 {
+    let total: u64 = notes.iter().map(|selected| selected.note.value).sum();
+    total as i64 - i64::from(options.fee)
+}
+
+/// Returns how much a transaction sending the account's verified notes to `to` would pay,
+/// spending at most `max_inputs` of them, largest first.
+pub fn max_sendable<P: AsRef<Path>>(
+    db_data: P,
+    account: u32,
+    to: &RecipientAddress,
+    max_inputs: Option<usize>,
+    options: TransactionOptions,
+) -> Result<MaxSendable, Error> {
+    let data = Connection::open(db_data)?;
+    let extfvk = decode_extfvk(&tracked_extfvk(&data, account)?)?;
+    let (target_height, anchor_height) = target_and_anchor_heights(&data)?;
+
+    let (notes, excluded_notes) = sweep_notes(&data, &extfvk, account, anchor_height, max_inputs)?;
+    Ok(MaxSendable {
+        account,
+        recipient: match to {
+            RecipientAddress::Shielded(_) => "shielded",
+            RecipientAddress::Transparent(_) => "transparent",
+        },
+        value: sweep_value(&notes, options).max(0),
+        fee: i64::from(options.fee),
+        notes: notes.len(),
+        excluded_notes,
+        target_height,
+        anchor_height,
+    })
+}
+
+/// Creates a transaction sending the amount reported by [`max_sendable`] to `to`, with no
+/// change output, stores it and returns its row ID.
+#[allow(clippy::too_many_arguments)]
+pub fn sweep_to_address<P: AsRef<Path>>(
+    db_data: P,
+    consensus_branch_id: BranchId,
+    prover: impl TxProver,
+    account: u32,
+    extsk: &ExtendedSpendingKey,
+    to: &RecipientAddress,
+    memo: Option<Memo>,
+    max_inputs: Option<usize>,
+    options: TransactionOptions,
+) -> Result<i64, Error> {
+    let mut data = Connection::open(db_data)?;
+    let extfvk = account_extfvk(&data, account, extsk)?;
+    let (target_height, anchor_height) = target_and_anchor_heights(&data)?;
+
+    let (notes, _) = sweep_notes(&data, &extfvk, account, anchor_height, max_inputs)?;
+    let value = sweep_value(&notes, options);
+    if value <= 0 {
+        return Err(format_err!(
+            "Verified notes are worth {}, not more than the fee",
+            value + i64::from(options.fee)
+        ));
+    }
+    let value = Amount::from_i64(value).unwrap();
+
+    let (tx, output_index) = build_transaction(
+        consensus_branch_id,
+        prover,
+        extsk,
+        notes,
+        to,
+        value,
+        memo.clone(),
+        target_height,
+        options,
+    )?;
+    store_sent_transaction(
+        &mut data,
+        &tx,
+        account,
+        to,
+        value,
+        memo.as_ref(),
+        output_index,
+        options.fee,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use rusqlite::Connection;
+    use zcash_client_backend::keys::spending_key;
+    use zcash_client_sqlite::address::RecipientAddress;
+    use zcash_primitives::{consensus::BranchId, zip32::ExtendedFullViewingKey};
+
+    use super::{input_limit, max_sendable, select_largest, sweep_to_address, MAX_SWEEP_INPUTS};
+    use crate::fixtures::{wallet_at_checkpoint, write_cache_db, MockProver, TestWallet};
+    use crate::transact::TransactionOptions;
+    use crate::wallet;
+
+    /// Returns a wallet whose account has scanned notes of 5,000, 60,000 and 30,000 zatoshis,
+    /// and an address of another wallet to sweep them to.
+    fn setup() -> (TestWallet, RecipientAddress) {
+        let mut wallet = wallet_at_checkpoint().unwrap();
+        for &value in &[5_000, 60_000, 30_000] {
+            wallet.chain.receive(&wallet.extfvk, value).unwrap();
+        }
+        wallet.chain.mine_blocks(11);
+        write_cache_db(&wallet.db_cache, wallet.chain.blocks()).unwrap();
+        wallet::scan_blocks(&wallet.db_cache, &wallet.db_data, None).unwrap();
+
+        let other = ExtendedFullViewingKey::from(&spending_key(&[1; 32], 1, 0));
+        let (_, address) = other.default_address().unwrap();
+        (wallet, RecipientAddress::Shielded(address))
+    }
+
+    #[test]
+    fn largest_notes_are_swept_first() {
+        let values = [5_000, 1_000, 60_000, 2_000, 30_000];
+        assert_eq!(select_largest(&values, 10), vec![0, 1, 2, 3, 4]);
+        assert_eq!(select_largest(&values, 3), vec![0, 2, 4]);
+        assert!(select_largest(&values, 0).is_empty());
+    }
+
+    #[test]
+    fn inputs_are_capped() {
+        assert_eq!(input_limit(None), MAX_SWEEP_INPUTS);
+        assert_eq!(input_limit(Some(MAX_SWEEP_INPUTS + 1)), MAX_SWEEP_INPUTS);
+        assert_eq!(input_limit(Some(usize::max_value())), MAX_SWEEP_INPUTS);
+        assert_eq!(input_limit(Some(2)), 2);
+    }
+
+    #[test]
+    fn sweeps_pay_the_largest_notes_less_the_fee() {
+        let (wallet, to) = setup();
+        let options = TransactionOptions::new(Some(10_000), None).unwrap();
+
+        let sendable = max_sendable(&wallet.db_data, 0, &to, None, options).unwrap();
+        assert_eq!(sendable.value, 85_000);
+        assert_eq!(sendable.notes, 3);
+        assert_eq!(sendable.excluded_notes, 0);
+
+        let sendable = max_sendable(&wallet.db_data, 0, &to, Some(2), options).unwrap();
+        assert_eq!(sendable.recipient, "shielded");
+        assert_eq!(sendable.value, 80_000);
+        assert_eq!(sendable.fee, 10_000);
+        assert_eq!(sendable.notes, 2);
+        assert_eq!(sendable.excluded_notes, 1);
+
+        let id_tx = sweep_to_address(
+            &wallet.db_data,
+            BranchId::Sapling,
+            MockProver,
+            0,
+            &wallet.extsk,
+            &to,
+            None,
+            Some(2),
+            options,
+        )
+        .unwrap();
+        let sent: i64 = Connection::open(&wallet.db_data)
+            .unwrap()
+            .query_row(
+                "SELECT SUM(value) FROM sent_notes WHERE tx = ?",
+                &[id_tx],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(sent, 80_000);
+        // Only the smallest note is left.
+        assert_eq!(wallet::get_balance(&wallet.db_data, 0).unwrap(), 5_000);
+    }
+
+    #[test]
+    fn sweeps_must_pay_more_than_the_fee() {
+        let (wallet, to) = setup();
+        let options = TransactionOptions::new(Some(95_000), None).unwrap();
+
+        let sendable = max_sendable(&wallet.db_data, 0, &to, None, options).unwrap();
+        assert_eq!(sendable.value, 0);
+        assert_eq!(sendable.notes, 3);
+
+        let err = sweep_to_address(
+            &wallet.db_data,
+            BranchId::Sapling,
+            MockProver,
+            0,
+            &wallet.extsk,
+            &to,
+            None,
+            None,
+            options,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Verified notes are worth 95000, not more than the fee"
+        );
+        assert_eq!(wallet::get_balance(&wallet.db_data, 0).unwrap(), 95_000);
+    }
+}
@@ -0,0 +1,513 @@
+//! A headless sync engine for the cache and data DBs.
+//!
+//! [`Synchronizer`] runs the same loop as the Kotlin `CompactBlockProcessor`: download new
+//! compact blocks into the cache DB, validate them against the data DB (rewinding both if the
+//! chain has reorganized), scan them, and then fetch the full transactions that the scan
+//! found so that their memos and outputs can be stored. It talks to lightwalletd through the
+//! [`BlockchainClient`] trait, so it can be run against a real server, a mock, or a fake.
+//!
+//! On Android it is driven through `RustBackend.syncWallet`, with a [`BlockchainClient`] that
+//! calls back into the SDK's Kotlin gRPC client, since the `lightwalletd` feature's own gRPC
+//! and TLS stack is not built into the Android library. Elsewhere, [`LightwalletdClient`] can
+//! be used directly.
+
+use failure::{format_err, Error};
+use protobuf::Message;
+use rusqlite::{Connection, NO_PARAMS};
+use serde::Serialize;
+use std::fmt;
+use std::path::PathBuf;
+use zcash_client_backend::proto::compact_formats::CompactBlock;
+use zcash_client_sqlite::scan::scan_cached_blocks;
+
+use crate::enhance::enhance_transactions;
+use crate::expiry::expire_transactions;
+#[cfg(feature = "lightwalletd")]
+use crate::lightwalletd::LightwalletdClient;
+use crate::reorg::{recover_from_reorg, Recovery};
+
+/// Number of blocks to download from lightwalletd at a time.
+pub const DOWNLOAD_BATCH_SIZE: u32 = 100;
+
+/// Maximum number of blocks to rewind when the chain has reorganized.
+pub const MAX_REORG_SIZE: u32 = 100;
+
+/// Number of consecutive reorgs after which syncing gives up, rather than rewinding forever.
+const MAX_CONSECUTIVE_REORGS: u32 = 10;
+
+/// The lightwalletd calls that the [`Synchronizer`] needs.
+pub trait BlockchainClient {
+    /// Returns the height of the tip of the server's best chain.
+    fn latest_height(&self) -> Result<u32, Error>;
+
+    /// Returns the compact blocks in the inclusive range `start..=end`.
+    fn block_range(&self, start: u32, end: u32) -> Result<Vec<CompactBlock>, Error>;
+
+    /// Returns the raw bytes of a mined transaction, given its txid in internal byte order.
+    fn transaction(&self, txid: &[u8]) -> Result<Vec<u8>, Error>;
+}
+
+#[cfg(feature = "lightwalletd")]
+impl BlockchainClient for LightwalletdClient {
+    fn latest_height(&self) -> Result<u32, Error> {
+        let block = self.latest_block().map_err(|e| format_err!("{}", e))?;
+        Ok(block.get_height() as u32)
+    }
+
+    fn block_range(&self, start: u32, end: u32) -> Result<Vec<CompactBlock>, Error> {
+        LightwalletdClient::block_range(self, u64::from(start), u64::from(end))
+            .map_err(|e| format_err!("{}", e))
+    }
+
+    fn transaction(&self, txid: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut tx =
+            LightwalletdClient::transaction(self, txid).map_err(|e| format_err!("{}", e))?;
+        Ok(tx.take_data())
+    }
+}
+
+/// Reasons that [`Synchronizer::sync`] gives up, other than errors from the client or the
+/// databases.
+#[derive(Debug, PartialEq)]
+pub enum SyncError {
+    /// The chain is invalid at `height`, and its fork point from the scanned chain is more
+    /// than `max_rewind` blocks below that, or below the wallet birthday. Nothing is rewound,
+    /// since a reorg that deep is more likely to come from a misbehaving server.
+    ReorgTooDeep { height: u32, max_rewind: u32 },
+    /// The chain was still invalid after rewinding this many times in a row.
+    TooManyReorgs(u32),
+}
+
+impl fmt::Display for SyncError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SyncError::ReorgTooDeep { height, max_rewind } => write!(
+                f,
+                "Chain is invalid at height {}, and the fork point is more than {} blocks below it",
+                height, max_rewind
+            ),
+            SyncError::TooManyReorgs(count) => {
+                write!(f, "Chain is still invalid after rewinding {} times", count)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SyncError {}
+
+/// Progress reported by [`Synchronizer::sync`].
+///
+/// It is passed to Kotlin as JSON, such as `{"status":"rewound","detail":1000}`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "status", content = "detail", rename_all = "kebab-case")]
+pub enum SyncStatus {
+    /// Blocks `start..=end` are being downloaded into the cache DB.
+    Downloading { start: u32, end: u32 },
+    /// The downloaded blocks are being checked against the scanned chain.
+    Validating,
+    /// The chain reorganized, and the DBs were rewound to the given height.
+    Rewound(u32),
+    /// The downloaded blocks are being scanned for the wallet's transactions.
+    Scanning,
+    /// The full data for the given number of newly found transactions is being fetched.
+    Enhancing(usize),
+    /// The wallet is up to date with the chain at the given height.
+    Synced(u32),
+}
+
+/// Keeps a wallet's cache and data DBs in sync with the chain.
+pub struct Synchronizer<C> {
+    client: C,
+    db_cache: PathBuf,
+    db_data: PathBuf,
+}
+
+impl<C: BlockchainClient> Synchronizer<C> {
+    /// Creates a synchronizer for an initialized wallet. The data DB must already have its
+    /// blocks table initialized, and the cache DB must have a `compactblocks` table.
+    pub fn new<P: Into<PathBuf>>(client: C, db_cache: P, db_data: P) -> Self {
+        Synchronizer {
+            client,
+            db_cache: db_cache.into(),
+            db_data: db_data.into(),
+        }
+    }
+
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+
+    /// Syncs the wallet up to the current chain tip, reporting progress to `on_status`.
+    ///
+    /// Returns the height the wallet was synced to.
+    pub fn sync<F: FnMut(SyncStatus)>(&self, mut on_status: F) -> Result<u32, Error> {
+        let mut reorgs = 0;
+        loop {
+            let latest_height = self.client.latest_height()?;
+            let last_scanned = self.last_scanned_height()?;
+            let last_downloaded = match self.last_downloaded_height()? {
+                Some(height) if height > last_scanned => height,
+                _ => last_scanned,
+            };
+
+            let mut start = last_downloaded + 1;
+            while start <= latest_height {
+                let end = latest_height.min(start + DOWNLOAD_BATCH_SIZE - 1);
+                on_status(SyncStatus::Downloading { start, end });
+                self.download(start, end)?;
+                start = end + 1;
+            }
+
+            on_status(SyncStatus::Validating);
+            let recovery =
+                recover_from_reorg(&self.db_cache, &self.db_data, MAX_REORG_SIZE, |height| {
+                    Ok(self.client.block_range(height, height)?.pop())
+                })?;
+            let rewound_to = match recovery {
+                Recovery::Valid => None,
+                Recovery::CacheRepaired(height) | Recovery::Rewound(height) => Some(height),
+                Recovery::ForkNotFound { invalid_height, .. } => {
+                    return Err(SyncError::ReorgTooDeep {
+                        height: invalid_height as u32,
+                        max_rewind: MAX_REORG_SIZE,
+                    }
+                    .into())
+                }
+            };
+            if let Some(height) = rewound_to {
+                reorgs += 1;
+                if reorgs > MAX_CONSECUTIVE_REORGS {
+                    return Err(SyncError::TooManyReorgs(MAX_CONSECUTIVE_REORGS).into());
+                }
+                on_status(SyncStatus::Rewound(height as u32));
+                continue;
+            }
+
+            on_status(SyncStatus::Scanning);
+            scan_cached_blocks(&self.db_cache, &self.db_data, None)
+                .map_err(|e| format_err!("Error while scanning blocks: {}", e))?;
+            let scanned_height = self.last_scanned_height()?;
+
+            self.enhance(last_scanned + 1, scanned_height, &mut on_status)?;
+            for id_tx in expire_transactions(&self.db_data, latest_height)? {
+                debug!("Transaction {} expired without being mined", id_tx);
+            }
+
+            on_status(SyncStatus::Synced(scanned_height));
+            return Ok(scanned_height);
+        }
+    }
+
+    /// Returns the height of the last block in the data DB.
+    fn last_scanned_height(&self) -> Result<u32, Error> {
+        let data = Connection::open(&self.db_data)?;
+        let height: Option<u32> =
+            data.query_row("SELECT MAX(height) FROM blocks", NO_PARAMS, |row| {
+                row.get(0)
+            })?;
+        height.ok_or_else(|| format_err!("Blocks table must be initialized before syncing"))
+    }
+
+    /// Returns the height of the last block in the cache DB, if any.
+    fn last_downloaded_height(&self) -> Result<Option<u32>, Error> {
+        let cache = Connection::open(&self.db_cache)?;
+        Ok(
+            cache.query_row("SELECT MAX(height) FROM compactblocks", NO_PARAMS, |row| {
+                row.get(0)
+            })?,
+        )
+    }
+
+    /// Fetches blocks `start..=end` and stores them in the cache DB.
+    fn download(&self, start: u32, end: u32) -> Result<(), Error> {
+        let blocks = self.client.block_range(start, end)?;
+        let mut cache = Connection::open(&self.db_cache)?;
+        let tx = cache.transaction()?;
+        {
+            let mut stmt =
+                tx.prepare("INSERT OR REPLACE INTO compactblocks (height, data) VALUES (?, ?)")?;
+            for block in blocks {
+                stmt.execute(&[
+                    &(block.height as i64) as &dyn rusqlite::ToSql,
+                    &block.write_to_bytes()?,
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Fetches and stores the full transactions found while scanning blocks `start..=end`.
+    ///
+    /// As in the Kotlin SDK, a transaction that cannot be fetched or decrypted is logged and
+    /// skipped; it only means that its memo is missing.
+    fn enhance<F: FnMut(SyncStatus)>(
+        &self,
+        start: u32,
+        end: u32,
+        on_status: &mut F,
+    ) -> Result<(), Error> {
+        let txids = {
+            let data = Connection::open(&self.db_data)?;
+            let mut stmt = data.prepare(
+                "SELECT txid, block FROM transactions
+                WHERE block >= ? AND block <= ? AND raw IS NULL",
+            )?;
+            let rows = stmt.query_map(&[start, end], |row| {
+                Ok((row.get::<_, Vec<u8>>(0)?, row.get::<_, u32>(1)?))
+            })?;
+            rows.collect::<Result<Vec<_>, _>>()?
+        };
+        if txids.is_empty() {
+            return Ok(());
+        }
+
+        on_status(SyncStatus::Enhancing(txids.len()));
+        let mut txs = vec![];
+        for (txid, height) in txids {
+            match self.client.transaction(&txid) {
+                Ok(raw) => txs.push((raw, height)),
+                Err(e) => error!("Failed to fetch transaction {}: {}", hex::encode(&txid), e),
+            }
+        }
+        for failure in enhance_transactions(&self.db_data, &txs)? {
+            error!(
+                "Failed to enhance transaction {}: {}",
+                failure.txid.map(hex::encode).unwrap_or_default(),
+                failure.error
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use failure::{format_err, Error};
+    use rusqlite::{Connection, NO_PARAMS};
+    use std::cell::RefCell;
+    use zcash_client_backend::proto::compact_formats::CompactBlock;
+    use zcash_client_sqlite::query::{get_balance, get_verified_balance};
+    use zcash_primitives::transaction::components::Amount;
+
+    use super::{BlockchainClient, SyncError, SyncStatus, Synchronizer, MAX_REORG_SIZE};
+    use crate::fixtures::{wallet_at_checkpoint, TestWallet};
+
+    /// A client serving a chain of empty blocks from memory.
+    struct FakeClient {
+        chain: RefCell<Vec<CompactBlock>>,
+    }
+
+    impl BlockchainClient for FakeClient {
+        fn latest_height(&self) -> Result<u32, Error> {
+            let chain = self.chain.borrow();
+            Ok(chain.last().unwrap().height as u32)
+        }
+
+        fn block_range(&self, start: u32, end: u32) -> Result<Vec<CompactBlock>, Error> {
+            let chain = self.chain.borrow();
+            Ok(chain
+                .iter()
+                .filter(|b| b.height >= u64::from(start) && b.height <= u64::from(end))
+                .cloned()
+                .collect())
+        }
+
+        fn transaction(&self, _: &[u8]) -> Result<Vec<u8>, Error> {
+            Err(format_err!("no transactions"))
+        }
+    }
+
+    fn hash(height: u64, fork: u8) -> Vec<u8> {
+        let mut hash = vec![fork; 32];
+        hash[..8].copy_from_slice(&height.to_le_bytes());
+        hash
+    }
+
+    /// Extends `chain` by `count` blocks from the given fork.
+    fn extend(chain: &mut Vec<CompactBlock>, count: u64, fork: u8) {
+        for _ in 0..count {
+            let prev = chain.last().unwrap();
+            let mut block = CompactBlock::new();
+            block.set_height(prev.height + 1);
+            block.set_prevHash(prev.hash.clone());
+            block.set_hash(hash(prev.height + 1, fork));
+            block.set_time(prev.time + 150);
+            chain.push(block);
+        }
+    }
+
+    fn setup() -> (TestWallet, Synchronizer<FakeClient>) {
+        let wallet = wallet_at_checkpoint().unwrap();
+
+        // The fake chain starts at the checkpoint block.
+        let mut birthday = CompactBlock::new();
+        birthday.set_height(wallet.checkpoint.height as u64);
+        birthday.set_hash(wallet.checkpoint.block_hash().unwrap().0.to_vec());
+        birthday.set_time(wallet.checkpoint.time);
+        let client = FakeClient {
+            chain: RefCell::new(vec![birthday]),
+        };
+
+        let sync = Synchronizer::new(client, wallet.db_cache.clone(), wallet.db_data.clone());
+        (wallet, sync)
+    }
+
+    /// Serves the checkpoint block followed by `blocks`.
+    fn serve(sync: &Synchronizer<FakeClient>, blocks: &[CompactBlock]) {
+        let mut chain = sync.client().chain.borrow_mut();
+        chain.truncate(1);
+        chain.extend_from_slice(blocks);
+    }
+
+    fn balances(sync: &Synchronizer<FakeClient>) -> (Amount, Amount) {
+        (
+            get_balance(&sync.db_data, 0).unwrap(),
+            get_verified_balance(&sync.db_data, 0).unwrap(),
+        )
+    }
+
+    fn scanned_hash(sync: &Synchronizer<FakeClient>, height: u64) -> Vec<u8> {
+        Connection::open(&sync.db_data)
+            .unwrap()
+            .query_row(
+                "SELECT hash FROM blocks WHERE height = ?",
+                &[height as i64],
+                |row| row.get(0),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn syncs_to_chain_tip() {
+        let (_wallet, sync) = setup();
+        let birthday = sync.client().latest_height().unwrap();
+        extend(&mut sync.client().chain.borrow_mut(), 250, 0);
+
+        let mut statuses = vec![];
+        let height = sync.sync(|status| statuses.push(status)).unwrap();
+
+        assert_eq!(height, birthday + 250);
+        assert_eq!(
+            statuses.first(),
+            Some(&SyncStatus::Downloading {
+                start: birthday + 1,
+                end: birthday + 100
+            })
+        );
+        assert_eq!(statuses.last(), Some(&SyncStatus::Synced(birthday + 250)));
+        assert!(statuses.contains(&SyncStatus::Scanning));
+
+        // Nothing new to do on a second sync.
+        assert_eq!(sync.sync(|_| ()).unwrap(), birthday + 250);
+    }
+
+    #[test]
+    fn reorgs_are_recovered_in_a_single_rewind() {
+        let (wallet, sync) = setup();
+        let birthday = u64::from(sync.client().latest_height().unwrap());
+        let mut chain = wallet.chain.clone();
+
+        // A note arrives at birthday + 6, and the chain continues to birthday + 20.
+        chain.mine_blocks(5);
+        let mut deep_fork = chain.fork(1);
+        chain.receive(&wallet.extfvk, 10_000).unwrap();
+        chain.mine_blocks(10);
+        let mut shallow_fork = chain.fork(2);
+        chain.mine_blocks(5);
+        serve(&sync, chain.blocks());
+        sync.sync(|_| ()).unwrap();
+        let note_value = Amount::from_u64(10_000).unwrap();
+        assert_eq!(balances(&sync), (note_value, note_value));
+
+        // Replace the last 5 blocks with a longer fork.
+        shallow_fork.mine_blocks(8);
+        serve(&sync, shallow_fork.blocks());
+        let mut rewound = vec![];
+        let height = sync
+            .sync(|status| {
+                if let SyncStatus::Rewound(height) = status {
+                    rewound.push(height);
+                }
+            })
+            .unwrap();
+
+        assert_eq!(rewound, vec![birthday as u32 + 15]);
+        assert_eq!(u64::from(height), birthday + 23);
+        let block_hash = |blocks: &[CompactBlock], height: u64| {
+            blocks[(height - birthday - 1) as usize].hash.clone()
+        };
+        assert_eq!(
+            scanned_hash(&sync, birthday + 15),
+            block_hash(chain.blocks(), birthday + 15)
+        );
+        assert_eq!(
+            scanned_hash(&sync, birthday + 16),
+            block_hash(shallow_fork.blocks(), birthday + 16)
+        );
+        assert_eq!(
+            scanned_hash(&sync, birthday + 23),
+            block_hash(shallow_fork.blocks(), birthday + 23)
+        );
+        assert_eq!(balances(&sync), (note_value, note_value));
+
+        let cached: i64 = Connection::open(&sync.db_cache)
+            .unwrap()
+            .query_row("SELECT MAX(height) FROM compactblocks", NO_PARAMS, |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(cached as u64, birthday + 23);
+
+        // A fork from before the note is also found in a single rewind, rather than by
+        // rewinding one block at a time.
+        deep_fork.mine_blocks(25);
+        serve(&sync, deep_fork.blocks());
+        let mut rewound = vec![];
+        let height = sync
+            .sync(|status| {
+                if let SyncStatus::Rewound(height) = status {
+                    rewound.push(height);
+                }
+            })
+            .unwrap();
+
+        assert_eq!(rewound, vec![birthday as u32 + 5]);
+        assert_eq!(u64::from(height), birthday + 30);
+        assert_eq!(
+            scanned_hash(&sync, birthday + 30),
+            block_hash(deep_fork.blocks(), birthday + 30)
+        );
+        assert_eq!(balances(&sync), (Amount::zero(), Amount::zero()));
+    }
+
+    #[test]
+    fn reorgs_deeper_than_max_reorg_size_are_rejected() {
+        let (wallet, sync) = setup();
+        let birthday = sync.client().latest_height().unwrap();
+        let mut chain = wallet.chain.clone();
+        chain.mine_blocks(5);
+        let mut fork = chain.fork(1);
+        chain.mine_blocks(u64::from(MAX_REORG_SIZE) + 10);
+        serve(&sync, chain.blocks());
+        let tip = sync.sync(|_| ()).unwrap();
+        assert_eq!(tip, birthday + 5 + MAX_REORG_SIZE + 10);
+
+        fork.mine_blocks(u64::from(MAX_REORG_SIZE) + 20);
+        serve(&sync, fork.blocks());
+        let err = sync.sync(|_| ()).unwrap_err();
+
+        assert_eq!(
+            err.downcast_ref::<SyncError>(),
+            Some(&SyncError::ReorgTooDeep {
+                height: tip,
+                max_rewind: MAX_REORG_SIZE
+            })
+        );
+        // Nothing was rewound.
+        assert_eq!(
+            scanned_hash(&sync, u64::from(tip)),
+            chain.blocks().last().unwrap().hash
+        );
+    }
+}
@@ -0,0 +1,618 @@
+//! Creation of transactions from the notes in the data DB.
+//!
+//! This follows `zcash_client_sqlite::transact::create_to_address`, which always pays the
+//! default fee and uses the builder's default expiry delta, but lets the caller choose both
+//! through [`TransactionOptions`]. The fee paid is stored in the `sent_transaction_fees`
+//! table created by [`init_fee_table`], so that the `transactions` table keeps the schema
+//! `zcash_client_sqlite` owns.
+
+use failure::{format_err, Error};
+use ff::{PrimeField, PrimeFieldRepr};
+use pairing::bls12_381::Bls12;
+use rusqlite::{types::ToSql, Connection, OptionalExtension, NO_PARAMS};
+use std::path::Path;
+use zcash_client_backend::encoding::encode_extended_full_viewing_key;
+use zcash_client_sqlite::address::RecipientAddress;
+use zcash_primitives::{
+    consensus::BranchId,
+    jubjub::fs::{Fs, FsRepr},
+    merkle_tree::IncrementalWitness,
+    note_encryption::Memo,
+    primitives::{Diversifier, Note},
+    prover::TxProver,
+    sapling::Node,
+    transaction::{
+        builder::Builder,
+        components::{amount::DEFAULT_FEE, Amount},
+        Transaction,
+    },
+    zip32::{ExtendedFullViewingKey, ExtendedSpendingKey},
+    JUBJUB,
+};
+
+use crate::selection::NoteSelectionStrategy;
+
+#[cfg(feature = "mainnet")]
+use zcash_client_backend::constants::mainnet::HRP_SAPLING_EXTENDED_FULL_VIEWING_KEY;
+#[cfg(not(feature = "mainnet"))]
+use zcash_client_backend::constants::testnet::HRP_SAPLING_EXTENDED_FULL_VIEWING_KEY;
+
+/// The number of blocks after the target height at which transactions expire by default.
+pub const DEFAULT_EXPIRY_DELTA: u32 = 20;
+
+/// The smallest expiry delta accepted. Nodes do not relay transactions that expire within
+/// three blocks.
+pub const MIN_EXPIRY_DELTA: u32 = 4;
+
+/// The largest expiry delta accepted: about five weeks of blocks, which leaves room for a
+/// transaction to be signed offline.
+pub const MAX_EXPIRY_DELTA: u32 = 40_320;
+
+/// The largest fee accepted, in zatoshis. Anything above this is almost certainly a mistake.
+pub const MAX_FEE: i64 = 1_000_000;
+
+/// Notes are spent with witnesses from this many blocks below the chain tip, so that a
+/// small reorg does not invalidate the anchor.
+const ANCHOR_OFFSET: u32 = 10;
+
+/// The fee and expiry of a transaction to be created, and how to choose the notes it spends.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TransactionOptions {
+    pub fee: Amount,
+    pub expiry_delta: u32,
+    pub strategy: NoteSelectionStrategy,
+}
+
+impl Default for TransactionOptions {
+    fn default() -> Self {
+        TransactionOptions {
+            fee: DEFAULT_FEE,
+            expiry_delta: DEFAULT_EXPIRY_DELTA,
+            strategy: NoteSelectionStrategy::default(),
+        }
+    }
+}
+
+impl TransactionOptions {
+    /// Validates the given fee in zatoshis and expiry delta in blocks, using the defaults for
+    /// any that are `None`.
+    pub fn new(fee: Option<i64>, expiry_delta: Option<u32>) -> Result<Self, Error> {
+        let mut options = TransactionOptions::default();
+        if let Some(fee) = fee {
+            if fee < 0 || fee > MAX_FEE {
+                return Err(format_err!(
+                    "Fee must be between 0 and {} zatoshis, got {}",
+                    MAX_FEE,
+                    fee
+                ));
+            }
+            options.fee = Amount::from_i64(fee).unwrap();
+        }
+        if let Some(expiry_delta) = expiry_delta {
+            if expiry_delta < MIN_EXPIRY_DELTA || expiry_delta > MAX_EXPIRY_DELTA {
+                return Err(format_err!(
+                    "Expiry delta must be between {} and {} blocks, got {}",
+                    MIN_EXPIRY_DELTA,
+                    MAX_EXPIRY_DELTA,
+                    expiry_delta
+                ));
+            }
+            options.expiry_delta = expiry_delta;
+        }
+        Ok(options)
+    }
+
+    pub fn with_strategy(self, strategy: NoteSelectionStrategy) -> Self {
+        TransactionOptions { strategy, ..self }
+    }
+}
+
+/// Creates the table recording the fee of each transaction the wallet creates, if it is not
+/// there yet.
+pub(crate) fn init_fee_table(data: &Connection) -> Result<(), Error> {
+    data.execute(
+        "CREATE TABLE IF NOT EXISTS sent_transaction_fees (
+            id_tx INTEGER PRIMARY KEY,
+            fee INTEGER NOT NULL,
+            FOREIGN KEY (id_tx) REFERENCES transactions(id_tx)
+        )",
+        NO_PARAMS,
+    )?;
+    Ok(())
+}
+
+/// Returns whether the data DB has the table created by [`init_fee_table`]. Data DBs created
+/// before it was added do not.
+pub(crate) fn has_fee_table(data: &Connection) -> Result<bool, Error> {
+    Ok(data
+        .query_row(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'sent_transaction_fees'",
+            NO_PARAMS,
+            |_| Ok(()),
+        )
+        .optional()?
+        .is_some())
+}
+
+/// Returns the height the next transaction will be mined at, assuming the wallet is synced,
+/// and the height of the witnesses it should spend notes with.
+pub(crate) fn target_and_anchor_heights(data: &Connection) -> Result<(u32, u32), Error> {
+    let (min_height, max_height): (Option<u32>, Option<u32>) = data.query_row(
+        "SELECT MIN(height), MAX(height) FROM blocks",
+        NO_PARAMS,
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    match (min_height, max_height) {
+        (Some(min_height), Some(max_height)) => {
+            let target_height = max_height + 1;
+            let anchor_height = target_height.saturating_sub(ANCHOR_OFFSET).max(min_height);
+            Ok((target_height, anchor_height))
+        }
+        _ => Err(format_err!("Must scan blocks first")),
+    }
+}
+
+/// A note that can be spent at the anchor height.
+pub(crate) struct SpendableNote {
+    pub diversifier: Diversifier,
+    pub note: Note<Bls12>,
+    pub witness: IncrementalWitness<Node>,
+    pub nf: Vec<u8>,
+}
+
+/// Reconstructs a note of the viewing key's from its stored diversifier, value and rcm.
+pub(crate) fn read_note(
+    extfvk: &ExtendedFullViewingKey,
+    diversifier: &[u8],
+    value: u64,
+    rcm: &[u8],
+) -> Result<(Diversifier, Note<Bls12>), Error> {
+    if diversifier.len() != 11 {
+        return Err(format_err!("Invalid diversifier length"));
+    }
+    let mut d = [0; 11];
+    d.copy_from_slice(diversifier);
+    let diversifier = Diversifier(d);
+
+    let mut repr = FsRepr::default();
+    repr.read_le(rcm)?;
+    let rcm = Fs::from_repr(repr).map_err(|e| format_err!("Invalid rcm: {}", e))?;
+
+    let note = extfvk
+        .fvk
+        .vk
+        .into_payment_address(diversifier, &JUBJUB)
+        .and_then(|from| from.create_note(value, rcm, &JUBJUB))
+        .ok_or_else(|| format_err!("Invalid diversifier"))?;
+    Ok((diversifier, note))
+}
+
+/// Returns the account's unspent notes that can be spent at the anchor height, with their
+/// row IDs, oldest first.
+pub(crate) fn spendable_notes(
+    data: &Connection,
+    extfvk: &ExtendedFullViewingKey,
+    account: u32,
+    anchor_height: u32,
+) -> Result<Vec<(i64, SpendableNote)>, Error> {
+    let mut stmt = data.prepare(
+        "SELECT id_note, diversifier, value, rcm, nf, sapling_witnesses.witness
+        FROM received_notes
+        INNER JOIN transactions ON transactions.id_tx = received_notes.tx
+        INNER JOIN sapling_witnesses ON sapling_witnesses.note = received_notes.id_note
+        WHERE account = ? AND spent IS NULL AND transactions.block <= ?
+            AND sapling_witnesses.block = ?
+        ORDER BY id_note",
+    )?;
+    let rows = stmt.query_map(&[account, anchor_height, anchor_height], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, Vec<u8>>(1)?,
+            row.get::<_, i64>(2)?,
+            row.get::<_, Vec<u8>>(3)?,
+            row.get::<_, Vec<u8>>(4)?,
+            row.get::<_, Vec<u8>>(5)?,
+        ))
+    })?;
+
+    let mut notes = vec![];
+    for row in rows {
+        let (id_note, diversifier, value, rcm, nf, witness) = row?;
+        let (diversifier, note) = read_note(extfvk, &diversifier, value as u64, &rcm)
+            .map_err(|e| format_err!("Invalid stored note: {}", e))?;
+        notes.push((
+            id_note,
+            SpendableNote {
+                diversifier,
+                note,
+                witness: IncrementalWitness::read(&witness[..])?,
+                nf,
+            },
+        ));
+    }
+    Ok(notes)
+}
+
+/// Chooses the account's notes to spend for `target_value` with the given strategy.
+pub(crate) fn select_notes(
+    data: &Connection,
+    extfvk: &ExtendedFullViewingKey,
+    account: u32,
+    anchor_height: u32,
+    target_value: i64,
+    strategy: NoteSelectionStrategy,
+) -> Result<Vec<SpendableNote>, Error> {
+    let notes = spendable_notes(data, extfvk, account, anchor_height)?;
+    let values: Vec<u64> = notes.iter().map(|(_, note)| note.note.value).collect();
+    let selected = strategy.select(&values, target_value as u64)?;
+
+    let mut notes: Vec<Option<SpendableNote>> =
+        notes.into_iter().map(|(_, note)| Some(note)).collect();
+    Ok(selected
+        .into_iter()
+        .filter_map(|index| notes[index].take())
+        .collect())
+}
+
+/// Returns the viewing key of `account` stored in the data DB.
+pub(crate) fn tracked_extfvk(data: &Connection, account: u32) -> Result<String, Error> {
+    data.query_row(
+        "SELECT extfvk FROM accounts WHERE account = ?",
+        &[account],
+        |row| row.get(0),
+    )
+    .optional()?
+    .ok_or_else(|| format_err!("Account {} does not exist", account))
+}
+
+/// Checks that `extsk` is the spending key of `account`, returning its viewing key.
+pub(crate) fn account_extfvk(
+    data: &Connection,
+    account: u32,
+    extsk: &ExtendedSpendingKey,
+) -> Result<ExtendedFullViewingKey, Error> {
+    let extfvk = ExtendedFullViewingKey::from(extsk);
+    if tracked_extfvk(data, account)?
+        != encode_extended_full_viewing_key(HRP_SAPLING_EXTENDED_FULL_VIEWING_KEY, &extfvk)
+    {
+        return Err(format_err!(
+            "Incorrect ExtendedSpendingKey for account {}",
+            account
+        ));
+    }
+    Ok(extfvk)
+}
+
+/// Returns an error unless the notes are worth at least `value` plus the fee.
+pub(crate) fn check_balance(
+    notes: &[SpendableNote],
+    value: Amount,
+    options: TransactionOptions,
+) -> Result<(), Error> {
+    let target_value = i64::from(value) + i64::from(options.fee);
+    let selected_value: u64 = notes.iter().map(|selected| selected.note.value).sum();
+    if selected_value < target_value as u64 {
+        return Err(format_err!(
+            "Insufficient balance (have {}, need {} including fee)",
+            selected_value,
+            target_value
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the height to create a transaction's [`Builder`] at, for it to expire
+/// `expiry_delta` blocks after `target_height`.
+///
+/// The builder has no expiry setter. It sets the expiry height to the height it is given plus
+/// the default delta, and uses that height for nothing else. `expiry_height_is_set` fails if
+/// that changes.
+///
+/// The returned height can be on the other side of a network upgrade from `target_height`.
+/// That is harmless because the consensus branch ID is passed to [`Builder::build`] rather
+/// than derived from the builder's height, so callers must pass the branch ID for
+/// `target_height`. `branch_id_ignores_the_builder_height` checks this at an activation height.
+fn builder_height(target_height: u32, expiry_delta: u32) -> u32 {
+    target_height + expiry_delta - DEFAULT_EXPIRY_DELTA
+}
+
+/// Builds and proves a transaction spending `notes` to pay `value` to `to`, returning it
+/// with the index of the payment among its outputs.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn build_transaction(
+    consensus_branch_id: BranchId,
+    prover: impl TxProver,
+    extsk: &ExtendedSpendingKey,
+    notes: Vec<SpendableNote>,
+    to: &RecipientAddress,
+    value: Amount,
+    memo: Option<Memo>,
+    target_height: u32,
+    options: TransactionOptions,
+) -> Result<(Transaction, i64), Error> {
+    let ovk = ExtendedFullViewingKey::from(extsk).fvk.ovk;
+
+    let mut builder = Builder::new(builder_height(target_height, options.expiry_delta));
+    builder.set_fee(options.fee);
+    for selected in notes {
+        let witness = selected
+            .witness
+            .path()
+            .ok_or_else(|| format_err!("Witness is for an empty tree"))?;
+        builder
+            .add_sapling_spend(extsk.clone(), selected.diversifier, selected.note, witness)
+            .map_err(|e| format_err!("Error while adding spend: {:?}", e))?;
+    }
+    match to {
+        RecipientAddress::Shielded(to) => builder.add_sapling_output(ovk, to.clone(), value, memo),
+        RecipientAddress::Transparent(to) => builder.add_transparent_output(to, value),
+    }
+    .map_err(|e| format_err!("Error while adding output: {:?}", e))?;
+    let (tx, tx_metadata) = builder
+        .build(consensus_branch_id, prover)
+        .map_err(|e| format_err!("Error while building transaction: {:?}", e))?;
+    if tx.expiry_height != target_height + options.expiry_delta {
+        return Err(format_err!(
+            "Transaction expires at {}, expected {}",
+            tx.expiry_height,
+            target_height + options.expiry_delta
+        ));
+    }
+
+    // We only added one output, so it is the first.
+    let output_index = tx_metadata.output_index(0).map_or(0, |index| index as i64);
+    Ok((tx, output_index))
+}
+
+/// Stores a transaction sent from `account`, marks the notes it spends as spent by it, and
+/// returns its row ID.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn store_sent_transaction(
+    data: &mut Connection,
+    tx: &Transaction,
+    account: u32,
+    to: &RecipientAddress,
+    value: Amount,
+    memo: Option<&Memo>,
+    output_index: i64,
+    fee: Amount,
+) -> Result<i64, Error> {
+    let mut raw = vec![];
+    tx.write(&mut raw)?;
+    let txid = tx.txid().0.to_vec();
+
+    init_fee_table(data)?;
+    let db_tx = data.transaction()?;
+    db_tx.execute(
+        "INSERT INTO transactions (txid, created, expiry_height, raw)
+        VALUES (?, datetime('now'), ?, ?)",
+        &[&txid as &dyn ToSql, &tx.expiry_height, &raw],
+    )?;
+    let id_tx = db_tx.last_insert_rowid();
+    db_tx.execute(
+        "INSERT INTO sent_transaction_fees (id_tx, fee) VALUES (?, ?)",
+        &[id_tx, i64::from(fee)],
+    )?;
+
+    // Lock the notes so that they are not selected again before the transaction is mined,
+    // when scanning marks them as spent by it for good.
+    for spend in &tx.shielded_spends {
+        db_tx.execute(
+            "UPDATE received_notes SET spent = ? WHERE nf = ?",
+            &[&id_tx as &dyn ToSql, &spend.nullifier.to_vec()],
+        )?;
+    }
+
+    db_tx.execute(
+        "INSERT INTO sent_notes (tx, output_index, from_account, address, value, memo)
+        VALUES (?, ?, ?, ?, ?, ?)",
+        &[
+            &id_tx as &dyn ToSql,
+            &output_index,
+            &account,
+            &to.to_string(),
+            &i64::from(value),
+            &memo.map(|memo| memo.as_bytes().to_vec()),
+        ],
+    )?;
+    db_tx.commit()?;
+
+    Ok(id_tx)
+}
+
+/// Creates a transaction paying `value` to `to` from the account's notes, stores it with the
+/// notes it spends marked as spent, and returns its row ID.
+#[allow(clippy::too_many_arguments)]
+pub fn create_to_address<P: AsRef<Path>>(
+    db_data: P,
+    consensus_branch_id: BranchId,
+    prover: impl TxProver,
+    account: u32,
+    extsk: &ExtendedSpendingKey,
+    to: &RecipientAddress,
+    value: Amount,
+    memo: Option<Memo>,
+    options: TransactionOptions,
+) -> Result<i64, Error> {
+    let mut data = Connection::open(db_data)?;
+    let extfvk = account_extfvk(&data, account, extsk)?;
+    let (target_height, anchor_height) = target_and_anchor_heights(&data)?;
+
+    let target_value = i64::from(value) + i64::from(options.fee);
+    let notes = select_notes(
+        &data,
+        &extfvk,
+        account,
+        anchor_height,
+        target_value,
+        options.strategy,
+    )?;
+
+    let (tx, output_index) = build_transaction(
+        consensus_branch_id,
+        prover,
+        extsk,
+        notes,
+        to,
+        value,
+        memo.clone(),
+        target_height,
+        options,
+    )?;
+    store_sent_transaction(
+        &mut data,
+        &tx,
+        account,
+        to,
+        value,
+        memo.as_ref(),
+        output_index,
+        options.fee,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use pairing::bls12_381::{Bls12, Fr};
+    use rusqlite::{Connection, NO_PARAMS};
+    use zcash_client_sqlite::init::init_data_database;
+    use zcash_primitives::{
+        consensus::{BranchId, NetworkUpgrade, Parameters},
+        jubjub::{edwards, fs::Fs, Unknown},
+        merkle_tree::CommitmentTreeWitness,
+        primitives::{Diversifier, PaymentAddress, ProofGenerationKey},
+        prover::TxProver,
+        redjubjub::{PublicKey, Signature},
+        sapling::Node,
+        transaction::{
+            builder::Builder,
+            components::{Amount, GROTH_PROOF_SIZE},
+        },
+    };
+
+    use super::{
+        builder_height, has_fee_table, init_fee_table, TransactionOptions, DEFAULT_EXPIRY_DELTA,
+        MAX_EXPIRY_DELTA, MAX_FEE,
+    };
+    use crate::{wallet, Network};
+
+    /// A prover for transactions with no Sapling spends or outputs, which need no proofs.
+    struct NoProofs;
+
+    impl TxProver for NoProofs {
+        type SaplingProvingContext = ();
+
+        fn new_sapling_proving_context(&self) {}
+
+        fn spend_proof(
+            &self,
+            _: &mut (),
+            _: ProofGenerationKey<Bls12>,
+            _: Diversifier,
+            _: Fs,
+            _: Fs,
+            _: u64,
+            _: Fr,
+            _: CommitmentTreeWitness<Node>,
+        ) -> Result<
+            (
+                [u8; GROTH_PROOF_SIZE],
+                edwards::Point<Bls12, Unknown>,
+                PublicKey<Bls12>,
+            ),
+            (),
+        > {
+            unreachable!("no spends")
+        }
+
+        fn output_proof(
+            &self,
+            _: &mut (),
+            _: Fs,
+            _: PaymentAddress<Bls12>,
+            _: Fs,
+            _: u64,
+        ) -> ([u8; GROTH_PROOF_SIZE], edwards::Point<Bls12, Unknown>) {
+            unreachable!("no outputs")
+        }
+
+        fn binding_sig(&self, _: &mut (), _: Amount, _: &[u8; 32]) -> Result<Signature, ()> {
+            Signature::read(&[0u8; 64][..]).map_err(|_| ())
+        }
+    }
+
+    #[test]
+    fn options_are_validated() {
+        let options = TransactionOptions::new(None, None).unwrap();
+        assert_eq!(options, TransactionOptions::default());
+        assert_eq!(options.expiry_delta, DEFAULT_EXPIRY_DELTA);
+
+        let options = TransactionOptions::new(Some(MAX_FEE), Some(MAX_EXPIRY_DELTA)).unwrap();
+        assert_eq!(i64::from(options.fee), MAX_FEE);
+        assert_eq!(options.expiry_delta, MAX_EXPIRY_DELTA);
+        assert_eq!(
+            i64::from(TransactionOptions::new(Some(0), None).unwrap().fee),
+            0
+        );
+
+        assert!(TransactionOptions::new(Some(-1), None).is_err());
+        assert!(TransactionOptions::new(Some(MAX_FEE + 1), None).is_err());
+        assert!(TransactionOptions::new(None, Some(3)).is_err());
+        assert!(TransactionOptions::new(None, Some(MAX_EXPIRY_DELTA + 1)).is_err());
+    }
+
+    #[test]
+    fn expiry_height_is_set() {
+        let target_height = 500_000;
+        for &expiry_delta in &[4, DEFAULT_EXPIRY_DELTA, 100, MAX_EXPIRY_DELTA] {
+            let mut builder = Builder::new(builder_height(target_height, expiry_delta));
+            builder.set_fee(Amount::zero());
+            let (tx, _) = builder.build(BranchId::Sapling, NoProofs).unwrap();
+            assert_eq!(tx.expiry_height, target_height + expiry_delta);
+        }
+    }
+
+    #[test]
+    fn branch_id_ignores_the_builder_height() {
+        let activation = Network::activation_height(NetworkUpgrade::Blossom).unwrap();
+        let target_height = activation - 1;
+        let branch_id = BranchId::for_height::<Network>(target_height);
+        assert_eq!(branch_id, BranchId::Sapling);
+
+        // The builder height is before the upgrade for a short expiry, and after it for a long
+        // one, but the transaction is built for the target height either way.
+        let short = builder_height(target_height, 4);
+        let long = builder_height(target_height, MAX_EXPIRY_DELTA);
+        assert_eq!(BranchId::for_height::<Network>(short), BranchId::Sapling);
+        assert_eq!(BranchId::for_height::<Network>(long), BranchId::Blossom);
+        for &(height, expiry_delta) in &[(short, 4), (long, MAX_EXPIRY_DELTA)] {
+            let mut builder = Builder::new(height);
+            builder.set_fee(Amount::zero());
+            let (tx, _) = builder.build(branch_id, NoProofs).unwrap();
+            assert_eq!(tx.expiry_height, target_height + expiry_delta);
+        }
+    }
+
+    #[test]
+    fn fee_table_is_created_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_data = dir.path().join("data.db");
+        init_data_database(&db_data).unwrap();
+
+        let data = Connection::open(&db_data).unwrap();
+        assert!(!has_fee_table(&data).unwrap());
+        init_fee_table(&data).unwrap();
+        init_fee_table(&data).unwrap();
+        assert!(has_fee_table(&data).unwrap());
+        data.execute(
+            "INSERT INTO sent_transaction_fees (id_tx, fee) VALUES (1, 1000)",
+            NO_PARAMS,
+        )
+        .unwrap();
+
+        // The wallet's own data DBs have it from the start.
+        let db_data = dir.path().join("wallet.db");
+        wallet::init_data_db(&db_data).unwrap();
+        assert!(has_fee_table(&Connection::open(&db_data).unwrap()).unwrap());
+    }
+}
@@ -0,0 +1,476 @@
+//! Building Sapling tree checkpoints from compact blocks.
+//!
+//! This is the scanning half of `update-sapling-tree`, kept in the library so that it can be
+//! tested against block files and the mock lightwalletd. It is only built with the
+//! `lightwalletd` feature.
+
+use ff::{PrimeField, PrimeFieldRepr};
+use pairing::bls12_381::{Fr, FrRepr};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+use zcash_client_backend::proto::compact_formats::CompactBlock;
+use zcash_primitives::{merkle_tree::CommitmentTree, sapling::Node};
+
+use crate::checkpoint::{Checkpoint, CheckpointFormat};
+use crate::lightwalletd;
+
+mod block_source;
+
+pub use self::block_source::{BlockFile, BlockSource};
+
+pub const BATCH_SIZE: u64 = 10_000;
+pub const MISSING_BLOCK_RETRIES: u32 = 3;
+pub const BATCH_RETRIES: u32 = 5;
+pub const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+pub const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+#[derive(Debug)]
+pub enum Error {
+    InvalidArgument(String),
+    InvalidBlock,
+    InvalidCheckpoint(failure::Error),
+    Output(failure::Error),
+    CheckpointMismatch(String),
+    MissingBlock(u64),
+    UnexpectedHeight(u64, u64),
+    PrevHashMismatch(u64),
+    Io(std::io::Error),
+    Lightwalletd(lightwalletd::Error),
+    Protobuf(protobuf::ProtobufError),
+    Sqlite(rusqlite::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<lightwalletd::Error> for Error {
+    fn from(e: lightwalletd::Error) -> Self {
+        Error::Lightwalletd(e)
+    }
+}
+
+impl From<protobuf::ProtobufError> for Error {
+    fn from(e: protobuf::ProtobufError) -> Self {
+        Error::Protobuf(e)
+    }
+}
+
+impl From<rusqlite::Error> for Error {
+    fn from(e: rusqlite::Error) -> Self {
+        Error::Sqlite(e)
+    }
+}
+
+/// How [`scan_blocks`] requests blocks and recovers from failures.
+#[derive(Clone, Debug)]
+pub struct ScanOptions {
+    /// Number of blocks to request from the source at a time.
+    pub batch_size: u64,
+    /// Number of times to request missing blocks again before giving up.
+    pub missing_block_retries: u32,
+    /// Number of times to retry a batch that fails with a server error.
+    pub batch_retries: u32,
+    /// How long to wait before retrying a failed batch the first time. The wait doubles on
+    /// each retry, up to [`MAX_BACKOFF`].
+    pub initial_backoff: Duration,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        ScanOptions {
+            batch_size: BATCH_SIZE,
+            missing_block_retries: MISSING_BLOCK_RETRIES,
+            batch_retries: BATCH_RETRIES,
+            initial_backoff: INITIAL_BACKOFF,
+        }
+    }
+}
+
+pub fn print_sapling_tree<W: Write>(
+    out: W,
+    format: CheckpointFormat,
+    height: u64,
+    hash: &[u8],
+    time: u32,
+    tree: &CommitmentTree<Node>,
+) -> Result<(), Error> {
+    Checkpoint::from_tree(height as i32, hash, time, tree)
+        .write(format, out)
+        .map_err(Error::Output)
+}
+
+/// Saves progress to the resume file, replacing it atomically so that an interrupted write
+/// cannot corrupt the previous snapshot.
+pub fn write_resume_file(
+    path: &Path,
+    height: u64,
+    hash: &[u8],
+    time: u32,
+    tree: &CommitmentTree<Node>,
+) -> Result<(), Error> {
+    let tmp = path.with_extension("tmp");
+    print_sapling_tree(
+        File::create(&tmp)?,
+        CheckpointFormat::Json,
+        height,
+        hash,
+        time,
+        tree,
+    )?;
+    Ok(fs::rename(&tmp, path)?)
+}
+
+/// Loads an existing checkpoint in any format, returning the height, hash and tree to continue from.
+pub fn load_checkpoint(path: &Path) -> Result<(u64, Vec<u8>, CommitmentTree<Node>), Error> {
+    let checkpoint = Checkpoint::read(&fs::read(path)?).map_err(Error::InvalidCheckpoint)?;
+    let hash = checkpoint
+        .block_hash()
+        .map_err(Error::InvalidCheckpoint)?
+        .0
+        .to_vec();
+    let tree = checkpoint
+        .commitment_tree()
+        .map_err(Error::InvalidCheckpoint)?;
+    Ok((checkpoint.height as u64, hash, tree))
+}
+
+/// Appends the note commitments of the blocks in `start..=end` to `tree`, reading them from
+/// `source` in batches, and calls `f` with each block once it has been added.
+///
+/// Every block must have the next height and build on the previous block, starting from
+/// `prev_hash` if it is given. Batches that come back short are requested again up to
+/// `options.missing_block_retries` times before giving up.
+pub fn scan_blocks<F>(
+    source: &mut BlockSource,
+    options: &ScanOptions,
+    start: u64,
+    end: u64,
+    mut prev_hash: Option<Vec<u8>>,
+    tree: &mut CommitmentTree<Node>,
+    mut f: F,
+) -> Result<(), Error>
+where
+    F: FnMut(&CompactBlock, &CommitmentTree<Node>) -> Result<(), Error>,
+{
+    let batch_size = options.batch_size;
+    let max_retries = options.missing_block_retries;
+    let mut next_height = start;
+    let mut retries = 0;
+    while next_height <= end {
+        let end_height = if end - next_height < batch_size {
+            end
+        } else {
+            next_height + batch_size - 1
+        };
+
+        // Request the next batch of blocks
+        eprintln!("Fetching blocks {}..{}", next_height, end_height);
+        let blocks = fetch_batch(source, options, next_height, end_height)?;
+
+        let mut parsed = 0;
+        for block in blocks {
+            if block.height > next_height && block.height <= end_height {
+                // Some blocks are missing; retry from the first of them.
+                break;
+            }
+            if block.height != next_height || block.height > end_height {
+                return Err(Error::UnexpectedHeight(next_height, block.height));
+            }
+            if let Some(ref expected) = prev_hash {
+                if &block.prevHash != expected {
+                    return Err(Error::PrevHashMismatch(block.height));
+                }
+            }
+            for tx in block.vtx.iter() {
+                for output in tx.outputs.iter() {
+                    // Append commitment to tree
+                    let mut repr = FrRepr::default();
+                    repr.read_le(&output.cmu[..])?;
+                    let cmu = Fr::from_repr(repr).map_err(|_| Error::InvalidBlock)?;
+                    let node = Node::new(cmu.into_repr());
+                    tree.append(node).expect("tree is not full");
+                }
+            }
+            f(&block, tree)?;
+            prev_hash = Some(block.hash);
+            next_height += 1;
+            parsed += 1
+        }
+        eprintln!("Parsed {} blocks", parsed);
+
+        if next_height <= end_height {
+            if retries == max_retries {
+                return Err(Error::MissingBlock(next_height));
+            }
+            retries += 1;
+            eprintln!(
+                "block {} is missing, retrying ({}/{})",
+                next_height, retries, max_retries
+            );
+        } else {
+            retries = 0;
+        }
+    }
+    Ok(())
+}
+
+/// Fetches the blocks in `start..=end`, retrying with exponential backoff if the server fails.
+pub fn fetch_batch(
+    source: &mut BlockSource,
+    options: &ScanOptions,
+    start: u64,
+    end: u64,
+) -> Result<Vec<CompactBlock>, Error> {
+    let max_retries = options.batch_retries;
+    let mut backoff = options.initial_backoff;
+    let mut retries = 0;
+    loop {
+        match source.block_range(start, end) {
+            Err(Error::Lightwalletd(e)) if retries < max_retries => {
+                retries += 1;
+                eprintln!(
+                    "failed to fetch blocks {}..{}: {}; retrying in {}s ({}/{})",
+                    start,
+                    end,
+                    e,
+                    backoff.as_secs(),
+                    retries,
+                    max_retries
+                );
+                thread::sleep(backoff);
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+            res => return res,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::path::Path;
+    use std::time::Duration;
+    use zcash_client_backend::{keys::spending_key, proto::compact_formats::CompactBlock};
+    use zcash_primitives::{
+        merkle_tree::CommitmentTree, sapling::Node, zip32::ExtendedFullViewingKey,
+    };
+
+    use super::{load_checkpoint, scan_blocks, write_resume_file, BlockSource, Error, ScanOptions};
+    use crate::fixtures::{write_block_file, ChainBuilder};
+    use crate::lightwalletd::LightwalletdClient;
+    use crate::mock_lightwalletd::{Faults, MockServer};
+
+    /// Height of the first generated block, above Sapling activation on both networks so that
+    /// snapshots of the chain are valid checkpoints.
+    const START: u64 = 500_000;
+
+    /// Builds 20 blocks from [`START`], with a note in every other block.
+    fn chain() -> ChainBuilder {
+        let extfvk = ExtendedFullViewingKey::from(&spending_key(&[0; 32], 1, 0));
+        let mut chain = ChainBuilder::new(START, &[0; 32], 0, CommitmentTree::new());
+        for i in 0..20 {
+            if i % 2 == 0 {
+                chain.receive(&extfvk, 1_000).unwrap();
+            }
+            chain.mine();
+        }
+        chain
+    }
+
+    fn options() -> ScanOptions {
+        ScanOptions {
+            batch_size: 7,
+            missing_block_retries: 1,
+            batch_retries: 1,
+            initial_backoff: Duration::from_millis(10),
+        }
+    }
+
+    fn block_file(dir: &Path, blocks: &[CompactBlock]) -> BlockSource {
+        let path = dir.join("blocks.bin");
+        write_block_file(File::create(&path).unwrap(), blocks).unwrap();
+        BlockSource::block_file(&path).unwrap()
+    }
+
+    /// Scans the 20 blocks from [`START`] on top of the all-zero hash, returning the tree and
+    /// the heights passed to the callback.
+    fn scan(source: &mut BlockSource) -> Result<(CommitmentTree<Node>, Vec<u64>), Error> {
+        let mut tree = CommitmentTree::new();
+        let mut heights = vec![];
+        scan_blocks(
+            source,
+            &options(),
+            START,
+            START + 19,
+            Some(vec![0; 32]),
+            &mut tree,
+            |block, _| {
+                heights.push(block.height);
+                Ok(())
+            },
+        )?;
+        Ok((tree, heights))
+    }
+
+    #[test]
+    fn blocks_are_scanned_into_the_tree() {
+        let dir = tempfile::tempdir().unwrap();
+        let chain = chain();
+        let mut source = block_file(dir.path(), chain.blocks());
+
+        let (tree, heights) = scan(&mut source).unwrap();
+        assert_eq!(tree.root(), chain.tree().root());
+        assert_eq!(heights, (START..START + 20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn gaps_are_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut blocks = chain().into_blocks();
+        blocks.remove(10);
+        let mut source = block_file(dir.path(), &blocks);
+
+        match scan(&mut source) {
+            Err(Error::MissingBlock(height)) if height == START + 10 => (),
+            res => panic!("unexpected result {:?}", res.map(|(_, heights)| heights)),
+        }
+    }
+
+    #[test]
+    fn wrong_hashes_are_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut blocks = chain().into_blocks();
+        blocks[5].prevHash[0] ^= 0xff;
+        let mut source = block_file(dir.path(), &blocks);
+
+        match scan(&mut source) {
+            Err(Error::PrevHashMismatch(height)) if height == START + 5 => (),
+            res => panic!("unexpected result {:?}", res.map(|(_, heights)| heights)),
+        }
+    }
+
+    #[test]
+    fn repeated_heights_are_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut blocks = chain().into_blocks();
+        let repeated = blocks[3].clone();
+        blocks.insert(4, repeated);
+        let mut source = block_file(dir.path(), &blocks);
+
+        match scan(&mut source) {
+            Err(Error::UnexpectedHeight(expected, height))
+                if expected == START + 4 && height == START + 3 => {}
+            res => panic!("unexpected result {:?}", res.map(|(_, heights)| heights)),
+        }
+    }
+
+    #[test]
+    fn failed_requests_are_retried() {
+        let chain = chain();
+        let server = MockServer::start(chain.blocks().to_vec()).unwrap();
+        let client = LightwalletdClient::connect(&server.config()).unwrap();
+        let mut source = BlockSource::Lightwalletd(client);
+
+        // A single failed request is within the one retry allowed.
+        server.set_faults(Faults {
+            failed_requests: 1,
+            ..Faults::default()
+        });
+        let (tree, _) = scan(&mut source).unwrap();
+        assert_eq!(tree.root(), chain.tree().root());
+
+        server.set_faults(Faults {
+            failed_requests: 2,
+            ..Faults::default()
+        });
+        match scan(&mut source) {
+            Err(Error::Lightwalletd(_)) => (),
+            res => panic!("unexpected result {:?}", res.map(|(_, heights)| heights)),
+        }
+    }
+
+    #[test]
+    fn scan_resumes_from_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let chain = chain();
+        let resume_file = dir.path().join("resume.json");
+
+        // Stop after writing a snapshot half way, as if the run had been interrupted.
+        let mut tree = CommitmentTree::new();
+        let mut source = block_file(dir.path(), chain.blocks());
+        let res = scan_blocks(
+            &mut source,
+            &options(),
+            START,
+            START + 19,
+            Some(vec![0; 32]),
+            &mut tree,
+            |block, tree| {
+                if block.height == START + 9 {
+                    write_resume_file(&resume_file, block.height, &block.hash, block.time, tree)?;
+                    return Err(Error::InvalidBlock);
+                }
+                Ok(())
+            },
+        );
+        match res {
+            Err(Error::InvalidBlock) => (),
+            res => panic!("unexpected result {:?}", res),
+        }
+        assert!(!resume_file.with_extension("tmp").exists());
+
+        let (height, hash, mut tree) = load_checkpoint(&resume_file).unwrap();
+        assert_eq!(height, START + 9);
+        let mut source = block_file(dir.path(), chain.blocks());
+        let mut heights = vec![];
+        scan_blocks(
+            &mut source,
+            &options(),
+            height + 1,
+            START + 19,
+            Some(hash),
+            &mut tree,
+            |block, _| {
+                heights.push(block.height);
+                Ok(())
+            },
+        )
+        .unwrap();
+
+        assert_eq!(heights, (START + 10..START + 20).collect::<Vec<_>>());
+        assert_eq!(tree.root(), chain.tree().root());
+    }
+
+    #[test]
+    fn mock_server_faults_are_detected() {
+        let chain = chain();
+        let server = MockServer::start(chain.blocks().to_vec()).unwrap();
+        let client = LightwalletdClient::connect(&server.config()).unwrap();
+        let mut source = BlockSource::Lightwalletd(client);
+
+        server.set_faults(Faults {
+            gaps: vec![START + 10],
+            ..Faults::default()
+        });
+        match scan(&mut source) {
+            Err(Error::MissingBlock(height)) if height == START + 10 => (),
+            res => panic!("unexpected result {:?}", res.map(|(_, heights)| heights)),
+        }
+
+        // A corrupted hash is caught by the block that builds on it.
+        server.set_faults(Faults {
+            wrong_hashes: vec![START + 4],
+            ..Faults::default()
+        });
+        match scan(&mut source) {
+            Err(Error::PrevHashMismatch(height)) if height == START + 5 => (),
+            res => panic!("unexpected result {:?}", res.map(|(_, heights)| heights)),
+        }
+    }
+}
@@ -0,0 +1,190 @@
+//! Sources of compact blocks for the checkpoint updater.
+
+use protobuf::parse_from_bytes;
+use rusqlite::Connection;
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+use zcash_client_backend::proto::compact_formats::CompactBlock;
+
+use super::Error;
+use crate::lightwalletd::LightwalletdClient;
+
+/// Where the updater reads compact blocks from.
+pub enum BlockSource {
+    /// A lightwalletd server.
+    Lightwalletd(LightwalletdClient),
+    /// A file of length-delimited `CompactBlock` protobufs, in height order.
+    BlockFile(BlockFile),
+    /// A cache DB in the SDK's `compactblocks` schema.
+    CacheDb(Connection),
+}
+
+impl BlockSource {
+    pub fn block_file(path: &Path) -> Result<Self, Error> {
+        Ok(BlockSource::BlockFile(BlockFile {
+            reader: BufReader::new(File::open(path)?),
+            pending: None,
+        }))
+    }
+
+    pub fn cache_db(path: &Path) -> Result<Self, Error> {
+        Ok(BlockSource::CacheDb(Connection::open(path)?))
+    }
+
+    /// Fetches the blocks in the inclusive range `start..=end`.
+    ///
+    /// Blocks that the source does not have are omitted rather than reported as errors.
+    pub fn block_range(&mut self, start: u64, end: u64) -> Result<Vec<CompactBlock>, Error> {
+        match self {
+            BlockSource::Lightwalletd(client) => Ok(client.block_range(start, end)?),
+            BlockSource::BlockFile(file) => file.block_range(start, end),
+            BlockSource::CacheDb(cache) => {
+                let mut stmt = cache.prepare(
+                    "SELECT data FROM compactblocks
+                    WHERE height >= ? AND height <= ?
+                    ORDER BY height ASC",
+                )?;
+                let rows =
+                    stmt.query_map(&[start as i64, end as i64], |row| row.get::<_, Vec<u8>>(0))?;
+                let mut blocks = vec![];
+                for data in rows {
+                    blocks.push(parse_from_bytes(&data?)?);
+                }
+                Ok(blocks)
+            }
+        }
+    }
+}
+
+/// Sequential reader over a file of length-delimited `CompactBlock`s.
+pub struct BlockFile {
+    reader: BufReader<File>,
+    /// A block that was read past the end of the previous range.
+    pending: Option<CompactBlock>,
+}
+
+impl BlockFile {
+    /// Reads a base-128 varint, returning `None` at a clean end of file.
+    fn read_length(&mut self) -> io::Result<Option<u64>> {
+        let mut value = 0u64;
+        for i in 0..10 {
+            let mut byte = [0u8];
+            if self.reader.read(&mut byte)? == 0 {
+                return if i == 0 {
+                    Ok(None)
+                } else {
+                    Err(io::ErrorKind::UnexpectedEof.into())
+                };
+            }
+            value |= u64::from(byte[0] & 0x7f) << (7 * i);
+            if byte[0] & 0x80 == 0 {
+                return Ok(Some(value));
+            }
+        }
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "length prefix is too long",
+        ))
+    }
+
+    fn next_block(&mut self) -> Result<Option<CompactBlock>, Error> {
+        if let Some(block) = self.pending.take() {
+            return Ok(Some(block));
+        }
+        match self.read_length()? {
+            Some(len) => {
+                let mut data = vec![0; len as usize];
+                self.reader.read_exact(&mut data)?;
+                Ok(Some(parse_from_bytes(&data)?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn block_range(&mut self, start: u64, end: u64) -> Result<Vec<CompactBlock>, Error> {
+        let mut blocks = vec![];
+        while let Some(block) = self.next_block()? {
+            if block.height < start {
+                continue;
+            }
+            if block.height > end {
+                self.pending = Some(block);
+                break;
+            }
+            blocks.push(block);
+        }
+        Ok(blocks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use zcash_client_backend::proto::compact_formats::CompactBlock;
+    use zcash_primitives::merkle_tree::CommitmentTree;
+
+    use super::BlockSource;
+    use crate::fixtures::{write_block_file, write_cache_db, ChainBuilder};
+
+    fn chain() -> Vec<CompactBlock> {
+        let mut chain = ChainBuilder::new(1000, &[0; 32], 0, CommitmentTree::new());
+        chain.mine_blocks(10);
+        chain.into_blocks()
+    }
+
+    fn heights(blocks: &[CompactBlock]) -> Vec<u64> {
+        blocks.iter().map(|block| block.height).collect()
+    }
+
+    #[test]
+    fn block_file_is_read_in_ranges() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("blocks.bin");
+        let blocks = chain();
+        write_block_file(File::create(&path).unwrap(), &blocks).unwrap();
+
+        let mut source = BlockSource::block_file(&path).unwrap();
+        // Blocks below the range are skipped, and the block read past its end is kept for
+        // the next range.
+        assert_eq!(
+            heights(&source.block_range(1002, 1004).unwrap()),
+            vec![1002, 1003, 1004]
+        );
+        let rest = source.block_range(1005, 1100).unwrap();
+        assert_eq!(heights(&rest), (1005..1010).collect::<Vec<_>>());
+        assert_eq!(rest[0], blocks[5]);
+        assert!(source.block_range(1010, 1020).unwrap().is_empty());
+    }
+
+    #[test]
+    fn truncated_block_file_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("blocks.bin");
+        let mut data = vec![];
+        write_block_file(&mut data, &chain()).unwrap();
+        data.truncate(data.len() - 1);
+        std::fs::write(&path, data).unwrap();
+
+        let mut source = BlockSource::block_file(&path).unwrap();
+        assert!(source.block_range(1000, 1100).is_err());
+    }
+
+    #[test]
+    fn cache_db_is_read_in_ranges() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.db");
+        let blocks = chain();
+        write_cache_db(&path, &blocks).unwrap();
+
+        let mut source = BlockSource::cache_db(&path).unwrap();
+        assert_eq!(
+            source.block_range(1003, 1005).unwrap(),
+            blocks[3..6].to_vec()
+        );
+        assert_eq!(
+            heights(&source.block_range(1008, 1100).unwrap()),
+            vec![1008, 1009]
+        );
+    }
+}
@@ -2,11 +2,14 @@ use jni::{
     descriptors::Desc,
     errors::Result as JNIResult,
     objects::{JClass, JObject, JString},
-    sys::{jobjectArray, jsize},
+    sys::{jbyteArray, jint, jlong, jobjectArray, jsize},
     JNIEnv,
 };
 use std::ops::Deref;
 
+use crate::wallet::{NoteSelectionStrategy, TransactionOptions};
+
+pub(crate) mod client;
 pub(crate) mod exception;
 
 pub(crate) fn java_string_to_rust(env: &JNIEnv<'_>, jstring: JString<'_>) -> String {
@@ -39,3 +42,60 @@ where
     }
     jret
 }
+
+pub(crate) fn rust_string_vec_to_java(env: &JNIEnv<'_>, data: Vec<String>) -> jobjectArray {
+    rust_vec_to_java(
+        env,
+        data,
+        "java/lang/String",
+        |env, s| env.new_string(s),
+        |env| env.new_string(""),
+    )
+}
+
+pub(crate) fn rust_bytes_vec_to_java(env: &JNIEnv<'_>, data: Vec<Vec<u8>>) -> jobjectArray {
+    let jret = env
+        .new_object_array(data.len() as jsize, "[B", JObject::null())
+        .expect("Couldn't create Java array!");
+    for (i, elem) in data.into_iter().enumerate() {
+        let jelem = env
+            .byte_array_from_slice(&elem)
+            .expect("Couldn't create Java byte array!");
+        env.set_object_array_element(jret, i as jsize, JObject::from(jelem))
+            .expect("Couldn't set Java array element!");
+    }
+    jret
+}
+
+pub(crate) fn java_bytes_vec_to_rust(env: &JNIEnv<'_>, array: jobjectArray) -> Vec<Vec<u8>> {
+    let len = env
+        .get_array_length(array)
+        .expect("Couldn't get Java array length!");
+    (0..len)
+        .map(|i| {
+            let jelem = env
+                .get_object_array_element(array, i)
+                .expect("Couldn't get Java array element!");
+            env.convert_byte_array(jelem.into_inner() as jbyteArray)
+                .expect("Couldn't convert Java byte array!")
+        })
+        .collect()
+}
+
+/// Converts the transaction options passed to the JNI entry points. A negative fee or expiry
+/// delta selects the default.
+pub(crate) fn java_transaction_options(
+    fee: jlong,
+    expiry_delta: jint,
+    note_selection: jint,
+    max_inputs: jint,
+) -> Result<TransactionOptions, failure::Error> {
+    let fee = if fee >= 0 { Some(fee) } else { None };
+    let expiry_delta = if expiry_delta >= 0 {
+        Some(expiry_delta as u32)
+    } else {
+        None
+    };
+    let strategy = NoteSelectionStrategy::from_code(note_selection, max_inputs)?;
+    Ok(TransactionOptions::new(fee, expiry_delta)?.with_strategy(strategy))
+}
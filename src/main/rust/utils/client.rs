@@ -0,0 +1,150 @@
+//! A [`BlockchainClient`] that calls back into Kotlin, so that the [`Synchronizer`] can be
+//! driven from JNI with the SDK's own gRPC connection to lightwalletd.
+//!
+//! [`Synchronizer`]: crate::sync::Synchronizer
+
+use failure::{format_err, Error};
+use jni::{
+    errors::Result as JNIResult,
+    objects::{JObject, JValue},
+    sys::{jbyteArray, jint},
+    JNIEnv,
+};
+use protobuf::parse_from_bytes;
+use zcash_client_backend::proto::compact_formats::CompactBlock;
+
+use crate::sync::{BlockchainClient, SyncStatus};
+
+/// Wraps a Kotlin `NativeBlockchainClient`.
+pub(crate) struct JavaBlockchainClient<'a, 'b> {
+    env: &'b JNIEnv<'a>,
+    client: JObject<'a>,
+}
+
+impl<'a, 'b> JavaBlockchainClient<'a, 'b> {
+    pub(crate) fn new(env: &'b JNIEnv<'a>, client: JObject<'a>) -> Self {
+        JavaBlockchainClient { env, client }
+    }
+
+    /// Names the Kotlin method in errors. An exception thrown by it is left pending, so that
+    /// it reaches the caller of the JNI entry point once the sync stops.
+    fn check<T>(method: &str, res: JNIResult<T>) -> Result<T, Error> {
+        res.map_err(|e| format_err!("{} failed: {}", method, e))
+    }
+}
+
+impl<'a, 'b> BlockchainClient for JavaBlockchainClient<'a, 'b> {
+    fn latest_height(&self) -> Result<u32, Error> {
+        let height = Self::check(
+            "getLatestBlockHeight",
+            self.env
+                .call_method(self.client, "getLatestBlockHeight", "()I", &[])
+                .and_then(JValue::i),
+        )?;
+        if height < 0 {
+            return Err(format_err!("Invalid latest block height {}", height));
+        }
+        Ok(height as u32)
+    }
+
+    fn block_range(&self, start: u32, end: u32) -> Result<Vec<CompactBlock>, Error> {
+        let env = self.env;
+        let array = Self::check(
+            "getBlockRange",
+            env.call_method(
+                self.client,
+                "getBlockRange",
+                "(II)[[B",
+                &[JValue::Int(start as jint), JValue::Int(end as jint)],
+            )
+            .and_then(JValue::l),
+        )?;
+
+        // Each element is released as soon as it is read, since a range can hold more
+        // blocks than there are local references available.
+        let len = env
+            .get_array_length(array.into_inner())
+            .map_err(|e| format_err!("{}", e))?;
+        let mut blocks = Vec::with_capacity(len as usize);
+        for i in 0..len {
+            let jblock = env
+                .get_object_array_element(array.into_inner(), i)
+                .map_err(|e| format_err!("{}", e))?;
+            let data = env
+                .convert_byte_array(jblock.into_inner() as jbyteArray)
+                .map_err(|e| format_err!("{}", e))?;
+            env.delete_local_ref(jblock)
+                .map_err(|e| format_err!("{}", e))?;
+            blocks.push(parse_from_bytes::<CompactBlock>(&data)?);
+        }
+        env.delete_local_ref(array)
+            .map_err(|e| format_err!("{}", e))?;
+        Ok(blocks)
+    }
+
+    fn transaction(&self, txid: &[u8]) -> Result<Vec<u8>, Error> {
+        let env = self.env;
+        let jtxid = env
+            .byte_array_from_slice(txid)
+            .map_err(|e| format_err!("{}", e))?;
+        let res = env
+            .call_method(
+                self.client,
+                "fetchTransaction",
+                "([B)[B",
+                &[JValue::Object(JObject::from(jtxid))],
+            )
+            .and_then(JValue::l);
+        env.delete_local_ref(JObject::from(jtxid))
+            .map_err(|e| format_err!("{}", e))?;
+
+        // A missing transaction only means that its memo is missing, and the sync carries on
+        // with further JNI calls, so an exception must not be left pending.
+        let raw = match res {
+            Ok(raw) => raw,
+            Err(e) => {
+                if env.exception_check().unwrap_or(false) {
+                    let _ = env.exception_describe();
+                    let _ = env.exception_clear();
+                }
+                return Err(format_err!("fetchTransaction failed: {}", e));
+            }
+        };
+        if raw.is_null() {
+            return Err(format_err!("Transaction not found"));
+        }
+        let data = env
+            .convert_byte_array(raw.into_inner() as jbyteArray)
+            .map_err(|e| format_err!("{}", e))?;
+        env.delete_local_ref(raw)
+            .map_err(|e| format_err!("{}", e))?;
+        Ok(data)
+    }
+}
+
+/// Passes `status` to a Kotlin `NativeSyncListener` as JSON. Failures are logged rather than
+/// stopping the sync.
+pub(crate) fn report_status(env: &JNIEnv<'_>, listener: JObject<'_>, status: &SyncStatus) {
+    if listener.is_null() {
+        return;
+    }
+    let res = serde_json::to_string(status)
+        .map_err(|e| format_err!("{}", e))
+        .and_then(|json| env.new_string(json).map_err(|e| format_err!("{}", e)))
+        .and_then(|jstatus| {
+            let res = env.call_method(
+                listener,
+                "onSyncStatus",
+                "(Ljava/lang/String;)V",
+                &[JValue::Object(jstatus.into())],
+            );
+            let _ = env.delete_local_ref(jstatus.into());
+            res.map_err(|e| format_err!("{}", e))
+        });
+    if let Err(e) = res {
+        if env.exception_check().unwrap_or(false) {
+            let _ = env.exception_clear();
+        }
+        warn!("Failed to report sync status {:?}: {}", status, e);
+    }
+}
@@ -0,0 +1,459 @@
+//! The wallet operations exposed to Kotlin, as plain Rust functions.
+//!
+//! Each `Java_cash_z_ecc_android_sdk_jni_RustBackend_*` function in the crate root only converts
+//! its arguments and result between Java and Rust types, and calls the function here that does
+//! the work. This lets the operations be tested, and used by other Rust code, without a JVM.
+
+use failure::{format_err, Error};
+use protobuf::parse_from_bytes;
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::path::Path;
+use zcash_client_backend::{
+    encoding::{
+        decode_extended_full_viewing_key, decode_extended_spending_key,
+        encode_extended_full_viewing_key, encode_extended_spending_key, encode_payment_address,
+    },
+    keys::spending_key,
+    proto::compact_formats::CompactBlock,
+};
+use zcash_client_sqlite::{address::RecipientAddress, chain, error::ErrorKind, init, query, scan};
+use zcash_primitives::{
+    consensus::BranchId,
+    note_encryption::Memo,
+    transaction::{components::Amount, Transaction},
+    zip32::{ExtendedFullViewingKey, ExtendedSpendingKey},
+};
+use zcash_proofs::prover::LocalTxProver;
+
+use crate::checkpoint::{init_blocks_table_from_checkpoint, Checkpoint, CheckpointRegistry};
+use crate::consolidate;
+use crate::reorg;
+use crate::sweep;
+use crate::transact::{self, init_fee_table};
+use crate::Network;
+
+pub use crate::consolidate::Consolidation;
+pub use crate::enhance::{enhance_transactions, transactions_to_enhance, EnhancementFailure};
+pub use crate::expiry::{
+    expire_transactions, transactions_to_rebroadcast, unmined_transactions, UnminedTransaction,
+};
+pub use crate::history::{
+    transaction_history, Direction, HistoryEntry, HistoryFilter, HistoryPage, MemoStatus,
+    TransactionStatus,
+};
+pub use crate::inspect::{parse_transaction, parse_transaction_for_wallet};
+pub use crate::offline::{
+    create_unsigned_transaction, record_signed_transaction, sign_transaction, UnsignedTransaction,
+};
+pub use crate::selection::{propose_transfer, NoteSelectionStrategy, Proposal, ProposedNote};
+pub use crate::sweep::{MaxSendable, MAX_SWEEP_INPUTS};
+pub use crate::transact::{
+    TransactionOptions, DEFAULT_EXPIRY_DELTA, MAX_EXPIRY_DELTA, MAX_FEE, MIN_EXPIRY_DELTA,
+};
+
+#[cfg(feature = "mainnet")]
+use zcash_client_backend::constants::mainnet::{
+    COIN_TYPE, HRP_SAPLING_EXTENDED_FULL_VIEWING_KEY, HRP_SAPLING_EXTENDED_SPENDING_KEY,
+    HRP_SAPLING_PAYMENT_ADDRESS,
+};
+#[cfg(not(feature = "mainnet"))]
+use zcash_client_backend::constants::testnet::{
+    COIN_TYPE, HRP_SAPLING_EXTENDED_FULL_VIEWING_KEY, HRP_SAPLING_EXTENDED_SPENDING_KEY,
+    HRP_SAPLING_PAYMENT_ADDRESS,
+};
+
+fn encode_extsks(extsks: &[ExtendedSpendingKey]) -> Vec<String> {
+    extsks
+        .iter()
+        .map(|extsk| encode_extended_spending_key(HRP_SAPLING_EXTENDED_SPENDING_KEY, extsk))
+        .collect()
+}
+
+pub(crate) fn decode_extsk(extsk: &str) -> Result<ExtendedSpendingKey, Error> {
+    match decode_extended_spending_key(HRP_SAPLING_EXTENDED_SPENDING_KEY, extsk) {
+        Ok(Some(extsk)) => Ok(extsk),
+        Ok(None) => Err(format_err!("ExtendedSpendingKey is for the wrong network")),
+        Err(e) => Err(format_err!("Invalid ExtendedSpendingKey: {}", e)),
+    }
+}
+
+pub(crate) fn decode_extfvk(extfvk: &str) -> Result<ExtendedFullViewingKey, Error> {
+    match decode_extended_full_viewing_key(HRP_SAPLING_EXTENDED_FULL_VIEWING_KEY, extfvk) {
+        Ok(Some(extfvk)) => Ok(extfvk),
+        Ok(None) => Err(format_err!(
+            "ExtendedFullViewingKey is for the wrong network"
+        )),
+        Err(e) => Err(format_err!("Invalid ExtendedFullViewingKey: {}", e)),
+    }
+}
+
+pub(crate) fn parse_address(addr: &str) -> Result<RecipientAddress, Error> {
+    RecipientAddress::from_str(addr).ok_or_else(|| format_err!("Address is for the wrong network"))
+}
+
+pub(crate) fn parse_value(value: i64) -> Result<Amount, Error> {
+    let value =
+        Amount::from_i64(value).map_err(|()| format_err!("Invalid amount, out of range"))?;
+    if value.is_negative() {
+        return Err(format_err!("Amount is negative"));
+    }
+    Ok(value)
+}
+
+pub fn init_data_db<P: AsRef<Path>>(db_data: P) -> Result<(), Error> {
+    init::init_data_database(&db_data)
+        .map_err(|e| format_err!("Error while initializing data DB: {}", e))?;
+    init_fee_table(&Connection::open(db_data)?)
+}
+
+/// Adds the first `accounts` accounts derived from `seed` to the data DB, returning their
+/// encoded spending keys.
+pub fn init_accounts_table<P: AsRef<Path>>(
+    db_data: P,
+    seed: &[u8],
+    accounts: u32,
+) -> Result<Vec<String>, Error> {
+    let extsks: Vec<_> = (0..accounts)
+        .map(|account| spending_key(seed, COIN_TYPE, account))
+        .collect();
+    let extfvks: Vec<_> = extsks.iter().map(ExtendedFullViewingKey::from).collect();
+
+    init::init_accounts_table(db_data, &extfvks)
+        .map_err(|e| format_err!("Error while initializing accounts: {}", e))?;
+    Ok(encode_extsks(&extsks))
+}
+
+pub fn derive_extended_spending_keys(seed: &[u8], accounts: u32) -> Vec<String> {
+    let extsks: Vec<_> = (0..accounts)
+        .map(|account| spending_key(seed, COIN_TYPE, account))
+        .collect();
+    encode_extsks(&extsks)
+}
+
+pub fn derive_extended_full_viewing_keys(seed: &[u8], accounts: u32) -> Vec<String> {
+    (0..accounts)
+        .map(|account| {
+            encode_extended_full_viewing_key(
+                HRP_SAPLING_EXTENDED_FULL_VIEWING_KEY,
+                &ExtendedFullViewingKey::from(&spending_key(seed, COIN_TYPE, account)),
+            )
+        })
+        .collect()
+}
+
+pub fn derive_address_from_seed(seed: &[u8], account: u32) -> String {
+    let address = spending_key(seed, COIN_TYPE, account)
+        .default_address()
+        .unwrap()
+        .1;
+    encode_payment_address(HRP_SAPLING_PAYMENT_ADDRESS, &address)
+}
+
+pub fn derive_address_from_viewing_key(extfvk: &str) -> Result<String, Error> {
+    let extfvk = match decode_extended_full_viewing_key(
+        HRP_SAPLING_EXTENDED_FULL_VIEWING_KEY,
+        extfvk,
+    ) {
+        Ok(Some(extfvk)) => extfvk,
+        Ok(None) => {
+            return Err(format_err!("Deriving viewing key from string returned no results. Encoding was valid but type was incorrect."));
+        }
+        Err(e) => {
+            return Err(format_err!(
+                "Error while deriving viewing key from string input: {}",
+                e
+            ));
+        }
+    };
+
+    let address = extfvk.default_address().unwrap().1;
+    Ok(encode_payment_address(
+        HRP_SAPLING_PAYMENT_ADDRESS,
+        &address,
+    ))
+}
+
+pub fn derive_extended_full_viewing_key(extsk: &str) -> Result<String, Error> {
+    let extfvk = match decode_extended_spending_key(HRP_SAPLING_EXTENDED_SPENDING_KEY, extsk) {
+        Ok(Some(extsk)) => ExtendedFullViewingKey::from(&extsk),
+        Ok(None) => {
+            return Err(format_err!("Deriving viewing key from spending key returned no results. Encoding was valid but type was incorrect."));
+        }
+        Err(e) => {
+            return Err(format_err!(
+                "Error while deriving viewing key from spending key: {}",
+                e
+            ));
+        }
+    };
+
+    Ok(encode_extended_full_viewing_key(
+        HRP_SAPLING_EXTENDED_FULL_VIEWING_KEY,
+        &extfvk,
+    ))
+}
+
+/// Initializes the blocks table from a checkpoint given by the caller, after checking it
+/// against the expected height and tree root if they are given.
+pub fn init_blocks_table<P: AsRef<Path>>(
+    db_data: P,
+    checkpoint: &Checkpoint,
+    expected_height: Option<i32>,
+    expected_root: Option<&str>,
+) -> Result<(), Error> {
+    checkpoint.validate()?;
+    checkpoint.check_expected(expected_height, expected_root)?;
+    init_blocks_table_from_checkpoint(db_data, checkpoint)
+}
+
+/// Initializes the blocks table from a serialized checkpoint, returning its height.
+pub fn init_blocks_table_from_checkpoint_data<P: AsRef<Path>>(
+    db_data: P,
+    checkpoint: &[u8],
+) -> Result<i32, Error> {
+    let checkpoint = Checkpoint::read(checkpoint)?;
+    init_blocks_table_from_checkpoint(db_data, &checkpoint).map(|()| checkpoint.height)
+}
+
+/// Initializes the blocks table from the bundled checkpoint closest to `birthday_height`,
+/// returning the checkpoint's height.
+pub fn init_blocks_table_from_birthday<P: AsRef<Path>>(
+    db_data: P,
+    birthday_height: i32,
+) -> Result<i32, Error> {
+    let registry = CheckpointRegistry::bundled()?;
+    let checkpoint = registry
+        .at_or_below_height(birthday_height)
+        .ok_or_else(|| format_err!("No checkpoint found at or below height {}", birthday_height))?;
+    init_blocks_table_from_checkpoint(db_data, checkpoint).map(|()| checkpoint.height)
+}
+
+/// Initializes the blocks table from the bundled checkpoint closest to `birthday_time`,
+/// returning the checkpoint's height.
+pub fn init_blocks_table_from_birthday_time<P: AsRef<Path>>(
+    db_data: P,
+    birthday_time: u32,
+) -> Result<i32, Error> {
+    let registry = CheckpointRegistry::bundled()?;
+    let checkpoint = registry
+        .at_or_before_time(birthday_time)
+        .ok_or_else(|| format_err!("No checkpoint found at or before time {}", birthday_time))?;
+    init_blocks_table_from_checkpoint(db_data, checkpoint).map(|()| checkpoint.height)
+}
+
+pub fn get_address<P: AsRef<Path>>(db_data: P, account: u32) -> Result<String, Error> {
+    query::get_address(db_data, account)
+        .map_err(|e| format_err!("Error while fetching address: {}", e))
+}
+
+pub fn is_valid_shielded_address(addr: &str) -> Result<bool, Error> {
+    match parse_address(addr)? {
+        RecipientAddress::Shielded(_) => Ok(true),
+        RecipientAddress::Transparent(_) => Ok(false),
+    }
+}
+
+pub fn is_valid_transparent_address(addr: &str) -> Result<bool, Error> {
+    match parse_address(addr)? {
+        RecipientAddress::Shielded(_) => Ok(false),
+        RecipientAddress::Transparent(_) => Ok(true),
+    }
+}
+
+/// Returns the balance of an account in zatoshis.
+pub fn get_balance<P: AsRef<Path>>(db_data: P, account: u32) -> Result<i64, Error> {
+    query::get_balance(db_data, account)
+        .map(i64::from)
+        .map_err(|e| format_err!("Error while fetching balance: {}", e))
+}
+
+/// Returns the balance of an account in zatoshis that is spendable now.
+pub fn get_verified_balance<P: AsRef<Path>>(db_data: P, account: u32) -> Result<i64, Error> {
+    query::get_verified_balance(db_data, account)
+        .map(i64::from)
+        .map_err(|e| format_err!("Error while fetching verified balance: {}", e))
+}
+
+/// Returns the memo of a received note, or an empty string if it has none.
+pub fn get_received_memo_as_utf8<P: AsRef<Path>>(
+    db_data: P,
+    id_note: i64,
+) -> Result<String, Error> {
+    query::get_received_memo_as_utf8(db_data, id_note)
+        .map(Option::unwrap_or_default)
+        .map_err(|e| format_err!("Error while fetching memo: {}", e))
+}
+
+/// Returns the memo of a sent note, or an empty string if it has none.
+pub fn get_sent_memo_as_utf8<P: AsRef<Path>>(db_data: P, id_note: i64) -> Result<String, Error> {
+    query::get_sent_memo_as_utf8(db_data, id_note)
+        .map(Option::unwrap_or_default)
+        .map_err(|e| format_err!("Error while fetching memo: {}", e))
+}
+
+/// Returns the height of the highest invalid cached block, or -1 if the cache is consistent
+/// with the scanned chain.
+pub fn validate_combined_chain<P: AsRef<Path>>(db_cache: P, db_data: P) -> Result<i32, Error> {
+    if let Err(e) = chain::validate_combined_chain(&db_cache, &db_data) {
+        match e.kind() {
+            ErrorKind::InvalidChain(upper_bound, _) => Ok(*upper_bound),
+            _ => Err(format_err!("Error while validating chain: {}", e)),
+        }
+    } else {
+        // All blocks are valid, so "highest invalid block height" is below genesis.
+        Ok(-1)
+    }
+}
+
+pub fn rewind_to_height<P: AsRef<Path>>(db_data: P, height: i32) -> Result<(), Error> {
+    chain::rewind_to_height(db_data, height)
+        .map_err(|e| format_err!("Error while rewinding data DB to height {}: {}", height, e))
+}
+
+/// Validates the combined chain and, if a reorg is detected, rewinds both databases to the
+/// fork point in one transaction, returning the height rewound to or -1 if the chain is valid.
+///
+/// `new_blocks` are serialized `CompactBlock`s of the new chain that are not in the cache,
+/// typically those at and below the last scanned height. The fork point is found by walking
+/// down the new chain through the cache and these blocks, comparing hashes with the scanned
+/// blocks; if the new chain runs out before reaching it, the rewind goes `max_rewind` blocks
+/// below the invalid block.
+pub fn handle_chain_reorg<P: AsRef<Path>>(
+    db_cache: P,
+    db_data: P,
+    max_rewind: u32,
+    new_blocks: &[Vec<u8>],
+) -> Result<i32, Error> {
+    let new_blocks = new_blocks
+        .iter()
+        .map(|data| {
+            let block = parse_from_bytes::<CompactBlock>(data)
+                .map_err(|e| format_err!("Invalid CompactBlock: {}", e))?;
+            Ok((block.height, block))
+        })
+        .collect::<Result<HashMap<_, _>, Error>>()?;
+
+    reorg::handle_chain_reorg(db_cache, db_data, max_rewind, |height| {
+        Ok(new_blocks.get(&u64::from(height)).cloned())
+    })
+    .map_err(|e| format_err!("Error while handling chain reorg: {}", e))
+}
+
+/// Scans cached blocks, at most `limit` of them if a limit is given.
+pub fn scan_blocks<P: AsRef<Path>>(
+    db_cache: P,
+    db_data: P,
+    limit: Option<i32>,
+) -> Result<(), Error> {
+    scan::scan_cached_blocks(db_cache, db_data, limit)
+        .map_err(|e| format_err!("Error while scanning blocks: {}", e))
+}
+
+pub fn decrypt_and_store_transaction<P: AsRef<Path>>(db_data: P, tx: &[u8]) -> Result<(), Error> {
+    let tx = Transaction::read(tx)?;
+    scan::decrypt_and_store_transaction(db_data, &tx)
+        .map_err(|e| format_err!("Error while decrypting transaction: {}", e))
+}
+
+/// Creates a transaction paying `value` zatoshis to `to`, returning its row ID.
+#[allow(clippy::too_many_arguments)]
+pub fn create_to_address<P: AsRef<Path>>(
+    db_data: P,
+    consensus_branch_id: u32,
+    account: u32,
+    extsk: &str,
+    to: &str,
+    value: i64,
+    memo: &[u8],
+    options: TransactionOptions,
+    spend_params: &Path,
+    output_params: &Path,
+) -> Result<i64, Error> {
+    let value = parse_value(value)?;
+    let extsk = decode_extsk(extsk)?;
+    let to = parse_address(to)?;
+    let memo = Memo::from_bytes(memo);
+    let prover = LocalTxProver::new(spend_params, output_params);
+    let branch_id = BranchId::try_from(consensus_branch_id)
+        .map_err(|e| format_err!("Invalid consensus branch id: {}", e))?;
+
+    transact::create_to_address(
+        db_data, branch_id, prover, account, &extsk, &to, value, memo, options,
+    )
+    .map_err(|e| format_err!("Error while creating transaction: {}", e))
+}
+
+/// Returns how much sending the account's verified notes to `to` would pay after the fee,
+/// spending at most `max_inputs` of them.
+pub fn max_sendable<P: AsRef<Path>>(
+    db_data: P,
+    account: u32,
+    to: &str,
+    max_inputs: Option<usize>,
+    options: TransactionOptions,
+) -> Result<MaxSendable, Error> {
+    let to = parse_address(to)?;
+
+    sweep::max_sendable(db_data, account, &to, max_inputs, options)
+        .map_err(|e| format_err!("Error while computing the maximum sendable amount: {}", e))
+}
+
+/// Creates a transaction sending everything [`max_sendable`] reports to `to`, with no change
+/// output, returning its row ID.
+#[allow(clippy::too_many_arguments)]
+pub fn sweep_to_address<P: AsRef<Path>>(
+    db_data: P,
+    consensus_branch_id: u32,
+    account: u32,
+    extsk: &str,
+    to: &str,
+    memo: &[u8],
+    max_inputs: Option<usize>,
+    options: TransactionOptions,
+    spend_params: &Path,
+    output_params: &Path,
+) -> Result<i64, Error> {
+    let extsk = decode_extsk(extsk)?;
+    let to = parse_address(to)?;
+    let memo = Memo::from_bytes(memo);
+    let prover = LocalTxProver::new(spend_params, output_params);
+    let branch_id = BranchId::try_from(consensus_branch_id)
+        .map_err(|e| format_err!("Invalid consensus branch id: {}", e))?;
+
+    sweep::sweep_to_address(
+        db_data, branch_id, prover, account, &extsk, &to, memo, max_inputs, options,
+    )
+    .map_err(|e| format_err!("Error while creating transaction: {}", e))
+}
+
+/// Merges up to `max_inputs` of the account's smallest verified notes into one note at its
+/// own address, reporting how many notes it has left.
+#[allow(clippy::too_many_arguments)]
+pub fn consolidate_notes<P: AsRef<Path>>(
+    db_data: P,
+    consensus_branch_id: u32,
+    account: u32,
+    extsk: &str,
+    max_inputs: usize,
+    options: TransactionOptions,
+    spend_params: &Path,
+    output_params: &Path,
+) -> Result<Consolidation, Error> {
+    let extsk = decode_extsk(extsk)?;
+    let prover = LocalTxProver::new(spend_params, output_params);
+    let branch_id = BranchId::try_from(consensus_branch_id)
+        .map_err(|e| format_err!("Invalid consensus branch id: {}", e))?;
+
+    consolidate::consolidate_notes(
+        db_data, branch_id, prover, account, &extsk, max_inputs, options,
+    )
+    .map_err(|e| format_err!("Error while consolidating notes: {}", e))
+}
+
+pub fn branch_id_for_height(height: u32) -> u32 {
+    let branch = BranchId::for_height::<Network>(height);
+    debug!("For height {} found consensus branch {:?}", height, branch);
+    u32::from(branch)
+}
@@ -0,0 +1,202 @@
+//! A smoke test of the JNI entry points inside a real JVM.
+//!
+//! The JVM is started through the `jni` invocation API, loads the built cdylib the way the
+//! SDK does, and the entry points are then called with real Java arguments. Run with
+//! `cargo test --features fixtures,jvm-tests`, with `libjvm` on the library path.
+
+use jni::{
+    objects::{JClass, JObject, JString, JValue},
+    sys::{jobjectArray, jsize, JNI_TRUE},
+    InitArgsBuilder, JNIEnv, JNIVersion, JavaVM,
+};
+use protobuf::Message;
+use rusqlite::{Connection, NO_PARAMS};
+use std::env::{self, consts};
+use std::path::PathBuf;
+use std::sync::Once;
+use zcash_client_sqlite::scan::scan_cached_blocks;
+use zcashwalletsdk::fixtures::{wallet_at_checkpoint, write_cache_db};
+use zcashwalletsdk::*;
+
+const SEED: [u8; 32] = [7; 32];
+
+/// Finds the cdylib next to the test binary's `deps` directory.
+fn cdylib_path() -> PathBuf {
+    let exe = env::current_exe().unwrap();
+    exe.parent()
+        .and_then(|deps| deps.parent())
+        .unwrap()
+        .join(format!(
+            "{}zcashwalletsdk{}",
+            consts::DLL_PREFIX,
+            consts::DLL_SUFFIX
+        ))
+}
+
+/// Makes a second handle to the environment, to pass by value to an entry point.
+fn env_handle<'a>(env: &JNIEnv<'a>) -> JNIEnv<'a> {
+    unsafe { JNIEnv::from_raw(env.get_native_interface()).unwrap() }
+}
+
+fn rust_string(env: &JNIEnv<'_>, s: JString<'_>) -> String {
+    env.get_string(s).unwrap().into()
+}
+
+fn string_array(env: &JNIEnv<'_>, array: jobjectArray) -> Vec<String> {
+    let len = env.get_array_length(array).unwrap();
+    (0..len)
+        .map(|i| rust_string(env, env.get_object_array_element(array, i).unwrap().into()))
+        .collect()
+}
+
+fn byte_arrays(env: &JNIEnv<'_>, data: &[Vec<u8>]) -> jobjectArray {
+    let array = env
+        .new_object_array(data.len() as jsize, "[B", JObject::null())
+        .unwrap();
+    for (i, elem) in data.iter().enumerate() {
+        let jelem = env.byte_array_from_slice(elem).unwrap();
+        env.set_object_array_element(array, i as jsize, JObject::from(jelem))
+            .unwrap();
+    }
+    array
+}
+
+/// Returns the JVM shared by the tests, with the cdylib loaded into it. A process can only
+/// ever create one JVM.
+fn jvm() -> &'static JavaVM {
+    static START: Once = Once::new();
+    static mut JVM: Option<JavaVM> = None;
+    START.call_once(|| {
+        let args = InitArgsBuilder::new()
+            .version(JNIVersion::V8)
+            .option("-Xcheck:jni")
+            .build()
+            .unwrap();
+        let jvm = JavaVM::new(args).unwrap();
+        {
+            let env = jvm.attach_current_thread().unwrap();
+            let path = cdylib_path();
+            assert!(path.exists(), "{} has not been built", path.display());
+            let jpath = env.new_string(path.to_str().unwrap()).unwrap();
+            env.call_static_method(
+                "java/lang/System",
+                "load",
+                "(Ljava/lang/String;)V",
+                &[JValue::Object(jpath.into())],
+            )
+            .unwrap();
+        }
+        unsafe { JVM = Some(jvm) };
+    });
+    unsafe { JVM.as_ref().unwrap() }
+}
+
+#[test]
+fn entry_points_work_in_a_jvm() {
+    let env = jvm().attach_current_thread().unwrap();
+
+    let class = JClass::from(JObject::null());
+    let dir = tempfile::tempdir().unwrap();
+    let db_data = dir.path().join("data.db");
+    let jdb_data = || env.new_string(db_data.to_str().unwrap()).unwrap();
+    let jseed = || env.byte_array_from_slice(&SEED).unwrap();
+
+    unsafe {
+        assert_eq!(
+            Java_cash_z_ecc_android_sdk_jni_RustBackend_initDataDb(
+                env_handle(&env),
+                class,
+                jdb_data()
+            ),
+            JNI_TRUE
+        );
+
+        let extsks = Java_cash_z_ecc_android_sdk_jni_RustBackend_initAccountsTable(
+            env_handle(&env),
+            class,
+            jdb_data(),
+            jseed(),
+            1,
+        );
+        assert_eq!(
+            string_array(&env, extsks),
+            wallet::derive_extended_spending_keys(&SEED, 1)
+        );
+
+        let address = Java_cash_z_ecc_android_sdk_jni_RustBackend_getAddress(
+            env_handle(&env),
+            class,
+            jdb_data(),
+            0,
+        );
+        assert_eq!(
+            rust_string(&env, address.into()),
+            wallet::derive_address_from_seed(&SEED, 0)
+        );
+
+        // Errors are thrown as Java exceptions.
+        let address = Java_cash_z_ecc_android_sdk_jni_RustBackend_deriveAddressFromSeed(
+            env_handle(&env),
+            class,
+            jseed(),
+            -1,
+        );
+        assert!(address.is_null());
+        assert!(env.exception_check().unwrap());
+        env.exception_clear().unwrap();
+    }
+}
+
+#[test]
+fn chain_reorg_is_handled_in_a_jvm() {
+    let env = jvm().attach_current_thread().unwrap();
+    let class = JClass::from(JObject::null());
+
+    let mut wallet = wallet_at_checkpoint().unwrap();
+    wallet.chain.mine_blocks(10);
+    let mut fork = wallet.chain.fork(1);
+    wallet.chain.receive(&wallet.extfvk, 10_000).unwrap();
+    wallet.chain.mine_blocks(3);
+    write_cache_db(&wallet.db_cache, wallet.chain.blocks()).unwrap();
+    scan_cached_blocks(&wallet.db_cache, &wallet.db_data, None).unwrap();
+    let tip = wallet.chain.tip_height().unwrap();
+
+    // The fork replaces the last 3 scanned blocks. Only the blocks above the scanned tip
+    // were downloaded into the cache, and the rest are passed in.
+    fork.mine_blocks(5);
+    let (new_blocks, above_tip): (Vec<_>, Vec<_>) = fork
+        .blocks()
+        .iter()
+        .filter(|block| block.height > tip - 3)
+        .cloned()
+        .partition(|block| block.height <= tip);
+    write_cache_db(&wallet.db_cache, &above_tip).unwrap();
+    let new_blocks: Vec<_> = new_blocks
+        .iter()
+        .map(|block| block.write_to_bytes().unwrap())
+        .collect();
+
+    let jdb_cache = env.new_string(wallet.db_cache.to_str().unwrap()).unwrap();
+    let jdb_data = env.new_string(wallet.db_data.to_str().unwrap()).unwrap();
+    let fork_height = unsafe {
+        Java_cash_z_ecc_android_sdk_jni_RustBackend_handleChainReorg(
+            env_handle(&env),
+            class,
+            jdb_cache,
+            jdb_data,
+            100,
+            byte_arrays(&env, &new_blocks),
+        )
+    };
+    assert!(!env.exception_check().unwrap());
+
+    // The fork point is found well short of maxRewind.
+    assert_eq!(u64::from(fork_height as u32), tip - 3);
+    let scanned_tip: i64 = Connection::open(&wallet.db_data)
+        .unwrap()
+        .query_row("SELECT MAX(height) FROM blocks", NO_PARAMS, |row| {
+            row.get(0)
+        })
+        .unwrap();
+    assert_eq!(scanned_tip, i64::from(fork_height));
+}
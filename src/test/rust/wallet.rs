@@ -0,0 +1,637 @@
+//! Integration tests for the wallet operations behind the JNI entry points, run against
+//! temporary SQLite databases and synthetic chains.
+//!
+//! Run with `cargo test --features fixtures`. Transaction creation also needs the Sapling
+//! parameters, which are looked for in `$ZCASH_PARAMS` or `~/.zcash-params`, so those tests
+//! are ignored unless run with `--ignored`.
+
+use rusqlite::Connection;
+use std::env;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+use zcash_client_backend::{encoding::decode_extended_full_viewing_key, keys::spending_key};
+use zcash_primitives::{transaction::Transaction, zip32::ExtendedFullViewingKey};
+use zcashwalletsdk::{
+    checkpoint::CheckpointRegistry,
+    fixtures::{write_cache_db, ChainBuilder},
+    wallet::{self, NoteSelectionStrategy, TransactionOptions},
+};
+
+#[cfg(feature = "mainnet")]
+use zcash_client_backend::constants::mainnet::{COIN_TYPE, HRP_SAPLING_EXTENDED_FULL_VIEWING_KEY};
+#[cfg(not(feature = "mainnet"))]
+use zcash_client_backend::constants::testnet::{COIN_TYPE, HRP_SAPLING_EXTENDED_FULL_VIEWING_KEY};
+
+const SEED: [u8; 32] = [7; 32];
+
+struct TestWallet {
+    _dir: TempDir,
+    db_cache: PathBuf,
+    db_data: PathBuf,
+    extsks: Vec<String>,
+    chain: ChainBuilder,
+}
+
+impl TestWallet {
+    /// Creates a wallet with two accounts, born at the latest bundled checkpoint.
+    fn new() -> Self {
+        let dir = tempfile::tempdir().unwrap();
+        let db_cache = dir.path().join("cache.db");
+        let db_data = dir.path().join("data.db");
+
+        wallet::init_data_db(&db_data).unwrap();
+        let extsks = wallet::init_accounts_table(&db_data, &SEED, 2).unwrap();
+        let registry = CheckpointRegistry::bundled().unwrap();
+        let checkpoint = registry.checkpoints().last().unwrap();
+        wallet::init_blocks_table(&db_data, checkpoint, None, None).unwrap();
+        let chain = ChainBuilder::from_checkpoint(checkpoint).unwrap();
+
+        TestWallet {
+            _dir: dir,
+            db_cache,
+            db_data,
+            extsks,
+            chain,
+        }
+    }
+
+    fn extfvk(&self, account: u32) -> ExtendedFullViewingKey {
+        ExtendedFullViewingKey::from(&spending_key(&SEED, COIN_TYPE, account))
+    }
+
+    /// Caches the blocks mined so far and scans them.
+    fn scan(&self) {
+        write_cache_db(&self.db_cache, self.chain.blocks()).unwrap();
+        wallet::scan_blocks(&self.db_cache, &self.db_data, None).unwrap();
+    }
+
+    fn balance(&self, account: u32) -> i64 {
+        wallet::get_balance(&self.db_data, account).unwrap()
+    }
+}
+
+#[test]
+fn accounts_match_derived_keys() {
+    let wallet = TestWallet::new();
+    assert_eq!(
+        wallet.extsks,
+        wallet::derive_extended_spending_keys(&SEED, 2)
+    );
+
+    let extfvks = wallet::derive_extended_full_viewing_keys(&SEED, 2);
+    for account in 0..2 {
+        let extfvk = &extfvks[account as usize];
+        assert_eq!(
+            &wallet::derive_extended_full_viewing_key(&wallet.extsks[account as usize]).unwrap(),
+            extfvk
+        );
+        assert!(
+            decode_extended_full_viewing_key(HRP_SAPLING_EXTENDED_FULL_VIEWING_KEY, extfvk)
+                .unwrap()
+                .is_some()
+        );
+
+        let address = wallet::get_address(&wallet.db_data, account).unwrap();
+        assert_eq!(wallet::derive_address_from_seed(&SEED, account), address);
+        assert_eq!(
+            wallet::derive_address_from_viewing_key(extfvk).unwrap(),
+            address
+        );
+    }
+    assert_ne!(extfvks[0], extfvks[1]);
+}
+
+#[test]
+fn invalid_keys_are_rejected() {
+    let extfvk = &wallet::derive_extended_full_viewing_keys(&SEED, 1)[0];
+    let extsk = &wallet::derive_extended_spending_keys(&SEED, 1)[0];
+
+    assert!(wallet::derive_extended_full_viewing_key(extfvk).is_err());
+    assert!(wallet::derive_address_from_viewing_key(extsk).is_err());
+    assert!(wallet::derive_address_from_viewing_key("not a key").is_err());
+}
+
+#[test]
+fn addresses_are_validated() {
+    let address = wallet::derive_address_from_seed(&SEED, 0);
+    assert!(wallet::is_valid_shielded_address(&address).unwrap());
+    assert!(!wallet::is_valid_transparent_address(&address).unwrap());
+
+    assert!(wallet::is_valid_shielded_address("not an address").is_err());
+    assert!(wallet::is_valid_transparent_address("").is_err());
+}
+
+#[test]
+fn blocks_table_can_only_be_initialized_once() {
+    let wallet = TestWallet::new();
+    let registry = CheckpointRegistry::bundled().unwrap();
+    let checkpoint = registry.checkpoints().last().unwrap();
+
+    assert!(wallet::init_blocks_table(&wallet.db_data, checkpoint, None, None).is_err());
+    assert!(wallet::init_blocks_table_from_birthday(&wallet.db_data, checkpoint.height).is_err());
+}
+
+#[test]
+fn blocks_table_is_initialized_from_birthday() {
+    let dir = tempfile::tempdir().unwrap();
+    let db_data = dir.path().join("data.db");
+    wallet::init_data_db(&db_data).unwrap();
+
+    let registry = CheckpointRegistry::bundled().unwrap();
+    let checkpoint = registry.checkpoints().last().unwrap();
+    assert_eq!(
+        wallet::init_blocks_table_from_birthday(&db_data, checkpoint.height + 1000).unwrap(),
+        checkpoint.height
+    );
+}
+
+#[test]
+fn scanning_tracks_balances() {
+    let mut wallet = TestWallet::new();
+    let (extfvk0, extfvk1) = (wallet.extfvk(0), wallet.extfvk(1));
+
+    let note = wallet.chain.receive(&extfvk0, 100_000).unwrap();
+    wallet.chain.receive(&extfvk1, 20_000).unwrap();
+    wallet.chain.mine_blocks(5);
+    wallet.scan();
+    assert_eq!(wallet.balance(0), 100_000);
+    assert_eq!(wallet.balance(1), 20_000);
+    // Received notes are not spendable until they have enough confirmations.
+    assert_eq!(wallet::get_verified_balance(&wallet.db_data, 0).unwrap(), 0);
+
+    wallet.chain.mine_blocks(10);
+    wallet.scan();
+    assert_eq!(
+        wallet::get_verified_balance(&wallet.db_data, 0).unwrap(),
+        100_000
+    );
+
+    wallet.chain.spend(&note.nf);
+    wallet.chain.mine();
+    wallet.scan();
+    assert_eq!(wallet.balance(0), 0);
+    assert_eq!(wallet.balance(1), 20_000);
+}
+
+#[test]
+fn scanning_can_be_batched() {
+    let mut wallet = TestWallet::new();
+    let extfvk = wallet.extfvk(0);
+    wallet.chain.mine_blocks(10);
+    wallet.chain.receive(&extfvk, 5_000).unwrap();
+    wallet.chain.mine_blocks(10);
+    write_cache_db(&wallet.db_cache, wallet.chain.blocks()).unwrap();
+
+    wallet::scan_blocks(&wallet.db_cache, &wallet.db_data, Some(10)).unwrap();
+    assert_eq!(wallet.balance(0), 0);
+    wallet::scan_blocks(&wallet.db_cache, &wallet.db_data, Some(10)).unwrap();
+    assert_eq!(wallet.balance(0), 5_000);
+}
+
+#[test]
+fn rewinds_undo_scanned_blocks() {
+    let mut wallet = TestWallet::new();
+    let extfvk = wallet.extfvk(0);
+    wallet.chain.mine_blocks(5);
+    let note = wallet.chain.receive(&extfvk, 70_000).unwrap();
+    wallet.chain.mine_blocks(5);
+    wallet.scan();
+    assert_eq!(wallet.balance(0), 70_000);
+    assert_eq!(
+        wallet::validate_combined_chain(&wallet.db_cache, &wallet.db_data).unwrap(),
+        -1
+    );
+
+    wallet::rewind_to_height(&wallet.db_data, note.height as i32 - 1).unwrap();
+    assert_eq!(wallet.balance(0), 0);
+
+    // Rescanning the cached blocks finds the note again.
+    wallet::scan_blocks(&wallet.db_cache, &wallet.db_data, None).unwrap();
+    assert_eq!(wallet.balance(0), 70_000);
+}
+
+#[test]
+fn forks_are_reported_by_validation() {
+    let mut wallet = TestWallet::new();
+    wallet.chain.mine_blocks(10);
+    let mut fork = wallet.chain.fork(1);
+    wallet.chain.mine_blocks(5);
+    wallet.scan();
+
+    fork.mine_blocks(8);
+    write_cache_db(&wallet.db_cache, fork.blocks()).unwrap();
+    let invalid = wallet::validate_combined_chain(&wallet.db_cache, &wallet.db_data).unwrap();
+    assert!(invalid > 0);
+
+    let fork_point = wallet.chain.blocks()[9].height as i32;
+    wallet::rewind_to_height(&wallet.db_data, fork_point).unwrap();
+    wallet::scan_blocks(&wallet.db_cache, &wallet.db_data, None).unwrap();
+    assert_eq!(
+        wallet::validate_combined_chain(&wallet.db_cache, &wallet.db_data).unwrap(),
+        -1
+    );
+}
+
+#[test]
+fn transfers_are_proposed_with_the_chosen_strategy() {
+    let mut wallet = TestWallet::new();
+    let extfvk = wallet.extfvk(0);
+    for value in &[3_000, 70_000, 1_000, 40_000] {
+        wallet.chain.receive(&extfvk, *value).unwrap();
+    }
+    wallet.chain.mine_blocks(11);
+    wallet.scan();
+
+    let propose = |strategy| {
+        let options = TransactionOptions::default().with_strategy(strategy);
+        let proposal = wallet::propose_transfer(&wallet.db_data, 0, 30_000, options).unwrap();
+        assert_eq!(proposal.strategy, strategy);
+        let values: Vec<u64> = proposal.notes.iter().map(|note| note.value).collect();
+        let total: u64 = values.iter().sum();
+        assert_eq!(proposal.change, total as i64 - 30_000 - proposal.fee);
+        values
+    };
+
+    assert_eq!(
+        propose(NoteSelectionStrategy::OldestFirst),
+        vec![3_000, 70_000]
+    );
+    assert_eq!(propose(NoteSelectionStrategy::LargestFirst), vec![70_000]);
+    assert_eq!(propose(NoteSelectionStrategy::MinimizeChange), vec![40_000]);
+    assert_eq!(
+        propose(NoteSelectionStrategy::ConsolidateDust { max_inputs: 3 }),
+        vec![3_000, 70_000, 1_000]
+    );
+
+    let options = TransactionOptions::default();
+    assert!(wallet::propose_transfer(&wallet.db_data, 0, 200_000, options).is_err());
+    // Proposing does not spend anything.
+    assert_eq!(wallet.balance(0), 114_000);
+}
+
+fn sapling_params() -> Option<(PathBuf, PathBuf)> {
+    let dir = match env::var_os("ZCASH_PARAMS") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".zcash-params"),
+    };
+    let spend = dir.join("sapling-spend.params");
+    let output = dir.join("sapling-output.params");
+    if spend.exists() && output.exists() {
+        Some((spend, output))
+    } else {
+        None
+    }
+}
+
+#[test]
+#[ignore = "needs the Sapling parameters"]
+fn transactions_can_be_created() {
+    let (spend_params, output_params) = sapling_params().expect("Sapling parameters not found");
+
+    let mut wallet = TestWallet::new();
+    let extfvk = wallet.extfvk(0);
+    wallet.chain.receive(&extfvk, 10_000).unwrap();
+    wallet.chain.receive(&extfvk, 200_000).unwrap();
+    wallet.chain.mine_blocks(11);
+    wallet.scan();
+
+    let to = wallet::derive_address_from_seed(&SEED, 1);
+    let target = wallet.chain.tip_height().unwrap() as u32 + 1;
+    let create = |value, options| {
+        wallet::create_to_address(
+            &wallet.db_data,
+            wallet::branch_id_for_height(target),
+            0,
+            &wallet.extsks[0],
+            &to,
+            value,
+            b"hello",
+            options,
+            Path::new(&spend_params),
+            Path::new(&output_params),
+        )
+    };
+
+    assert!(create(1_000_000, TransactionOptions::default()).is_err());
+    let options = TransactionOptions::new(Some(20_000), Some(100))
+        .unwrap()
+        .with_strategy(NoteSelectionStrategy::LargestFirst);
+    let id_tx = create(50_000, options).unwrap();
+    assert!(id_tx > 0);
+    // Only the large note was spent, and the change is not confirmed yet.
+    assert_eq!(wallet.balance(0), 10_000);
+    assert_eq!(
+        wallet::get_verified_balance(&wallet.db_data, 0).unwrap(),
+        10_000
+    );
+
+    let unmined = wallet::unmined_transactions(&wallet.db_data, target).unwrap();
+    assert_eq!(unmined.len(), 1);
+    assert_eq!(unmined[0].id_tx, id_tx);
+    assert_eq!(unmined[0].expiry_height, target + 100);
+    let fee: i64 = Connection::open(&wallet.db_data)
+        .unwrap()
+        .query_row(
+            "SELECT fee FROM sent_transaction_fees WHERE id_tx = ?",
+            &[id_tx],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(fee, 20_000);
+}
+
+#[test]
+#[ignore = "needs the Sapling parameters"]
+fn transactions_can_be_signed_offline() {
+    let (spend_params, output_params) = sapling_params().expect("Sapling parameters not found");
+
+    let mut wallet = TestWallet::new();
+    let extfvk = wallet.extfvk(0);
+    wallet.chain.receive(&extfvk, 200_000).unwrap();
+    wallet.chain.mine_blocks(11);
+    wallet.scan();
+
+    let to = wallet::derive_address_from_seed(&SEED, 1);
+    let target = wallet.chain.tip_height().unwrap() as u32 + 1;
+    let unsigned = wallet::create_unsigned_transaction(
+        &wallet.db_data,
+        wallet::branch_id_for_height(target),
+        0,
+        &to,
+        50_000,
+        b"offline",
+        TransactionOptions::default(),
+    )
+    .unwrap();
+    // Nothing is spent until the signed transaction is recorded.
+    assert_eq!(wallet.balance(0), 200_000);
+
+    // The signing device only sees the serialized package.
+    let unsigned = wallet::UnsignedTransaction::from_json(&unsigned.to_json().unwrap()).unwrap();
+    assert!(
+        wallet::sign_transaction(&unsigned, &wallet.extsks[1], &spend_params, &output_params)
+            .is_err()
+    );
+    let raw = wallet::sign_transaction(&unsigned, &wallet.extsks[0], &spend_params, &output_params)
+        .unwrap();
+
+    let record = |raw: &[u8]| {
+        wallet::record_signed_transaction(
+            &wallet.db_data,
+            &unsigned,
+            raw,
+            &spend_params,
+            &output_params,
+        )
+    };
+
+    // A transaction whose proof or signature has been tampered with is not recorded.
+    let tx = Transaction::read(&raw[..]).unwrap();
+    let mut spend_auth_sig = vec![];
+    tx.shielded_spends[0]
+        .spend_auth_sig
+        .unwrap()
+        .write(&mut spend_auth_sig)
+        .unwrap();
+    for field in &[&tx.shielded_spends[0].zkproof[..], &spend_auth_sig[..]] {
+        let offset = raw
+            .windows(field.len())
+            .position(|window| window == *field)
+            .unwrap();
+        let mut tampered = raw.clone();
+        tampered[offset + 10] ^= 1;
+        assert!(record(&tampered).is_err());
+        assert_eq!(wallet.balance(0), 200_000);
+    }
+
+    let id_tx = record(&raw).unwrap();
+    assert_eq!(wallet.balance(0), 0);
+    let unmined = wallet::unmined_transactions(&wallet.db_data, target).unwrap();
+    assert_eq!(unmined.len(), 1);
+    assert_eq!(unmined[0].id_tx, id_tx);
+    // The same transaction cannot be recorded twice.
+    assert!(record(&raw).is_err());
+}
+
+#[test]
+#[ignore = "needs the Sapling parameters"]
+fn small_notes_can_be_consolidated() {
+    let (spend_params, output_params) = sapling_params().expect("Sapling parameters not found");
+
+    let mut wallet = TestWallet::new();
+    let extfvk = wallet.extfvk(0);
+    for value in &[500_000, 3_000, 1_000, 2_000] {
+        wallet.chain.receive(&extfvk, *value).unwrap();
+    }
+    wallet.chain.mine_blocks(11);
+    wallet.scan();
+
+    let target = wallet.chain.tip_height().unwrap() as u32 + 1;
+    let consolidate = |max_inputs| {
+        wallet::consolidate_notes(
+            &wallet.db_data,
+            wallet::branch_id_for_height(target),
+            0,
+            &wallet.extsks[0],
+            max_inputs,
+            TransactionOptions::new(Some(1_000), None).unwrap(),
+            &spend_params,
+            &output_params,
+        )
+    };
+
+    assert!(consolidate(1).is_err());
+    let consolidation = consolidate(3).unwrap();
+    assert!(consolidation.id_tx.is_some());
+    assert_eq!(consolidation.merged_notes, 3);
+    assert_eq!(consolidation.value, 5_000);
+    assert_eq!(consolidation.remaining_notes, 1);
+    assert_eq!(wallet.balance(0), 500_000);
+
+    // A single spendable note is left alone.
+    let consolidation = consolidate(3).unwrap();
+    assert_eq!(consolidation.id_tx, None);
+    assert_eq!(consolidation.remaining_notes, 1);
+}
+
+#[test]
+fn max_sendable_respects_fee_and_input_limit() {
+    let mut wallet = TestWallet::new();
+    let extfvk = wallet.extfvk(0);
+    for value in &[50_000, 3_000, 20_000] {
+        wallet.chain.receive(&extfvk, *value).unwrap();
+    }
+    wallet.chain.mine_blocks(11);
+    // Unverified notes are not counted.
+    wallet.chain.receive(&extfvk, 100_000).unwrap();
+    wallet.chain.mine_blocks(1);
+    wallet.scan();
+
+    let to = wallet::derive_address_from_seed(&SEED, 1);
+    let options = TransactionOptions::new(Some(1_000), None).unwrap();
+    let max = wallet::max_sendable(&wallet.db_data, 0, &to, None, options).unwrap();
+    assert_eq!(max.recipient, "shielded");
+    assert_eq!(max.value, 72_000);
+    assert_eq!(max.fee, 1_000);
+    assert_eq!((max.notes, max.excluded_notes), (3, 0));
+
+    // The largest notes are kept when the inputs are limited.
+    let max = wallet::max_sendable(&wallet.db_data, 0, &to, Some(2), options).unwrap();
+    assert_eq!(max.value, 69_000);
+    assert_eq!((max.notes, max.excluded_notes), (2, 1));
+
+    let max = wallet::max_sendable(&wallet.db_data, 1, &to, None, options).unwrap();
+    assert_eq!((max.value, max.notes), (0, 0));
+    assert!(wallet::max_sendable(&wallet.db_data, 0, "not an address", None, options).is_err());
+}
+
+#[test]
+#[ignore = "needs the Sapling parameters"]
+fn balances_can_be_swept() {
+    let (spend_params, output_params) = sapling_params().expect("Sapling parameters not found");
+
+    let mut wallet = TestWallet::new();
+    let extfvk = wallet.extfvk(0);
+    wallet.chain.receive(&extfvk, 40_000).unwrap();
+    wallet.chain.receive(&extfvk, 25_000).unwrap();
+    wallet.chain.mine_blocks(11);
+    wallet.scan();
+
+    let to = wallet::derive_address_from_seed(&SEED, 1);
+    let target = wallet.chain.tip_height().unwrap() as u32 + 1;
+    let options = TransactionOptions::default();
+    let max = wallet::max_sendable(&wallet.db_data, 0, &to, None, options).unwrap();
+    let id_tx = wallet::sweep_to_address(
+        &wallet.db_data,
+        wallet::branch_id_for_height(target),
+        0,
+        &wallet.extsks[0],
+        &to,
+        b"everything",
+        None,
+        options,
+        &spend_params,
+        &output_params,
+    )
+    .unwrap();
+    assert_eq!(wallet.balance(0), 0);
+
+    // The recipient gets everything but the fee, and nothing comes back as change.
+    let db = Connection::open(&wallet.db_data).unwrap();
+    let sent: i64 = db
+        .query_row(
+            "SELECT SUM(value) FROM sent_notes WHERE tx = ?",
+            &[id_tx],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(sent, max.value);
+    let raw: Vec<u8> = db
+        .query_row(
+            "SELECT raw FROM transactions WHERE id_tx = ?",
+            &[id_tx],
+            |row| row.get(0),
+        )
+        .unwrap();
+    let parsed = wallet::parse_transaction_for_wallet(&raw, &wallet.db_data).unwrap();
+    assert!(parsed
+        .decrypted_outputs
+        .unwrap()
+        .iter()
+        .all(|output| output.account != 0 || output.outgoing));
+}
+
+#[test]
+fn history_is_paged_and_filtered() {
+    let mut wallet = TestWallet::new();
+    let (extfvk0, extfvk1) = (wallet.extfvk(0), wallet.extfvk(1));
+
+    let note = wallet.chain.receive(&extfvk0, 100_000).unwrap();
+    wallet.chain.mine();
+    let first = wallet.chain.tip_height().unwrap() as u32;
+    wallet.chain.receive(&extfvk1, 20_000).unwrap();
+    wallet.chain.receive(&extfvk0, 30_000).unwrap();
+    wallet.chain.mine();
+    wallet.chain.spend(&note.nf);
+    wallet.chain.mine();
+    let last = wallet.chain.tip_height().unwrap() as u32;
+    wallet.scan();
+
+    let history = |filter: &wallet::HistoryFilter, offset, limit| {
+        wallet::transaction_history(&wallet.db_data, 0, last, filter, offset, limit).unwrap()
+    };
+    let all = wallet::HistoryFilter::default();
+    let page = history(&all, 0, 10);
+    assert_eq!(page.total, 3);
+    let values: Vec<i64> = page.entries.iter().map(|entry| entry.net_value).collect();
+    assert_eq!(values, vec![-100_000, 30_000, 100_000]);
+    assert_eq!(page.entries[0].direction, wallet::Direction::Sent);
+    assert_eq!(page.entries[0].mined_height, Some(last));
+    assert!(page.entries[0].block_time.is_some());
+    let received = &page.entries[2];
+    assert_eq!(received.direction, wallet::Direction::Received);
+    assert_eq!(received.status, wallet::TransactionStatus::Mined);
+    assert_eq!(received.memo_status, wallet::MemoStatus::None);
+    assert_eq!(
+        received.addresses,
+        vec![wallet::derive_address_from_seed(&SEED, 0)]
+    );
+
+    // Pages continue where the previous one stopped.
+    let page = history(&all, 1, 1);
+    assert_eq!(page.total, 3);
+    assert_eq!(page.offset, 1);
+    assert_eq!(page.entries.len(), 1);
+    assert_eq!(page.entries[0].net_value, 30_000);
+
+    let page = history(
+        &wallet::HistoryFilter {
+            direction: Some(wallet::Direction::Received),
+            max_height: Some(first),
+            ..Default::default()
+        },
+        0,
+        10,
+    );
+    assert_eq!(page.total, 1);
+    assert_eq!(page.entries[0].net_value, 100_000);
+
+    let page = history(
+        &wallet::HistoryFilter {
+            direction: Some(wallet::Direction::Sent),
+            ..Default::default()
+        },
+        0,
+        10,
+    );
+    assert_eq!(page.total, 1);
+    assert_eq!(page.entries[0].net_value, -100_000);
+
+    // The total counts the matches on every page.
+    let page = history(
+        &wallet::HistoryFilter {
+            min_height: Some(first + 1),
+            ..Default::default()
+        },
+        1,
+        1,
+    );
+    assert_eq!(page.total, 2);
+    assert_eq!(page.entries.len(), 1);
+    assert_eq!(page.entries[0].net_value, 30_000);
+
+    let page = history(
+        &wallet::HistoryFilter {
+            memo: Some("rent".to_owned()),
+            ..Default::default()
+        },
+        0,
+        10,
+    );
+    assert_eq!(page.total, 0);
+
+    // The other account only sees its own transaction.
+    let page = wallet::transaction_history(&wallet.db_data, 1, last, &all, 0, 10).unwrap();
+    assert_eq!(page.total, 1);
+    assert_eq!(page.entries[0].net_value, 20_000);
+}